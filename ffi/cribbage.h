@@ -0,0 +1,104 @@
+/* The C interface of the cribbage library; written by cribbage-ffi-header, do not edit */
+
+#ifndef CRIBBAGE_H
+#define CRIBBAGE_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Status codes returned when processing an event */
+#define CRIBBAGE_OK 0 /* The event was accepted */
+#define CRIBBAGE_IMPLEMENTATION_ERROR 1 /* Error::ImplementationError */
+#define CRIBBAGE_EXPECTED_EVENT 2 /* Error::ExpectedEvent */
+#define CRIBBAGE_GAME_START_INVALID_CONFIG 3 /* Error::GameStartInvalidConfig */
+#define CRIBBAGE_INITIAL_CUT_ERROR 4 /* Error::InitialCutError */
+#define CRIBBAGE_DISCARD_ERRORS 5 /* Error::DiscardErrors */
+#define CRIBBAGE_NIBS_CALL_ERROR 6 /* Error::NibsCallError */
+#define CRIBBAGE_PLAY_WAIT_FOR_CARD_ERROR 7 /* Error::PlayWaitForCardError */
+#define CRIBBAGE_GO_DECISION_ERROR 8 /* Error::GoDecisionError */
+#define CRIBBAGE_AUCTION_ERROR 9 /* Error::AuctionError */
+#define CRIBBAGE_PLAY_SCORE_ERROR 10 /* Error::PlayScoreError */
+#define CRIBBAGE_SHOW_SCORE_ERROR 11 /* Error::ShowScoreError */
+#define CRIBBAGE_CRIB_SCORE_ERROR 12 /* Error::CribScoreError */
+#define CRIBBAGE_MATCH_IS_OVER 13 /* Error::MatchIsOver */
+#define CRIBBAGE_TIME_HAS_NOT_RUN_OUT 14 /* Error::TimeHasNotRunOut */
+#define CRIBBAGE_NOT_YOUR_TURN 15 /* Error::NotYourTurn */
+#define CRIBBAGE_SEAT_NOT_ALLOWED 16 /* Error::SeatNotAllowed */
+#define CRIBBAGE_UNIMPLEMENTED_STATE 17 /* Error::UnimplementedState */
+#define CRIBBAGE_UNRECOGNIZED_STATE 18 /* Error::UnrecognizedState */
+#define CRIBBAGE_MUGGINS_ERROR 19 /* Error::MugginsError */
+#define CRIBBAGE_NULL_POINTER 100 /* A pointer passed was NULL */
+#define CRIBBAGE_INVALID_JSON 101 /* The JSON passed is not UTF-8 or not a GameEvent */
+#define CRIBBAGE_INVALID_EVENT 102 /* The CribbageEvent passed has an unknown kind or too many discards */
+#define CRIBBAGE_PANIC 103 /* The library panicked; the game should be destroyed */
+
+/* Kinds of CribbageEvent */
+#define CRIBBAGE_EVENT_CONFIRMATION 0 /* GameEvent::Confirmation */
+#define CRIBBAGE_EVENT_DENIAL 1 /* GameEvent::Denial */
+#define CRIBBAGE_EVENT_TIMEOUT 2 /* GameEvent::Timeout */
+#define CRIBBAGE_EVENT_PLAY_CARD 3 /* GameEvent::Play(PlayTurn::CardSelected(value)) */
+#define CRIBBAGE_EVENT_GO 4 /* GameEvent::Play(PlayTurn::Go) */
+#define CRIBBAGE_EVENT_BID 5 /* GameEvent::Bid(value) */
+#define CRIBBAGE_EVENT_PASS 6 /* GameEvent::Pass */
+#define CRIBBAGE_EVENT_PEG_ONE 7 /* GameEvent::GoDecision(GoDecision::PegOne) */
+#define CRIBBAGE_EVENT_BACK_UP_TEN 8 /* GameEvent::GoDecision(GoDecision::BackUpTen(value)) */
+#define CRIBBAGE_EVENT_DISCARD 9 /* GameEvent::DiscardSelection with the discards of the seat sending it */
+#define CRIBBAGE_EVENT_NO_MUGGINS 10 /* GameEvent::Muggins(None) */
+
+#define CRIBBAGE_MAX_DISCARDS 4
+#define CRIBBAGE_ANY_SEAT 255
+
+typedef struct CribbageGame CribbageGame;
+
+typedef struct CribbageEvent {
+    uint8_t kind;
+    uint8_t value;
+    uint8_t num_discards;
+    uint8_t discards[CRIBBAGE_MAX_DISCARDS];
+} CribbageEvent;
+
+/* Makes a game to be freed with cribbage_game_destroy */
+CribbageGame *cribbage_game_create(void);
+
+/* Makes a game whose every shuffle is decided by the seed */
+CribbageGame *cribbage_game_create_from_seed(uint64_t seed);
+
+void cribbage_game_destroy(CribbageGame *game);
+
+/* Processes an event from a seat, or from CRIBBAGE_ANY_SEAT; returns a status code */
+int cribbage_game_process_event(CribbageGame *game, uint8_t seat, const CribbageEvent *event);
+
+/* Processes the JSON of a GameEvent from a seat, or from CRIBBAGE_ANY_SEAT; returns a status code */
+int cribbage_game_process_event_json(CribbageGame *game, uint8_t seat, const char *event_json);
+
+/* Returns the Success or Error of the last event processed as JSON, or NULL before any event */
+char *cribbage_game_last_result_json(const CribbageGame *game);
+
+/* Returns the SeatView of a seat as JSON, or NULL when the seat is not at the table */
+char *cribbage_game_view_json(const CribbageGame *game, uint8_t seat);
+
+/* Returns the sequence number of the last accepted event, or zero */
+uint64_t cribbage_game_sequence(const CribbageGame *game);
+
+int cribbage_game_num_players(const CribbageGame *game);
+
+/* Returns the player index of the dealer, or -1 before the first dealer is cut for */
+int cribbage_game_index_dealer(const CribbageGame *game);
+
+/* Returns the player index of the player playing or scoring, or -1 when there is none */
+int cribbage_game_index_active(const CribbageGame *game);
+
+/* Returns the milliseconds left for the decision being waited on, or -1 without a time limit */
+int64_t cribbage_game_time_remaining(const CribbageGame *game);
+
+/* Frees a string returned by the library */
+void cribbage_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CRIBBAGE_H */
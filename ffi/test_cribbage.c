@@ -0,0 +1,111 @@
+/*
+ * Plays the opening of a game through the C interface of the cribbage library
+ *
+ * Build and run from the root of the crate with:
+ *   cargo build
+ *   cc -Wall -Wextra -o target/test_cribbage ffi/test_cribbage.c -Iffi -Ltarget/debug -lcribbage
+ *   LD_LIBRARY_PATH=target/debug target/test_cribbage
+ */
+
+#include "cribbage.h"
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+static const char *GAME_SETUP =
+    "{\"GameSetup\":{\"variant\":\"TwoStandard\",\"victor_dealer_option\":\"TwoPlayers\","
+    "\"is_manual_scoring\":false,\"is_underpegging\":false,\"is_muggins\":false,"
+    "\"is_lowball\":false,\"is_back_up_ten\":false,\"auction\":null,\"skunk_lines\":null,"
+    "\"jokers\":null,\"scoring_rules\":{\"is_run_across_reset\":false,\"is_go_worth_two\":false,"
+    "\"is_crib_four_flush\":false,\"is_seven_card_heels\":true,\"nineteen_bonus\":0}}}";
+
+static int failures = 0;
+static uint64_t num_accepted = 0;
+
+static void check(int is_ok, const char *what) {
+    if (!is_ok) {
+        fprintf(stderr, "FAILED: %s\n", what);
+        failures++;
+    }
+}
+
+/* Checks that an event was accepted and counts it */
+static void check_ok(int status, const char *what) {
+    check(status == CRIBBAGE_OK, what);
+    if (status == CRIBBAGE_OK) {
+        num_accepted++;
+    }
+}
+
+static CribbageEvent event(uint8_t kind, uint8_t value) {
+    CribbageEvent event;
+    memset(&event, 0, sizeof event);
+    event.kind = kind;
+    event.value = value;
+    return event;
+}
+
+int main(void) {
+    CribbageGame *game = cribbage_game_create_from_seed(7);
+    check(game != NULL, "a game is created");
+
+    check_ok(cribbage_game_process_event_json(game, CRIBBAGE_ANY_SEAT, GAME_SETUP),
+          "the game is set up with JSON");
+    check(cribbage_game_num_players(game) == 2, "two players are seated");
+    check(cribbage_game_process_event_json(game, 0, "{\"Play\":") == CRIBBAGE_INVALID_JSON,
+          "bad JSON is rejected");
+
+    CribbageEvent confirmation = event(CRIBBAGE_EVENT_CONFIRMATION, 0);
+    check(cribbage_game_process_event(game, 2, &confirmation) == CRIBBAGE_SEAT_NOT_ALLOWED,
+          "a seat not at the table is rejected");
+    char *last_result = cribbage_game_last_result_json(game);
+    check(last_result != NULL && strcmp(last_result, "{\"Err\":\"SeatNotAllowed\"}") == 0,
+          "the last result is the error");
+    cribbage_string_free(last_result);
+
+    while (cribbage_game_index_dealer(game) == -1) {
+        check_ok(cribbage_game_process_event(game, 0, &confirmation),
+              "the players cut for the deal");
+    }
+    uint8_t dealer = (uint8_t)cribbage_game_index_dealer(game);
+    uint8_t pone = 1 - dealer;
+    check(cribbage_game_process_event(game, pone, &confirmation) == CRIBBAGE_NOT_YOUR_TURN,
+          "only the dealer deals");
+    check_ok(cribbage_game_process_event(game, dealer, &confirmation), "the deal");
+    check_ok(cribbage_game_process_event(game, dealer, &confirmation), "the sort");
+
+    /* Each seat discards its first two cards */
+    CribbageEvent discard = event(CRIBBAGE_EVENT_DISCARD, 0);
+    discard.num_discards = 2;
+    discard.discards[0] = 0;
+    discard.discards[1] = 1;
+    check_ok(cribbage_game_process_event(game, dealer, &discard),
+          "the dealer discards");
+    check_ok(cribbage_game_process_event(game, pone, &discard), "the pone discards");
+    check_ok(cribbage_game_process_event(game, pone, &confirmation),
+          "the pone cuts the starter");
+    check(cribbage_game_index_active(game) == pone, "the pone leads");
+
+    CribbageEvent play = event(CRIBBAGE_EVENT_PLAY_CARD, 0);
+    check(cribbage_game_process_event(game, dealer, &play) == CRIBBAGE_NOT_YOUR_TURN,
+          "the dealer may not lead");
+    check_ok(cribbage_game_process_event(game, pone, &play), "the pone plays");
+    check(cribbage_game_time_remaining(game) == -1, "there is no time limit");
+
+    char *view = cribbage_game_view_json(game, pone);
+    check(view != NULL && strstr(view, "\"state\":\"PlayWaitForCard\"") != NULL,
+          "the view shows the play");
+    cribbage_string_free(view);
+    check(cribbage_game_view_json(game, 2) == NULL, "there is no view for a seat not at the table");
+    check(cribbage_game_sequence(game) == num_accepted, "every accepted event is numbered");
+
+    cribbage_game_destroy(game);
+
+    if (failures > 0) {
+        fprintf(stderr, "%d checks failed\n", failures);
+        return EXIT_FAILURE;
+    }
+    printf("All checks passed\n");
+    return EXIT_SUCCESS;
+}
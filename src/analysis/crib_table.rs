@@ -0,0 +1,216 @@
+use crate::deck;
+use crate::score;
+use crate::strategy;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use serde::Serialize;
+
+// Tables of the expected crib total for every pair of discarded values in a two player game of
+// six card cribbage, both when discarding into your own crib and into the opponent's
+//
+// The tables are simulated once with a seed and then queried as often as needed. The opponent's
+// two discards are made by a strategy, which makes the difference between the two tables: a
+// sensible pone throws cards that are poor for the dealer's crib and a sensible dealer throws
+// cards that are good for it
+
+#[cfg(test)]
+mod test {
+    use super::CribTable;
+    use crate::deck::CardValue;
+
+    #[test]
+    fn random_discards() {
+        let table = CribTable::with_random_discards(200, 5);
+
+        assert_eq!(table.samples_per_pair, 200);
+        assert_eq!(
+            table.expected(CardValue::Two, CardValue::Three, true),
+            table.expected(CardValue::Three, CardValue::Two, true)
+        );
+        assert_eq!(table.expected(CardValue::Joker, CardValue::Two, true), None);
+        assert_eq!(
+            table.discard_value(CardValue::Five, CardValue::Joker, false),
+            None
+        );
+        // A pair of fives is the best throw and a king with a nine one of the worst
+        assert!(
+            table
+                .expected(CardValue::Five, CardValue::Five, true)
+                .unwrap()
+                > table
+                    .expected(CardValue::King, CardValue::Nine, true)
+                    .unwrap()
+                    + 3.0
+        );
+        assert_eq!(
+            table.discard_value(CardValue::Five, CardValue::Five, false),
+            table
+                .expected(CardValue::Five, CardValue::Five, false)
+                .map(|expected| -expected)
+        );
+
+        // The opponent's discards don't depend on whose crib it is so the tables only differ by
+        // chance
+        for first in &[CardValue::Ace, CardValue::Seven, CardValue::Jack] {
+            for second in &[CardValue::Four, CardValue::Eight, CardValue::Queen] {
+                let own = table.expected(*first, *second, true).unwrap();
+                let opponent = table.expected(*first, *second, false).unwrap();
+                assert!(own > 0.0 && own < 29.0);
+                assert!((own - opponent).abs() < 1.5);
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_table() {
+        assert_eq!(
+            CribTable::with_random_discards(5, 1),
+            CribTable::with_random_discards(5, 1)
+        );
+    }
+
+    // A greedy pone keeps points out of the dealer's crib and a greedy dealer adds to it
+    #[test]
+    fn strategy_discards() {
+        let mut strategy = crate::strategy::GreedyStrategy::new();
+        let table = CribTable::with_strategy_discards(&mut strategy, 2, 5);
+
+        let mean = |table: &Vec<Vec<f64>>| table.iter().flatten().sum::<f64>() / 169.0;
+        assert!(mean(&table.own) < mean(&table.opponent));
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CribTable {
+    // own[a][b] is the expected total of your own crib when discarding cards of the values a and b
+    // where 0 is an ace and 12 a king; each table is symmetric
+    pub own: Vec<Vec<f64>>,
+    // The same for discarding into the opponent's crib
+    pub opponent: Vec<Vec<f64>>,
+    // The number of simulated cribs behind each entry
+    pub samples_per_pair: u32,
+}
+
+impl CribTable {
+    // Builds the tables with an opponent who discards two of their six cards at random
+    pub fn with_random_discards(samples_per_pair: u32, seed: u64) -> CribTable {
+        let mut strategy = strategy::RandomStrategy::new(seed);
+        CribTable::with_strategy_discards(&mut strategy, samples_per_pair, seed)
+    }
+
+    // Builds the tables with an opponent whose discards are chosen by the given strategy
+    pub fn with_strategy_discards(
+        strategy: &mut dyn strategy::Strategy,
+        samples_per_pair: u32,
+        seed: u64,
+    ) -> CribTable {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut cards: Vec<deck::Card> = (0..52)
+            .map(|code| super::card(code / 4, code % 4))
+            .collect();
+
+        let mut own = vec![vec![0.0; 13]; 13];
+        let mut opponent = vec![vec![0.0; 13]; 13];
+        for first in 0..13 {
+            for second in first..13 {
+                for is_own_crib in &[true, false] {
+                    let mut total = 0;
+                    for _ in 0..samples_per_pair {
+                        cards.shuffle(&mut rng);
+                        total +=
+                            simulate_crib(&cards, first, second, *is_own_crib, strategy) as u64;
+                    }
+                    let expected = total as f64 / f64::from(samples_per_pair.max(1));
+
+                    let table = if *is_own_crib {
+                        &mut own
+                    } else {
+                        &mut opponent
+                    };
+                    table[first][second] = expected;
+                    table[second][first] = expected;
+                }
+            }
+        }
+
+        CribTable {
+            own,
+            opponent,
+            samples_per_pair,
+        }
+    }
+
+    // Returns the expected total of the crib when discarding cards of the given values into it; None
+    // when either card is a joker as the tables are simulated with a standard deck
+    pub fn expected(
+        &self,
+        first: deck::CardValue,
+        second: deck::CardValue,
+        is_own_crib: bool,
+    ) -> Option<f64> {
+        if first == deck::CardValue::Joker || second == deck::CardValue::Joker {
+            return None;
+        }
+        let table = if is_own_crib {
+            &self.own
+        } else {
+            &self.opponent
+        };
+        Some(table[first as usize][second as usize])
+    }
+
+    // Returns the expected points gained by the discarding player from the crib, which are lost
+    // when the crib belongs to the opponent
+    pub fn discard_value(
+        &self,
+        first: deck::CardValue,
+        second: deck::CardValue,
+        is_own_crib: bool,
+    ) -> Option<f64> {
+        let expected = self.expected(first, second, is_own_crib)?;
+        if is_own_crib {
+            Some(expected)
+        } else {
+            Some(-expected)
+        }
+    }
+}
+
+// Returns the total of one crib holding a card of each of the given values, taking their suits,
+// the discarding player's four other cards, the opponent's hand and the starter from the order of
+// the shuffled cards
+fn simulate_crib(
+    cards: &[deck::Card],
+    first: usize,
+    second: usize,
+    is_own_crib: bool,
+    strategy: &mut dyn strategy::Strategy,
+) -> u8 {
+    let value = |card: &deck::Card| deck::return_value(*card) as usize - 1;
+    let first_position = cards.iter().position(|card| value(card) == first).unwrap();
+    let second_position = cards
+        .iter()
+        .enumerate()
+        .position(|(position, card)| position != first_position && value(card) == second)
+        .unwrap();
+
+    let mut remaining = cards
+        .iter()
+        .enumerate()
+        .filter(|(position, _)| *position != first_position && *position != second_position)
+        .map(|(_, card)| *card)
+        .skip(4);
+    let opponent_hand: Vec<deck::Card> = remaining.by_ref().take(6).collect();
+    let starter = remaining.next().unwrap();
+
+    let mut crib = vec![cards[first_position], cards[second_position]];
+    for index in strategy.choose_discard(&opponent_hand, 2, !is_own_crib) {
+        crib.push(opponent_hand[index as usize]);
+    }
+
+    score::show::score_hand(0, &crib, starter, true)
+        .iter()
+        .map(|score_event| score_event.point_value)
+        .sum()
+}
@@ -0,0 +1,40 @@
+pub mod crib_table;
+pub mod stats;
+
+use crate::deck;
+
+// Tables computed from the scoring functions for use in teaching material, discard advice and
+// computer players
+
+// Every value and suit in the order of their indices such that a card can be built from a pair of
+// indices when enumerating hands
+const VALUES: [deck::CardValue; 13] = [
+    deck::CardValue::Ace,
+    deck::CardValue::Two,
+    deck::CardValue::Three,
+    deck::CardValue::Four,
+    deck::CardValue::Five,
+    deck::CardValue::Six,
+    deck::CardValue::Seven,
+    deck::CardValue::Eight,
+    deck::CardValue::Nine,
+    deck::CardValue::Ten,
+    deck::CardValue::Jack,
+    deck::CardValue::Queen,
+    deck::CardValue::King,
+];
+
+const SUITS: [deck::CardSuit; 4] = [
+    deck::CardSuit::Hearts,
+    deck::CardSuit::Diamonds,
+    deck::CardSuit::Clubs,
+    deck::CardSuit::Spades,
+];
+
+// Returns the card with the given value index from 0 for an ace to 12 for a king and suit index
+fn card(value: usize, suit: usize) -> deck::Card {
+    deck::Card {
+        value: VALUES[value],
+        suit: SUITS[suit],
+    }
+}
@@ -0,0 +1,517 @@
+use crate::score;
+use serde::Serialize;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+// Scores every possible hand of four cards with a starter, as a hand and as a crib, and collects
+// the distribution of totals, the most common hands by what they score and the average totals by
+// rank
+//
+// Relabelling the suits of a hand never changes its score so only one hand of each set of hands
+// that differ by a relabelling of suits is considered, weighted by the number of hands in the set.
+// That cuts the almost thirteen million hands down to around 650 thousand. What those hands score
+// depends only on their values and whether they hold a flush or nobs, so each such combination is
+// scored once, leaving around 45 thousand hands to score
+
+#[cfg(test)]
+mod test {
+    use super::{HandPattern, ScoreDistribution};
+    use crate::util::return_card;
+    use std::collections::HashMap;
+
+    #[test]
+    fn distribution() {
+        let mut distribution = ScoreDistribution::new();
+        distribution.frequencies[0] = 1;
+        distribution.frequencies[2] = 3;
+        distribution.frequencies[4] = 3;
+        distribution.frequencies[29] = 1;
+
+        assert_eq!(distribution.num_hands(), 8);
+        assert_eq!(distribution.probability(2), 0.375);
+        assert_eq!(distribution.mean(), (6.0 + 12.0 + 29.0) / 8.0);
+        assert_eq!(distribution.most_common(3), vec![(2, 3), (4, 3), (0, 1)]);
+        assert_eq!(distribution.impossible_totals().len(), 26);
+        assert_eq!(distribution.impossible_totals()[..3], [1, 3, 5]);
+    }
+
+    #[test]
+    fn hand_patterns() {
+        let pattern = |hand: [(char, char); 4], starter: (char, char)| {
+            let hand: Vec<crate::deck::Card> = hand
+                .iter()
+                .map(|(value, suit)| return_card(*value, *suit))
+                .collect();
+            let starter = return_card(starter.0, starter.1);
+            HandPattern::from_score_events(&crate::score::show::score_hand(
+                0, &hand, starter, false,
+            ))
+        };
+
+        let twenty_nine = pattern([('5', 'H'), ('5', 'D'), ('5', 'C'), ('J', 'S')], ('5', 'S'));
+        assert_eq!(
+            twenty_nine,
+            HandPattern {
+                fifteens: 8,
+                pairs: 6,
+                runs: vec![],
+                flush: 0,
+                nobs: true,
+            }
+        );
+        assert_eq!(twenty_nine.total(), 29);
+
+        // A double run of three counts both runs, longest first, and the pair
+        let double_run = pattern([('3', 'H'), ('4', 'D'), ('4', 'C'), ('5', 'S')], ('K', 'H'));
+        assert_eq!(double_run.runs, vec![3, 3]);
+        assert_eq!(double_run.pairs, 1);
+        assert_eq!(double_run.total(), 10);
+
+        let flush = pattern([('2', 'H'), ('4', 'H'), ('6', 'H'), ('Q', 'H')], ('8', 'S'));
+        assert_eq!(flush.flush, 4);
+        assert_eq!(flush.total(), 4);
+        assert_eq!(HandPattern::from_score_events(&[]).total(), 0);
+    }
+
+    #[test]
+    fn patterns_by_frequency() {
+        let mut tally = HashMap::new();
+        let pair = HandPattern {
+            pairs: 1,
+            ..HandPattern::from_score_events(&[])
+        };
+        let fifteen = HandPattern {
+            fifteens: 1,
+            ..HandPattern::from_score_events(&[])
+        };
+        tally.insert(HandPattern::from_score_events(&[]), 5);
+        tally.insert(pair.clone(), 9);
+        tally.insert(fifteen.clone(), 9);
+
+        // Equally common patterns are listed with the lower pattern first
+        assert_eq!(super::by_frequency(tally)[..2], [(pair, 9), (fifteen, 9)]);
+    }
+
+    // Checked against the widely published table of hand frequencies
+    #[test]
+    fn every_hand() {
+        let stats = super::enumerate_hands();
+
+        assert_eq!(stats.hand.num_hands(), 12_994_800);
+        assert_eq!(stats.crib.num_hands(), 12_994_800);
+        assert!(stats.hands_scored < 12_994_800 / 250);
+
+        assert_eq!(stats.hand.frequencies[0], 1_009_008);
+        assert_eq!(stats.hand.frequencies[28], 76);
+        assert_eq!(stats.hand.frequencies[29], 4);
+        assert_eq!(stats.hand.frequencies[24], 3_680);
+        assert_eq!(
+            stats.hand.most_common(2),
+            vec![(4, 2_855_676), (2, 2_813_796)]
+        );
+        assert_eq!(
+            stats
+                .hand_patterns
+                .iter()
+                .map(|(_, frequency)| frequency)
+                .sum::<u64>(),
+            12_994_800
+        );
+        // A single pair is the most common hand, ahead of fifteen four
+        let pair = HandPattern {
+            pairs: 1,
+            ..HandPattern::from_score_events(&[])
+        };
+        assert_eq!(stats.hand_patterns[0], (pair, 1_668_708));
+        assert_eq!(stats.hand_patterns[1].0.fifteens, 2);
+        assert!((stats.hand.mean() - 4.769).abs() < 0.001);
+
+        assert_eq!(stats.hand.impossible_totals(), vec![19, 25, 26, 27]);
+        assert_eq!(stats.crib.impossible_totals(), vec![19, 25, 26, 27]);
+
+        // The crib loses only the flushes of the four cards alone
+        assert!(stats.crib.mean() < stats.hand.mean());
+        assert_eq!(stats.crib.frequencies[29], 4);
+
+        // Fives are worth the most whether held or cut
+        let best = |means: &[f64]| {
+            (0..13)
+                .max_by(|a, b| means[*a].partial_cmp(&means[*b]).unwrap())
+                .unwrap()
+        };
+        assert_eq!(best(&stats.hand_mean_by_starter), 4);
+        assert_eq!(best(&stats.hand_mean_by_held), 4);
+    }
+}
+
+// The highest total that a hand of four cards and the starter can score
+pub const MAX_TOTAL: u8 = 29;
+
+// The number of ways to relabel the four suits
+const NUM_SUIT_PERMUTATIONS: u64 = 24;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScoreDistribution {
+    // frequencies[n] is the number of hands that score exactly n points
+    pub frequencies: Vec<u64>,
+}
+
+impl ScoreDistribution {
+    fn new() -> ScoreDistribution {
+        ScoreDistribution {
+            frequencies: vec![0; MAX_TOTAL as usize + 1],
+        }
+    }
+
+    pub fn num_hands(&self) -> u64 {
+        self.frequencies.iter().sum()
+    }
+
+    // Returns the chance of a hand scoring the given total
+    pub fn probability(&self, total: u8) -> f64 {
+        self.frequencies[total as usize] as f64 / self.num_hands() as f64
+    }
+
+    pub fn mean(&self) -> f64 {
+        let points: u64 = self
+            .frequencies
+            .iter()
+            .enumerate()
+            .map(|(total, frequency)| total as u64 * frequency)
+            .sum();
+        points as f64 / self.num_hands() as f64
+    }
+
+    // Returns every total up to the maximum that no hand scores; as every hand has been scored this
+    // is a proof by exhaustion that those totals can't be made
+    pub fn impossible_totals(&self) -> Vec<u8> {
+        (0..=MAX_TOTAL)
+            .filter(|total| self.frequencies[*total as usize] == 0)
+            .collect()
+    }
+
+    // Returns the given number of totals with the most hands and their frequencies, most common
+    // first and the lower total first when two are equally common
+    pub fn most_common(&self, count: usize) -> Vec<(u8, u64)> {
+        let mut totals: Vec<(u8, u64)> = (0..=MAX_TOTAL)
+            .map(|total| (total, self.frequencies[total as usize]))
+            .collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        totals.truncate(count);
+        totals
+    }
+}
+
+// What a hand scores for ignoring which cards score it, such as two fifteens and a pair; hands are
+// counted as the same hand when they score in the same way
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct HandPattern {
+    pub fifteens: u8,
+    // Each two cards of the same value; a pair royal is three pairs and a double pair royal six
+    pub pairs: u8,
+    // The length of each run, longest first; a double run is two runs
+    pub runs: Vec<u8>,
+    // The number of cards in a flush, if there is one
+    pub flush: u8,
+    pub nobs: bool,
+}
+
+impl HandPattern {
+    pub fn from_score_events(score_events: &[score::ScoreEvent]) -> HandPattern {
+        let mut pattern = HandPattern {
+            fifteens: 0,
+            pairs: 0,
+            runs: Vec::new(),
+            flush: 0,
+            nobs: false,
+        };
+        for score_event in score_events {
+            match &score_event.score_type {
+                score::ScoreType::Show(score::ShowScoreType::Fifteen(_)) => pattern.fifteens += 1,
+                score::ScoreType::Show(score::ShowScoreType::Pair(_)) => pattern.pairs += 1,
+                score::ScoreType::Show(score::ShowScoreType::Triple(_)) => pattern.pairs += 3,
+                score::ScoreType::Show(score::ShowScoreType::Quadruple(_)) => pattern.pairs += 6,
+                score::ScoreType::Show(score::ShowScoreType::Straight(cards)) => {
+                    pattern.runs.push(cards.len() as u8)
+                }
+                score::ScoreType::Show(score::ShowScoreType::FourFlush(_)) => pattern.flush = 4,
+                score::ScoreType::Show(score::ShowScoreType::FiveFlush(_)) => pattern.flush = 5,
+                score::ScoreType::Show(score::ShowScoreType::Nobs(_)) => pattern.nobs = true,
+                _ => {}
+            }
+        }
+        pattern.runs.sort_unstable_by(|a, b| b.cmp(a));
+        pattern
+    }
+
+    pub fn total(&self) -> u8 {
+        self.fifteens * 2
+            + self.pairs * 2
+            + self.runs.iter().sum::<u8>()
+            + self.flush
+            + self.nobs as u8
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HandStatistics {
+    pub hand: ScoreDistribution,
+    pub crib: ScoreDistribution,
+    // Every way a hand scores with the number of hands scoring that way, most common first
+    pub hand_patterns: Vec<(HandPattern, u64)>,
+    // The average hand total by the value of the starter from 0 for an ace to 12 for a king
+    pub hand_mean_by_starter: Vec<f64>,
+    // The average hand total of the hands holding at least one card of each value
+    pub hand_mean_by_held: Vec<f64>,
+    // The number of hands actually scored after the suit symmetry reduction and leaving out hands
+    // which score the same as one already scored
+    pub hands_scored: u64,
+}
+
+// Everything which decides what a hand scores: the values of the starter and the sorted hand,
+// the size of any flush and whether the hand holds nobs
+#[derive(PartialEq, Eq, Hash)]
+struct ScoreKey {
+    starter_value: u8,
+    hand_values: [u8; 4],
+    flush: u8,
+    nobs: bool,
+}
+
+// What the hands of a ScoreKey score, with the number of hands seen so far
+struct ScoredHand {
+    hand_total: usize,
+    crib_total: usize,
+    pattern: HandPattern,
+    weight: u64,
+}
+
+// Running totals of the enumeration
+struct Tally {
+    hand: ScoreDistribution,
+    crib: ScoreDistribution,
+    scored: HashMap<ScoreKey, ScoredHand>,
+    points_by_starter: Vec<u64>,
+    hands_by_starter: Vec<u64>,
+    points_by_held: Vec<u64>,
+    hands_by_held: Vec<u64>,
+    hands_scored: u64,
+}
+
+// Scores every hand of four cards with a starter; takes a second or two without optimisations
+pub fn enumerate_hands() -> HandStatistics {
+    // The starter is always of the first suit so a relabelling moving the first suit only gives
+    // larger hands and never needs trying
+    let permutations: Vec<[u8; 4]> = suit_permutations()
+        .into_iter()
+        .filter(|permutation| permutation[0] == 0)
+        .collect();
+    let mut tally = Tally {
+        hand: ScoreDistribution::new(),
+        crib: ScoreDistribution::new(),
+        scored: HashMap::new(),
+        points_by_starter: vec![0; 13],
+        hands_by_starter: vec![0; 13],
+        points_by_held: vec![0; 13],
+        hands_by_held: vec![0; 13],
+        hands_scored: 0,
+    };
+
+    // Cards are given as value * 4 + suit such that they sort in the same order as deck::Card.
+    // The starter can always be relabelled to the first suit
+    for starter_value in 0..13 {
+        let mut hand = Vec::with_capacity(4);
+        extend_hand(
+            starter_value * 4,
+            &mut hand,
+            0,
+            0,
+            &permutations,
+            &mut tally,
+        );
+    }
+
+    let means = |points: &[u64], hands: &[u64]| -> Vec<f64> {
+        points
+            .iter()
+            .zip(hands)
+            .map(|(points, hands)| *points as f64 / *hands as f64)
+            .collect()
+    };
+
+    let mut hand_patterns = HashMap::new();
+    for scored_hand in tally.scored.into_values() {
+        *hand_patterns.entry(scored_hand.pattern).or_insert(0) += scored_hand.weight;
+    }
+
+    HandStatistics {
+        hand_mean_by_starter: means(&tally.points_by_starter, &tally.hands_by_starter),
+        hand_mean_by_held: means(&tally.points_by_held, &tally.hands_by_held),
+        hand: tally.hand,
+        crib: tally.crib,
+        hand_patterns: by_frequency(hand_patterns),
+        hands_scored: tally.hands_scored,
+    }
+}
+
+// Returns the patterns most common first and the lower pattern first when two are equally common
+fn by_frequency(hand_patterns: HashMap<HandPattern, u64>) -> Vec<(HandPattern, u64)> {
+    let mut hand_patterns: Vec<(HandPattern, u64)> = hand_patterns.into_iter().collect();
+    hand_patterns.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    hand_patterns
+}
+
+// Adds every card from the code next onwards to the hand in turn until the hand is full. A card
+// may only take a suit that has already been used or the first suit not yet used, since any hand
+// introducing a later suit first can be relabelled to a smaller hand
+fn extend_hand(
+    starter: u8,
+    hand: &mut Vec<u8>,
+    next: u8,
+    max_suit: u8,
+    permutations: &[[u8; 4]],
+    tally: &mut Tally,
+) {
+    if hand.len() == 4 {
+        score_symmetry_class(starter, hand, permutations, tally);
+        return;
+    }
+
+    for code in next..52 {
+        let suit = code % 4;
+        if code == starter || suit > max_suit + 1 {
+            continue;
+        }
+        hand.push(code);
+        extend_hand(
+            starter,
+            hand,
+            code + 1,
+            max_suit.max(suit),
+            permutations,
+            tally,
+        );
+        hand.pop();
+    }
+}
+
+// Counts the hand once for each of the hands it can be relabelled to if it's the smallest of them,
+// scoring it unless a hand which scores the same has been scored already
+fn score_symmetry_class(starter: u8, hand: &[u8], permutations: &[[u8; 4]], tally: &mut Tally) {
+    let relabel =
+        |code: u8, permutation: &[u8; 4]| code - code % 4 + permutation[code as usize % 4];
+    let original = [starter, hand[0], hand[1], hand[2], hand[3]];
+
+    let mut num_symmetries = 0;
+    for permutation in permutations {
+        let mut image = [
+            relabel(starter, permutation),
+            relabel(hand[0], permutation),
+            relabel(hand[1], permutation),
+            relabel(hand[2], permutation),
+            relabel(hand[3], permutation),
+        ];
+        image[1..].sort_unstable();
+        match image.cmp(&original) {
+            std::cmp::Ordering::Less => return,
+            std::cmp::Ordering::Equal => num_symmetries += 1,
+            std::cmp::Ordering::Greater => {}
+        }
+    }
+    let weight = NUM_SUIT_PERMUTATIONS / num_symmetries;
+
+    let value = |code: u8| code / 4;
+    let suit = |code: u8| code % 4;
+    let is_flush = hand.iter().all(|code| suit(*code) == suit(hand[0]));
+    let key = ScoreKey {
+        starter_value: value(starter),
+        hand_values: [
+            value(hand[0]),
+            value(hand[1]),
+            value(hand[2]),
+            value(hand[3]),
+        ],
+        flush: match (is_flush, suit(starter) == suit(hand[0])) {
+            (true, true) => 5,
+            (true, false) => 4,
+            _ => 0,
+        },
+        // Jacks have the value index 10
+        nobs: hand
+            .iter()
+            .any(|code| value(*code) == 10 && suit(*code) == suit(starter)),
+    };
+    let key_flush = key.flush;
+    let scored_hand = match tally.scored.entry(key) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => {
+            tally.hands_scored += 1;
+            entry.insert(score_hand(starter, hand, key_flush))
+        }
+    };
+    scored_hand.weight += weight;
+    let hand_total = scored_hand.hand_total;
+
+    tally.hand.frequencies[hand_total] += weight;
+    tally.crib.frequencies[scored_hand.crib_total] += weight;
+
+    let starter_value = starter as usize / 4;
+    tally.points_by_starter[starter_value] += weight * hand_total as u64;
+    tally.hands_by_starter[starter_value] += weight;
+
+    // The hand is sorted so each value held is counted at its first card
+    for (position, code) in hand.iter().enumerate() {
+        let value = *code as usize / 4;
+        if position == 0 || hand[position - 1] as usize / 4 != value {
+            tally.points_by_held[value] += weight * hand_total as u64;
+            tally.hands_by_held[value] += weight;
+        }
+    }
+}
+
+// Scores the hand as a hand and as a crib given the size of its flush
+fn score_hand(starter: u8, hand: &[u8], flush: u8) -> ScoredHand {
+    let to_card = |code: u8| super::card(code as usize / 4, code as usize % 4);
+    let cards: Vec<crate::deck::Card> = hand.iter().map(|code| to_card(*code)).collect();
+    let starter_card = to_card(starter);
+
+    let total = |events: &[score::ScoreEvent]| -> usize {
+        events
+            .iter()
+            .map(|score_event| score_event.point_value as usize)
+            .sum()
+    };
+    let events = score::show::score_hand(0, &cards, starter_card, false);
+    let hand_total = total(&events);
+
+    // The crib differs from the hand only in not counting a flush of the four cards alone so it
+    // only needs scoring again when there is one
+    let crib_total = if flush == 4 {
+        total(&score::show::score_hand(0, &cards, starter_card, true))
+    } else {
+        hand_total
+    };
+
+    ScoredHand {
+        hand_total,
+        crib_total,
+        pattern: HandPattern::from_score_events(&events),
+        weight: 0,
+    }
+}
+
+// Returns every ordering of the four suit indices
+fn suit_permutations() -> Vec<[u8; 4]> {
+    let mut permutations = Vec::new();
+    for a in 0..4 {
+        for b in 0..4 {
+            for c in 0..4 {
+                if a != b && a != c && b != c {
+                    // The four indices add up to 6
+                    permutations.push([a, b, c, 6 - a - b - c]);
+                }
+            }
+        }
+    }
+    permutations
+}
@@ -0,0 +1,617 @@
+use crate::game_process_return;
+use crate::observer;
+use crate::score;
+use crate::settings;
+use crate::strategy;
+use serde::Serialize;
+use std::sync::{Arc, Mutex};
+
+// Plays many seeded games between strategies with automatic scoring and collects statistics such
+// that changes to a strategy or the rules can be evaluated offline
+//
+// Every seat passes in an auction, so the dealer keeps the crib, and under Back Up 10 moves the
+// opponent furthest ahead back unless nobody has pegged or the limit has been reached
+
+#[cfg(test)]
+mod test {
+    use super::ArenaConfig;
+
+    fn config(variant: crate::settings::RuleVariant, strategies: &[&str]) -> ArenaConfig {
+        ArenaConfig {
+            settings: super::default_settings(variant),
+            num_games: 3,
+            seed: 11,
+            strategies: strategies.iter().map(|name| name.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn every_variant_completes() {
+        for variant in crate::util::return_variants() {
+            let report = super::run(&config(variant, &["random"])).unwrap();
+            let num_players = crate::util::return_num_players_for_variant(variant) as usize;
+
+            assert_eq!(report.num_games, 3);
+            assert_eq!(report.seats.len(), num_players);
+            // Every game has a winner, and with partners both partners win
+            let wins: u32 = report.seats.iter().map(|seat| seat.wins).sum();
+            assert!(wins >= 3);
+            for seat in &report.seats {
+                assert!(seat.hands > 0);
+                assert!(seat.win_rate_low <= seat.win_rate && seat.win_rate <= seat.win_rate_high);
+            }
+        }
+    }
+
+    // A captain playing to 61 against two pairs, each dealt six cards with a two card discard
+    #[test]
+    fn custom_variant_completes() {
+        let variant = crate::settings::CustomVariant::new(5)
+            .partners(1, 3)
+            .partners(2, 4)
+            .cards_dealt(5)
+            .discards(1)
+            .captain_target_score(61)
+            .build()
+            .unwrap();
+        let report = super::run(&config(variant, &["greedy"])).unwrap();
+        assert_eq!(report.seats.len(), 5);
+        assert!(report.seats.iter().map(|seat| seat.wins).sum::<u32>() >= 3);
+
+        let variant = crate::settings::CustomVariant::new(3)
+            .target_score(91)
+            .build()
+            .unwrap();
+        let report = super::run(&config(variant, &["random"])).unwrap();
+        assert_eq!(report.seats.len(), 3);
+        assert!(report.seats.iter().map(|seat| seat.wins).sum::<u32>() >= 3);
+    }
+
+    // The rules are played as configured, such as a bonus for a nineteen raising the points of the
+    // show
+    #[test]
+    fn nineteen_bonus() {
+        let mut config = config(crate::settings::RuleVariant::TwoStandard, &["greedy"]);
+        config.num_games = 5;
+        let standard = super::run(&config).unwrap();
+        config.settings.scoring_rules.nineteen_bonus = 20;
+        let bonus = super::run(&config).unwrap();
+
+        let show = |report: &super::ArenaReport| {
+            report
+                .seats
+                .iter()
+                .map(|seat| seat.show_per_hand)
+                .sum::<f64>()
+        };
+        assert!(show(&bonus) > show(&standard) + 1.0);
+    }
+
+    // Games with the house rules which need a choice of the arena, an auction for the crib and Back
+    // Up 10, are played to their end
+    #[test]
+    fn house_rules_complete() {
+        let mut config = config(
+            crate::settings::RuleVariant::TwoStandard,
+            &["greedy", "random"],
+        );
+        config.settings.is_back_up_ten = true;
+        config.settings.scoring_rules.back_up_ten_limit = Some(20);
+        config.settings.auction = Some(crate::settings::AuctionRules {
+            minimum_bid: 1,
+            minimum_increment: 1,
+            maximum_bid: 30,
+        });
+        config.settings.skunk_lines = Some(crate::settings::SkunkLines {
+            skunk: 100,
+            double_skunk: 80,
+        });
+        config.settings.jokers = Some(crate::settings::JokerRules {
+            num_jokers: 2,
+            play_value: 10,
+        });
+        config.num_games = 1;
+        let report = super::run(&config).unwrap();
+        assert_eq!(report.seats.iter().map(|seat| seat.wins).sum::<u32>(), 1);
+    }
+
+    #[test]
+    fn manual_scoring() {
+        let mut config = config(crate::settings::RuleVariant::TwoStandard, &["greedy"]);
+        config.settings.is_manual_scoring = true;
+        assert_eq!(
+            super::run(&config).err(),
+            Some(super::ArenaError::ManualScoringIsNotPlayed)
+        );
+    }
+
+    #[test]
+    fn same_seed_same_report() {
+        let config = config(
+            crate::settings::RuleVariant::TwoStandard,
+            &["random", "greedy"],
+        );
+        let first = super::run(&config).unwrap();
+        let second = super::run(&config).unwrap();
+        assert_eq!(first.to_csv(), second.to_csv());
+        assert_eq!(first.seats[0].strategy, "random");
+        assert_eq!(first.seats[1].strategy, "greedy");
+    }
+
+    #[test]
+    fn unknown_strategy() {
+        assert_eq!(
+            super::run(&config(
+                crate::settings::RuleVariant::TwoStandard,
+                &["nobody"]
+            ))
+            .err(),
+            Some(super::ArenaError::UnknownStrategy(String::from("nobody")))
+        );
+    }
+
+    #[test]
+    fn wilson_interval() {
+        let (low, high) = super::wilson_interval(50, 100);
+        assert!((low - 0.4038).abs() < 0.001);
+        assert!((high - 0.5962).abs() < 0.001);
+        assert_eq!(super::wilson_interval(0, 0), (0.0, 1.0));
+    }
+}
+
+pub struct ArenaConfig {
+    // The rules every game is played with, which must use automatic scoring; see default_settings
+    pub settings: settings::GameSettings,
+    pub num_games: u32,
+    // Each game and strategy is seeded from this such that a run can be repeated exactly
+    pub seed: u64,
+    // The name of the strategy for each seat; when there are fewer names than seats the names are
+    // repeated around the table
+    pub strategies: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ArenaError {
+    UnknownStrategy(String),
+    NoStrategies,
+    // The strategies only make the choices of the play so every hand must be scored automatically
+    ManualScoringIsNotPlayed,
+    // A strategy chose an invalid event or the game could not continue; the seed of the game is
+    // given such that it can be replayed
+    GameError(u64, game_process_return::Error),
+}
+
+// Statistics for one seat over every game played
+#[derive(Debug, Clone, Serialize)]
+pub struct SeatReport {
+    pub seat: u8,
+    pub strategy: String,
+    // Games won by the seat or by their partner
+    pub wins: u32,
+    pub win_rate: f64,
+    // 95% Wilson score interval of the win rate
+    pub win_rate_low: f64,
+    pub win_rate_high: f64,
+    pub hands: u32,
+    // Average points scored by the seat per hand in the play, their own hand, and their crib
+    pub pegging_per_hand: f64,
+    pub show_per_hand: f64,
+    pub crib_per_hand: f64,
+    // Games lost by the seat while behind the skunk and double skunk lines
+    pub times_skunked: u32,
+    pub times_double_skunked: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ArenaReport {
+    pub variant: settings::RuleVariant,
+    pub num_games: u32,
+    pub seed: u64,
+    pub seats: Vec<SeatReport>,
+    // Fraction of games in which any loser was behind the skunk or double skunk line
+    pub skunk_rate: f64,
+    pub double_skunk_rate: f64,
+    // Fraction of games won by the first dealer or their partner
+    pub first_dealer_win_rate: f64,
+    // Average points per hand scored by the dealer and by each other seat
+    pub dealer_points_per_hand: f64,
+    pub non_dealer_points_per_hand: f64,
+}
+
+// Running totals for each seat
+#[derive(Default, Clone)]
+struct SeatTally {
+    wins: u32,
+    hands: u32,
+    pegging: u32,
+    show: u32,
+    crib: u32,
+    times_skunked: u32,
+    times_double_skunked: u32,
+}
+
+#[derive(Default)]
+struct Tally {
+    seats: Vec<SeatTally>,
+    skunks: u32,
+    double_skunks: u32,
+    first_dealer_wins: u32,
+    dealer_points: u32,
+    non_dealer_points: u32,
+    hands: u32,
+}
+
+// Plays every game of the given configuration and returns the statistics of the run
+pub fn run(config: &ArenaConfig) -> Result<ArenaReport, ArenaError> {
+    if config.strategies.is_empty() {
+        return Err(ArenaError::NoStrategies);
+    }
+    if config.settings.is_manual_scoring {
+        return Err(ArenaError::ManualScoringIsNotPlayed);
+    }
+
+    let num_players = num_players(config.settings.variant);
+    let mut tally = Tally {
+        seats: vec![SeatTally::default(); num_players],
+        ..Tally::default()
+    };
+
+    let mut strategy_names = Vec::with_capacity(num_players);
+    for game_index in 0..config.num_games {
+        let game_seed = config.seed.wrapping_add(u64::from(game_index));
+
+        let mut strategies: Vec<Box<dyn strategy::Strategy>> = Vec::with_capacity(num_players);
+        for seat in 0..num_players {
+            let name = &config.strategies[seat % config.strategies.len()];
+            let seat_seed = game_seed.wrapping_mul(31).wrapping_add(seat as u64);
+            match strategy::from_name(name, seat_seed) {
+                Some(strategy) => strategies.push(strategy),
+                None => return Err(ArenaError::UnknownStrategy(name.clone())),
+            }
+        }
+        if strategy_names.is_empty() {
+            strategy_names = strategies.iter().map(|strategy| strategy.name()).collect();
+        }
+
+        play_game(config.settings, game_seed, &mut strategies, &mut tally)
+            .map_err(|e| ArenaError::GameError(game_seed, e))?;
+    }
+
+    let num_games = config.num_games;
+    let rate = |count: u32, total: u32| {
+        if total == 0 {
+            0.0
+        } else {
+            f64::from(count) / f64::from(total)
+        }
+    };
+
+    let seats = tally
+        .seats
+        .iter()
+        .enumerate()
+        .map(|(seat, seat_tally)| {
+            let (win_rate_low, win_rate_high) = wilson_interval(seat_tally.wins, num_games);
+            SeatReport {
+                seat: seat as u8,
+                strategy: strategy_names.get(seat).cloned().unwrap_or_default(),
+                wins: seat_tally.wins,
+                win_rate: rate(seat_tally.wins, num_games),
+                win_rate_low,
+                win_rate_high,
+                hands: seat_tally.hands,
+                pegging_per_hand: rate(seat_tally.pegging, seat_tally.hands),
+                show_per_hand: rate(seat_tally.show, seat_tally.hands),
+                crib_per_hand: rate(seat_tally.crib, seat_tally.hands),
+                times_skunked: seat_tally.times_skunked,
+                times_double_skunked: seat_tally.times_double_skunked,
+            }
+        })
+        .collect();
+
+    Ok(ArenaReport {
+        variant: config.settings.variant,
+        num_games,
+        seed: config.seed,
+        seats,
+        skunk_rate: rate(tally.skunks, num_games),
+        double_skunk_rate: rate(tally.double_skunks, num_games),
+        first_dealer_win_rate: rate(tally.first_dealer_wins, num_games),
+        dealer_points_per_hand: rate(tally.dealer_points, tally.hands),
+        non_dealer_points_per_hand: rate(
+            tally.non_dealer_points,
+            tally.hands * (num_players as u32 - 1),
+        ),
+    })
+}
+
+impl ArenaReport {
+    // Returns the report as CSV with one row per seat; the statistics of the whole run are
+    // repeated on each row
+    pub fn to_csv(&self) -> String {
+        let mut output = String::from(
+            "variant,num_games,seed,seat,strategy,wins,win_rate,win_rate_low,win_rate_high,hands,\
+             pegging_per_hand,show_per_hand,crib_per_hand,times_skunked,times_double_skunked,\
+             skunk_rate,double_skunk_rate,first_dealer_win_rate,dealer_points_per_hand,\
+             non_dealer_points_per_hand\n",
+        );
+        for seat in &self.seats {
+            output.push_str(&format!(
+                "{:?},{},{},{},{},{},{:.4},{:.4},{:.4},{},{:.4},{:.4},{:.4},{},{},{:.4},{:.4},{:.4},{:.4},{:.4}\n",
+                self.variant,
+                self.num_games,
+                self.seed,
+                seat.seat,
+                seat.strategy,
+                seat.wins,
+                seat.win_rate,
+                seat.win_rate_low,
+                seat.win_rate_high,
+                seat.hands,
+                seat.pegging_per_hand,
+                seat.show_per_hand,
+                seat.crib_per_hand,
+                seat.times_skunked,
+                seat.times_double_skunked,
+                self.skunk_rate,
+                self.double_skunk_rate,
+                self.first_dealer_win_rate,
+                self.dealer_points_per_hand,
+                self.non_dealer_points_per_hand,
+            ));
+        }
+        output
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+}
+
+// Returns the 95% Wilson score interval for the given number of successes out of the given number
+// of trials
+fn wilson_interval(successes: u32, trials: u32) -> (f64, f64) {
+    if trials == 0 {
+        return (0.0, 1.0);
+    }
+
+    let z = 1.96;
+    let n = f64::from(trials);
+    let p = f64::from(successes) / n;
+    let denominator = 1.0 + z * z / n;
+    let centre = p + z * z / (2.0 * n);
+    let margin = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+
+    (
+        ((centre - margin) / denominator).max(0.0),
+        ((centre + margin) / denominator).min(1.0),
+    )
+}
+
+// Returns the standard rules of the variant with automatic scoring, to which house rules may be
+// added
+pub fn default_settings(variant: settings::RuleVariant) -> settings::GameSettings {
+    settings::GameSettings {
+        variant,
+        victor_dealer_option: victor_dealer_option(variant),
+        is_manual_scoring: false,
+        is_underpegging: false,
+        is_muggins: false,
+        is_lowball: false,
+        is_back_up_ten: false,
+        auction: None,
+        skunk_lines: None,
+        jokers: None,
+        scoring_rules: settings::ScoringRules::default(),
+        time_limits: None,
+    }
+}
+
+// Keeps the ScoreEvents of the play as the game reports them, such as his heels and the points
+// pegged with a GoDecision, which aren't all returned with the event which scored them
+#[derive(Default)]
+struct PlayScoreRecorder {
+    score_events: Arc<Mutex<Vec<score::ScoreEvent>>>,
+}
+
+impl observer::GameObserver for PlayScoreRecorder {
+    fn on_score(&mut self, score_event: &score::ScoreEvent) {
+        if let score::ScoreType::Play(_) = score_event.score_type {
+            self.score_events.lock().unwrap().push(score_event.clone());
+        }
+    }
+}
+
+// Plays a single game to its end with every seat's choices made by its strategy
+fn play_game(
+    settings: settings::GameSettings,
+    seed: u64,
+    strategies: &mut [Box<dyn strategy::Strategy>],
+    tally: &mut Tally,
+) -> Result<(), game_process_return::Error> {
+    let variant = settings.variant;
+    let mut game = crate::Game::from_seed(seed);
+    let recorder = PlayScoreRecorder::default();
+    let play_score_events = Arc::clone(&recorder.score_events);
+    game.add_observer(Box::new(recorder));
+    game.process_event(crate::GameEvent::GameSetup(settings))?;
+
+    let mut first_dealer = None;
+    // Points scored by each seat in the current hand
+    let mut hand_points = vec![0; strategies.len()];
+
+    while game.state() != crate::GameState::Win {
+        let event = match game.state() {
+            crate::GameState::Discard => {
+                let index_dealer = game.index_dealer().unwrap() as usize;
+                let num_kept = num_cards_kept(variant);
+                let selections = game
+                    .players()
+                    .iter()
+                    .enumerate()
+                    .map(|(seat, player)| {
+                        strategies[seat].choose_discard(
+                            &player.hand,
+                            player.hand.len() - num_kept,
+                            seat == index_dealer,
+                        )
+                    })
+                    .collect();
+                crate::GameEvent::DiscardSelection(selections)
+            }
+            crate::GameState::PlayWaitForCard => {
+                let seat = game.index_active().unwrap();
+                let hand = &game.players()[seat as usize].hand;
+                let is_played: Vec<bool> = (0..hand.len() as u8)
+                    .map(|index| game.has_card_been_played(seat, index))
+                    .collect();
+                crate::GameEvent::Play(strategies[seat as usize].choose_play(
+                    hand,
+                    &is_played,
+                    game.play_groups().last().unwrap(),
+                    &game.settings().unwrap(),
+                ))
+            }
+            crate::GameState::Auction => crate::GameEvent::Pass,
+            crate::GameState::PlayGoDecision => crate::GameEvent::GoDecision(go_decision(&game)),
+            _ => crate::GameEvent::Confirmation,
+        };
+
+        let state = game.state();
+        let index_dealer = game.index_dealer();
+        let success = match game.process_event(event) {
+            // The opponent has been moved back as far as the rules allow this game
+            Err(game_process_return::Error::GoDecisionError(
+                game_process_return::GoDecisionError::OpponentIsAtTheBackUpTenLimit,
+            )) => game.process_event(crate::GameEvent::GoDecision(crate::GoDecision::PegOne))?,
+            result => result?,
+        };
+
+        for score_event in play_score_events.lock().unwrap().drain(..) {
+            let seat = score_event.player_index as usize;
+            tally.seats[seat].pegging += u32::from(score_event.point_value);
+            hand_points[seat] += u32::from(score_event.point_value);
+        }
+
+        match success {
+            game_process_return::Success::InitialCut(
+                game_process_return::InitialCutReturn::DealerChosen,
+            ) => {
+                first_dealer = game.index_dealer();
+            }
+            game_process_return::Success::Deal => {
+                for seat_tally in &mut tally.seats {
+                    seat_tally.hands += 1;
+                }
+                tally.hands += 1;
+            }
+            game_process_return::Success::ShowScore(score_events) => {
+                add_show_points(&score_events, tally, &mut hand_points, false);
+            }
+            game_process_return::Success::CribScore(score_events) => {
+                add_show_points(&score_events, tally, &mut hand_points, true);
+            }
+            _ => {}
+        }
+
+        // The hand is over once the crib is scored or the game is won part way through it
+        if state == crate::GameState::CribScore || game.state() == crate::GameState::Win {
+            let dealer = index_dealer.unwrap() as usize;
+            for (seat, points) in hand_points.iter_mut().enumerate() {
+                if seat == dealer {
+                    tally.dealer_points += *points;
+                } else {
+                    tally.non_dealer_points += *points;
+                }
+                *points = 0;
+            }
+        }
+    }
+
+    record_result(&game, first_dealer, tally);
+    Ok(())
+}
+
+fn add_show_points(
+    score_events: &[score::ScoreEvent],
+    tally: &mut Tally,
+    hand_points: &mut [u32],
+    is_crib: bool,
+) {
+    for score_event in score_events {
+        let seat = score_event.player_index as usize;
+        if is_crib {
+            tally.seats[seat].crib += u32::from(score_event.point_value);
+        } else {
+            tally.seats[seat].show += u32::from(score_event.point_value);
+        }
+        hand_points[seat] += u32::from(score_event.point_value);
+    }
+}
+
+// Records the winners and any skunks of a finished game from the game's match standings
+fn record_result(game: &crate::Game, first_dealer: Option<u8>, tally: &mut Tally) {
+    let result = match game.match_standings().results.last() {
+        Some(result) => result,
+        None => return,
+    };
+
+    // Partners share a score so the first dealer has won whenever their partnership has
+    if let Some(first_dealer) = first_dealer {
+        if result.winners.contains(&first_dealer) {
+            tally.first_dealer_wins += 1;
+        }
+    }
+
+    for winner in &result.winners {
+        tally.seats[*winner as usize].wins += 1;
+    }
+    for skunked in &result.skunked {
+        tally.seats[*skunked as usize].times_skunked += 1;
+    }
+    for double_skunked in &result.double_skunked {
+        tally.seats[*double_skunked as usize].times_double_skunked += 1;
+    }
+
+    if !result.skunked.is_empty() {
+        tally.skunks += 1;
+    }
+    if !result.double_skunked.is_empty() {
+        tally.double_skunks += 1;
+    }
+}
+
+// Moves back the opponent furthest ahead, or pegs one for the go when no opponent has pegged
+fn go_decision(game: &crate::Game) -> crate::GoDecision {
+    let index_active = game.index_active().unwrap();
+    let partner_index = game.players()[index_active as usize].partner_index;
+    let leader = (0..game.players().len() as u8)
+        .filter(|index| *index != index_active && Some(*index) != partner_index)
+        .max_by_key(|index| game.players()[*index as usize].front_peg_pos);
+    match leader {
+        Some(leader) if game.players()[leader as usize].front_peg_pos > 0 => {
+            crate::GoDecision::BackUpTen(leader)
+        }
+        _ => crate::GoDecision::PegOne,
+    }
+}
+
+fn num_players(variant: settings::RuleVariant) -> usize {
+    variant.preset().num_players as usize
+}
+
+// Returns the number of cards each seat keeps in their hand after discarding
+fn num_cards_kept(variant: settings::RuleVariant) -> usize {
+    variant.preset().hand_size() as usize
+}
+
+fn victor_dealer_option(variant: settings::RuleVariant) -> settings::VictorDealerOption {
+    let preset = variant.preset();
+    if preset.num_players == 2 {
+        settings::VictorDealerOption::TwoPlayers
+    } else if preset.captain_index().is_some() {
+        settings::VictorDealerOption::CaptainDeals
+    } else {
+        settings::VictorDealerOption::LosersDrawForDealer
+    }
+}
@@ -0,0 +1,183 @@
+use cribbage::arena;
+use cribbage::settings;
+use std::env;
+use std::fs;
+use std::process;
+
+// Plays seeded games between strategies and reports the results
+//
+// Usage: cribbage-arena [--games N] [--seed N] [--variant NAME] [--strategies NAME,NAME,...]
+//                       [--csv PATH] [--json PATH] [house rules]
+//
+// House rules: [--back-up-ten] [--back-up-ten-limit N] [--auction MIN,INCREMENT,MAX]
+//              [--jokers N,VALUE] [--skunk-lines SKUNK,DOUBLE] [--run-across-reset]
+//              [--go-worth-two] [--crib-four-flush] [--no-seven-card-heels] [--nineteen-bonus N]
+
+const USAGE: &str = "Usage: cribbage-arena [--games N] [--seed N] [--variant NAME] \
+                     [--strategies NAME,NAME,...] [--csv PATH] [--json PATH] [--back-up-ten] \
+                     [--back-up-ten-limit N] [--auction MIN,INCREMENT,MAX] [--jokers N,VALUE] \
+                     [--skunk-lines SKUNK,DOUBLE] [--run-across-reset] [--go-worth-two] \
+                     [--crib-four-flush] [--no-seven-card-heels] [--nineteen-bonus N]";
+
+fn main() {
+    let mut config = arena::ArenaConfig {
+        settings: arena::default_settings(settings::RuleVariant::TwoStandard),
+        num_games: 1000,
+        seed: 0,
+        strategies: vec![String::from("greedy"), String::from("random")],
+    };
+    let mut csv_path = None;
+    let mut json_path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || match args.next() {
+            Some(value) => value,
+            None => exit_with_usage(&format!("Missing value for {}", arg)),
+        };
+        match arg.as_str() {
+            "--games" => config.num_games = parse(&value()),
+            "--seed" => config.seed = parse(&value()),
+            "--variant" => {
+                let variant = parse(&value());
+                config.settings.variant = variant;
+                config.settings.victor_dealer_option =
+                    arena::default_settings(variant).victor_dealer_option;
+            }
+            "--strategies" => {
+                config.strategies = value().split(',').map(String::from).collect();
+            }
+            "--csv" => csv_path = Some(value()),
+            "--json" => json_path = Some(value()),
+            "--back-up-ten" => config.settings.is_back_up_ten = true,
+            "--back-up-ten-limit" => {
+                config.settings.scoring_rules.back_up_ten_limit = Some(parse(&value()));
+            }
+            "--auction" => {
+                let [minimum_bid, minimum_increment, maximum_bid] = parse_list(&value());
+                config.settings.auction = Some(settings::AuctionRules {
+                    minimum_bid,
+                    minimum_increment,
+                    maximum_bid,
+                });
+            }
+            "--jokers" => {
+                let [num_jokers, play_value] = parse_list(&value());
+                config.settings.jokers = Some(settings::JokerRules {
+                    num_jokers,
+                    play_value,
+                });
+            }
+            "--skunk-lines" => {
+                let [skunk, double_skunk] = parse_list(&value());
+                config.settings.skunk_lines = Some(settings::SkunkLines {
+                    skunk,
+                    double_skunk,
+                });
+            }
+            "--run-across-reset" => config.settings.scoring_rules.is_run_across_reset = true,
+            "--go-worth-two" => config.settings.scoring_rules.is_go_worth_two = true,
+            "--crib-four-flush" => config.settings.scoring_rules.is_crib_four_flush = true,
+            "--no-seven-card-heels" => config.settings.scoring_rules.is_seven_card_heels = false,
+            "--nineteen-bonus" => config.settings.scoring_rules.nineteen_bonus = parse(&value()),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                println!(
+                    "Strategies: {}",
+                    cribbage::strategy::STRATEGY_NAMES.join(", ")
+                );
+                return;
+            }
+            _ => exit_with_usage(&format!("Unknown argument {}", arg)),
+        }
+    }
+
+    let report = match arena::run(&config) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Arena failed: {:?}", e);
+            process::exit(1);
+        }
+    };
+
+    print_report(&report);
+
+    if let Some(path) = csv_path {
+        write_file(&path, &report.to_csv());
+    }
+    if let Some(path) = json_path {
+        write_file(&path, &report.to_json());
+    }
+}
+
+fn print_report(report: &arena::ArenaReport) {
+    println!(
+        "{:?}: {} games with seed {}",
+        report.variant, report.num_games, report.seed
+    );
+    println!(
+        "{:>4}  {:<10} {:>6} {:>7} {:>17} {:>8} {:>8} {:>8} {:>8}",
+        "seat", "strategy", "wins", "win %", "95% interval", "pegging", "show", "crib", "skunked"
+    );
+    for seat in &report.seats {
+        println!(
+            "{:>4}  {:<10} {:>6} {:>6.1}% {:>7.1}% - {:>5.1}% {:>8.2} {:>8.2} {:>8.2} {:>8}",
+            seat.seat,
+            seat.strategy,
+            seat.wins,
+            seat.win_rate * 100.0,
+            seat.win_rate_low * 100.0,
+            seat.win_rate_high * 100.0,
+            seat.pegging_per_hand,
+            seat.show_per_hand,
+            seat.crib_per_hand,
+            seat.times_skunked,
+        );
+    }
+    println!(
+        "Skunks: {:.1}%, double skunks: {:.1}%",
+        report.skunk_rate * 100.0,
+        report.double_skunk_rate * 100.0
+    );
+    println!(
+        "Dealer advantage: {:.2} points per hand as dealer against {:.2} otherwise; first dealer \
+         won {:.1}% of games",
+        report.dealer_points_per_hand,
+        report.non_dealer_points_per_hand,
+        report.first_dealer_win_rate * 100.0
+    );
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    match value.parse() {
+        Ok(parsed) => parsed,
+        Err(_) => exit_with_usage(&format!("Invalid value {}", value)),
+    }
+}
+
+// Parses a comma separated list of exactly N values
+fn parse_list<T: std::str::FromStr + Copy + Default, const N: usize>(value: &str) -> [T; N] {
+    let values: Vec<T> = value.split(',').map(parse).collect();
+    if values.len() != N {
+        exit_with_usage(&format!(
+            "Expected {} comma separated values in {}",
+            N, value
+        ));
+    }
+    let mut list = [T::default(); N];
+    list.copy_from_slice(&values);
+    list
+}
+
+fn write_file(path: &str, contents: &str) {
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Could not write {}: {}", path, e);
+        process::exit(1);
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
@@ -0,0 +1,246 @@
+use cribbage::deck;
+use cribbage::score;
+use std::env;
+use std::io::{self, BufRead};
+use std::process;
+
+// Scores a hand in the show and prints every ScoreEvent with the total
+//
+// Usage: cribbage-count CARD... [--starter CARD] [--crib] [--json]
+//        cribbage-count --batch [--json]
+//
+// Cards are written in short notation such as 5H, TD or 10D, and XS for a joker. Without
+// --starter the last card given is the starter. With --batch a hand is read from each line of
+// stdin in the same form, and a line may end with "= N" to check that the hand scores N points;
+// the exit code is 1 when any hand doesn't score what it was expected to
+
+const USAGE: &str = "Usage: cribbage-count CARD... [--starter CARD] [--crib] [--json]\n       \
+                     cribbage-count --batch [--json]";
+
+// A hand to be scored as given on the command line or on a line of the batch input
+struct Count {
+    hand: Vec<deck::Card>,
+    starter: deck::Card,
+    is_crib: bool,
+    expected: Option<u16>,
+}
+
+fn main() {
+    let mut is_json = false;
+    let mut is_batch = false;
+    let mut words = Vec::new();
+    for arg in env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => is_json = true,
+            "--batch" => is_batch = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => words.push(arg),
+        }
+    }
+
+    if !is_batch {
+        let count = match parse_count(&words) {
+            Ok(count) => count,
+            Err(message) => exit_with_usage(&message),
+        };
+        let score_events = score_count(&count);
+        if is_json {
+            println!("{}", to_json(&count, &score_events));
+        } else {
+            print_count(&count, &score_events);
+        }
+        return;
+    }
+
+    if !words.is_empty() {
+        exit_with_usage("Hands are read from stdin with --batch");
+    }
+    let mut num_mismatches = 0;
+    for (index, line) in io::stdin().lock().lines().enumerate() {
+        let line = match line {
+            Ok(line) => line,
+            Err(e) => {
+                eprintln!("Could not read stdin: {}", e);
+                process::exit(2);
+            }
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let words: Vec<String> = line.split_whitespace().map(String::from).collect();
+        let count = match parse_count(&words) {
+            Ok(count) => count,
+            Err(message) => {
+                eprintln!("Line {}: {}", index + 1, message);
+                process::exit(2);
+            }
+        };
+        let score_events = score_count(&count);
+        let total = total(&score_events);
+        let is_mismatch = count.expected.map_or(false, |expected| expected != total);
+        if is_mismatch {
+            num_mismatches += 1;
+        }
+
+        if is_json {
+            println!("{}", to_json(&count, &score_events));
+        } else {
+            println!(
+                "{}{}",
+                line,
+                match count.expected {
+                    Some(expected) if is_mismatch => {
+                        format!(": scored {} but expected {}", total, expected)
+                    }
+                    Some(_) => String::from(": ok"),
+                    None => format!(" = {}", total),
+                }
+            );
+        }
+    }
+
+    if num_mismatches > 0 {
+        eprintln!(
+            "{} hand{} didn't score as expected",
+            num_mismatches,
+            if num_mismatches == 1 { "" } else { "s" }
+        );
+        process::exit(1);
+    }
+}
+
+// Parses the cards and options of a single hand
+fn parse_count(words: &[String]) -> Result<Count, String> {
+    let mut cards = Vec::new();
+    let mut starter = None;
+    let mut is_crib = false;
+    let mut expected = None;
+
+    let mut words = words.iter();
+    while let Some(word) = words.next() {
+        match word.as_str() {
+            "--crib" => is_crib = true,
+            "--starter" => match words.next() {
+                Some(card) => starter = Some(card.parse::<deck::Card>()?),
+                None => return Err(String::from("Missing card for --starter")),
+            },
+            "=" => match words.next().map(|total| total.parse()) {
+                Some(Ok(total)) => expected = Some(total),
+                _ => return Err(String::from("Missing expected total after =")),
+            },
+            card => cards.push(card.parse::<deck::Card>()?),
+        }
+    }
+
+    let starter = match starter {
+        Some(starter) => starter,
+        None => cards
+            .pop()
+            .ok_or_else(|| String::from("No cards were given"))?,
+    };
+    if cards.is_empty() {
+        return Err(String::from("No cards were given besides the starter"));
+    }
+    let mut all_cards = cards.clone();
+    all_cards.push(starter);
+    all_cards.sort();
+    if all_cards.windows(2).any(|pair| pair[0] == pair[1]) {
+        return Err(String::from("The same card was given twice"));
+    }
+
+    Ok(Count {
+        hand: cards,
+        starter,
+        is_crib,
+        expected,
+    })
+}
+
+fn score_count(count: &Count) -> Vec<score::ScoreEvent> {
+    score::show::score_hand(0, &count.hand, count.starter, count.is_crib)
+}
+
+fn total(score_events: &[score::ScoreEvent]) -> u16 {
+    score_events
+        .iter()
+        .map(|score_event| u16::from(score_event.point_value))
+        .sum()
+}
+
+fn print_count(count: &Count, score_events: &[score::ScoreEvent]) {
+    println!(
+        "{} with {} as the starter{}",
+        cards_text(&count.hand),
+        count.starter,
+        if count.is_crib { " in the crib" } else { "" }
+    );
+    for score_event in score_events {
+        println!("  {}", score::spoken::describe(score_event));
+    }
+    println!("  {}", score::spoken::spoken_count(score_events));
+    println!("Total: {}", total(score_events));
+}
+
+fn to_json(count: &Count, score_events: &[score::ScoreEvent]) -> String {
+    let events: Vec<serde_json::Value> = score_events
+        .iter()
+        .map(|score_event| {
+            let (name, cards) = match &score_event.score_type {
+                score::ScoreType::Show(show_score_type) => show_parts(show_score_type),
+                score::ScoreType::Play(_) => ("Play", &[][..]),
+            };
+            serde_json::json!({
+                "type": name,
+                "points": score_event.point_value,
+                "cards": card_strings(cards),
+                "description": score::spoken::describe(score_event),
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "hand": card_strings(&count.hand),
+        "starter": count.starter.to_string(),
+        "is_crib": count.is_crib,
+        "score_events": events,
+        "spoken": score::spoken::spoken_count(score_events),
+        "total": total(score_events),
+        "expected": count.expected,
+    })
+    .to_string()
+}
+
+// Returns the name of a ShowScoreType as it is written in the enum and its cards
+fn show_parts(show_score_type: &score::ShowScoreType) -> (&'static str, &[deck::Card]) {
+    match show_score_type {
+        score::ShowScoreType::Fifteen(cards) => ("Fifteen", cards),
+        score::ShowScoreType::Pair(cards) => ("Pair", cards),
+        score::ShowScoreType::Triple(cards) => ("Triple", cards),
+        score::ShowScoreType::Quadruple(cards) => ("Quadruple", cards),
+        score::ShowScoreType::Straight(cards) => ("Straight", cards),
+        score::ShowScoreType::FourFlush(cards) => ("FourFlush", cards),
+        score::ShowScoreType::FiveFlush(cards) => ("FiveFlush", cards),
+        score::ShowScoreType::Nobs(cards) => ("Nobs", cards),
+        score::ShowScoreType::Wild(cards) => ("Wild", cards),
+        score::ShowScoreType::Nineteen(cards) => ("Nineteen", cards),
+    }
+}
+
+fn card_strings(cards: &[deck::Card]) -> Vec<String> {
+    cards.iter().map(|card| card.to_string()).collect()
+}
+
+fn cards_text(cards: &[deck::Card]) -> String {
+    card_strings(cards).join(" ")
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
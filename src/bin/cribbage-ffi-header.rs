@@ -0,0 +1,21 @@
+use cribbage::ffi;
+
+// Prints the C header of the ffi module, which is kept at ffi/cribbage.h
+//
+// Usage: cribbage-ffi-header > ffi/cribbage.h
+
+const USAGE: &str = "Usage: cribbage-ffi-header > ffi/cribbage.h";
+
+fn main() {
+    if let Some(arg) = std::env::args().nth(1) {
+        match arg.as_str() {
+            "--help" | "-h" => println!("{}", USAGE),
+            _ => {
+                eprintln!("Unknown argument {}\n{}", arg, USAGE);
+                std::process::exit(2);
+            }
+        }
+        return;
+    }
+    print!("{}", ffi::header());
+}
@@ -0,0 +1,63 @@
+use cribbage::server;
+use std::env;
+use std::net::TcpListener;
+use std::process;
+use std::str::FromStr;
+
+// Hosts games for clients speaking newline-delimited JSON over TCP; see the server module for the
+// messages
+//
+// Usage: cribbage-server [--address ADDRESS] [--port N]
+
+const USAGE: &str = "Usage: cribbage-server [--address ADDRESS] [--port N]";
+
+fn main() {
+    let mut address = String::from("127.0.0.1");
+    let mut port: u16 = 7231;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || match args.next() {
+            Some(value) => value,
+            None => exit_with_usage(&format!("Missing value for {}", arg)),
+        };
+        match arg.as_str() {
+            "--address" => address = value(),
+            "--port" => port = parse(&value()),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => exit_with_usage(&format!("Unknown argument {}", arg)),
+        }
+    }
+
+    let listener = match TcpListener::bind((address.as_str(), port)) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Could not listen on {}:{}: {}", address, port, e);
+            process::exit(1);
+        }
+    };
+    if let Ok(local_address) = listener.local_addr() {
+        println!("Listening on {}", local_address);
+    }
+
+    if let Err(e) = server::serve(listener) {
+        eprintln!("Server stopped: {}", e);
+        process::exit(1);
+    }
+}
+
+fn parse<T: FromStr>(value: &str) -> T {
+    match value.parse() {
+        Ok(value) => value,
+        Err(_) => exit_with_usage(&format!("Invalid value {}", value)),
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
@@ -0,0 +1,151 @@
+use cribbage::analysis::stats;
+use std::env;
+use std::fs;
+use std::process;
+
+// Scores every hand of four cards with a starter and prints the tables of totals and the most
+// common hands
+//
+// Usage: cribbage-stats [--json PATH]
+
+const USAGE: &str = "Usage: cribbage-stats [--json PATH]";
+
+const VALUE_NAMES: [&str; 13] = [
+    "A", "2", "3", "4", "5", "6", "7", "8", "9", "T", "J", "Q", "K",
+];
+
+fn main() {
+    let mut json_path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => match args.next() {
+                Some(path) => json_path = Some(path),
+                None => exit_with_usage("Missing value for --json"),
+            },
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => exit_with_usage(&format!("Unknown argument {}", arg)),
+        }
+    }
+
+    let statistics = stats::enumerate_hands();
+    print_statistics(&statistics);
+
+    if let Some(path) = json_path {
+        let json = serde_json::to_string_pretty(&statistics).unwrap();
+        if let Err(e) = fs::write(&path, json) {
+            eprintln!("Could not write {}: {}", path, e);
+            process::exit(1);
+        }
+    }
+}
+
+fn print_statistics(statistics: &stats::HandStatistics) {
+    println!(
+        "{} hands counted by scoring {} after suit symmetry",
+        statistics.hand.num_hands(),
+        statistics.hands_scored
+    );
+    println!();
+    println!(
+        "{:>5} {:>10} {:>9} {:>10} {:>9}",
+        "total", "hands", "hand %", "cribs", "crib %"
+    );
+    for total in 0..=stats::MAX_TOTAL {
+        println!(
+            "{:>5} {:>10} {:>8.4}% {:>10} {:>8.4}%",
+            total,
+            statistics.hand.frequencies[total as usize],
+            statistics.hand.probability(total) * 100.0,
+            statistics.crib.frequencies[total as usize],
+            statistics.crib.probability(total) * 100.0,
+        );
+    }
+    println!(
+        "{:>5} {:>20.4} {:>20.4}",
+        "mean",
+        statistics.hand.mean(),
+        statistics.crib.mean()
+    );
+    println!();
+
+    let impossible: Vec<String> = statistics
+        .hand
+        .impossible_totals()
+        .iter()
+        .map(|total| total.to_string())
+        .collect();
+    println!(
+        "Impossible totals: {}; none of the {} hands scores them",
+        impossible.join(", "),
+        statistics.hand.num_hands()
+    );
+
+    let most_common: Vec<String> = statistics
+        .hand
+        .most_common(5)
+        .iter()
+        .map(|(total, frequency)| format!("{} ({})", total, frequency))
+        .collect();
+    println!("Most common totals: {}", most_common.join(", "));
+    println!();
+
+    println!("{:>10} {:>9} {:>5}  hand", "hands", "%", "total");
+    for (pattern, frequency) in statistics.hand_patterns.iter().take(10) {
+        println!(
+            "{:>10} {:>8.4}% {:>5}  {}",
+            frequency,
+            *frequency as f64 / statistics.hand.num_hands() as f64 * 100.0,
+            pattern.total(),
+            describe(pattern)
+        );
+    }
+    println!();
+
+    println!("{:>5} {:>10} {:>10}", "rank", "as starter", "when held");
+    for (value, name) in VALUE_NAMES.iter().enumerate() {
+        println!(
+            "{:>5} {:>10.4} {:>10.4}",
+            name, statistics.hand_mean_by_starter[value], statistics.hand_mean_by_held[value]
+        );
+    }
+}
+
+// Returns a description of what a hand scores for such as "2 fifteens, a pair"
+fn describe(pattern: &stats::HandPattern) -> String {
+    let mut parts = Vec::new();
+    match pattern.fifteens {
+        0 => {}
+        1 => parts.push("a fifteen".to_string()),
+        fifteens => parts.push(format!("{} fifteens", fifteens)),
+    }
+    match pattern.pairs {
+        0 => {}
+        1 => parts.push("a pair".to_string()),
+        pairs => parts.push(format!("{} pairs", pairs)),
+    }
+    for run in &pattern.runs {
+        parts.push(format!("a run of {}", run));
+    }
+    if pattern.flush > 0 {
+        parts.push(format!("a flush of {}", pattern.flush));
+    }
+    if pattern.nobs {
+        parts.push("nobs".to_string());
+    }
+    if parts.is_empty() {
+        "nothing".to_string()
+    } else {
+        parts.join(", ")
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
@@ -0,0 +1,1312 @@
+use cribbage::board;
+use cribbage::deck;
+use cribbage::game_process_return;
+use cribbage::score;
+use cribbage::settings;
+use cribbage::strategy;
+use cribbage::{Game, GameEvent, GameState, GoDecision, PlayTurn};
+use std::env;
+use std::io::{self, Write};
+use std::process;
+
+// Plays cribbage at the terminal with any mix of people sharing the keyboard and bots
+//
+// Usage: cribbage-tui [--variant NAME] [--seats SEAT,SEAT,...] [--seed N] [--manual]
+//                     [--underpegging] [--muggins] [--back-up-ten] [--auction] [--ascii]
+//
+// Each seat is "human" or the name of a strategy; by default the first seat is human and every
+// other seat is played by the greedy strategy. When more than one person plays, the terminal is
+// cleared and handed over before anyone's hand is shown. With --manual every hand is counted by
+// its owner, with --underpegging a count that leaves points out is accepted and the points are
+// lost, and with --muggins the opponents may call the points left out and peg them themselves
+
+const USAGE: &str = "Usage: cribbage-tui [--variant NAME] [--seats SEAT,SEAT,...] [--seed N] \
+                     [--manual] [--underpegging] [--muggins] [--back-up-ten] [--auction] \
+                     [--ascii]";
+
+const PLAY_CLAIM_HELP: &str = "Claim the play as a comma separated list of: 15, 31, pair, \
+                               triple, quad, run N, go. Leave it blank when nothing scores";
+const SHOW_CLAIM_HELP: &str = "Claim the show as a comma separated list of: 15 CARDS, pair \
+                               CARDS, triple CARDS, quad CARDS, run CARDS, flush CARDS, nobs \
+                               JACK, wild JOKER CARD, nineteen. Cards are written like 5H, TD \
+                               or XS and a number at the end of a claim gives its points such as \
+                               run 3H 3D 4S 5C 8 for a double run";
+
+struct Options {
+    variant: settings::RuleVariant,
+    seats: Vec<String>,
+    seed: Option<u64>,
+    is_manual_scoring: bool,
+    is_underpegging: bool,
+    is_muggins: bool,
+    is_back_up_ten: bool,
+    is_auction: bool,
+    is_ascii: bool,
+}
+
+enum Seat {
+    Human,
+    Bot(Box<dyn strategy::Strategy>),
+}
+
+// What is being counted when scoring manually
+#[derive(Copy, Clone, PartialEq)]
+enum Count {
+    Play,
+    Show,
+    Crib,
+}
+
+struct Tui {
+    game: Game,
+    seats: Vec<Seat>,
+    names: Vec<String>,
+    // None for the variants without a standard board such as five handed cribbage
+    board: Option<board::Board>,
+    is_ascii: bool,
+    // The person last shown the table; the terminal is handed over when another person must act
+    viewer: Option<u8>,
+}
+
+fn main() {
+    let mut options = Options {
+        variant: settings::RuleVariant::TwoStandard,
+        seats: Vec::new(),
+        seed: None,
+        is_manual_scoring: false,
+        is_underpegging: false,
+        is_muggins: false,
+        is_back_up_ten: false,
+        is_auction: false,
+        is_ascii: false,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || match args.next() {
+            Some(value) => value,
+            None => exit_with_usage(&format!("Missing value for {}", arg)),
+        };
+        match arg.as_str() {
+            "--variant" => options.variant = parse(&value()),
+            "--seats" => options.seats = value().split(',').map(String::from).collect(),
+            "--seed" => options.seed = Some(parse(&value())),
+            "--manual" => options.is_manual_scoring = true,
+            "--underpegging" => {
+                options.is_manual_scoring = true;
+                options.is_underpegging = true;
+            }
+            "--muggins" => {
+                options.is_manual_scoring = true;
+                options.is_underpegging = true;
+                options.is_muggins = true;
+            }
+            "--back-up-ten" => options.is_back_up_ten = true,
+            "--auction" => options.is_auction = true,
+            "--ascii" => options.is_ascii = true,
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                println!(
+                    "Seats: human, {}",
+                    cribbage::strategy::STRATEGY_NAMES.join(", ")
+                );
+                return;
+            }
+            _ => exit_with_usage(&format!("Unknown argument {}", arg)),
+        }
+    }
+
+    let mut tui = Tui::new(&options);
+    tui.run();
+}
+
+impl Tui {
+    fn new(options: &Options) -> Tui {
+        let num_players = options.variant.preset().num_players as usize;
+        let mut seat_names = options.seats.clone();
+        if seat_names.is_empty() {
+            seat_names.push(String::from("human"));
+        }
+        if seat_names.len() == 1 {
+            seat_names.resize(num_players, String::from("greedy"));
+        }
+        if seat_names.len() != num_players {
+            exit_with_usage(&format!(
+                "{:?} has {} seats but {} were given",
+                options.variant,
+                num_players,
+                seat_names.len()
+            ));
+        }
+
+        let seed = options.seed.unwrap_or_else(rand::random);
+        let mut seats = Vec::with_capacity(num_players);
+        let mut names = Vec::with_capacity(num_players);
+        for (index, seat_name) in seat_names.iter().enumerate() {
+            if seat_name == "human" {
+                seats.push(Seat::Human);
+                names.push(format!("Player {}", index + 1));
+            } else {
+                let seat_seed = seed.wrapping_mul(31).wrapping_add(index as u64);
+                match strategy::from_name(seat_name, seat_seed) {
+                    Some(strategy) => seats.push(Seat::Bot(strategy)),
+                    None => exit_with_usage(&format!("Unknown seat {}", seat_name)),
+                }
+                names.push(format!("Player {} ({})", index + 1, seat_name));
+            }
+        }
+
+        let mut game = match options.seed {
+            Some(seed) => Game::from_seed(seed),
+            None => Game::new(),
+        };
+        let setup = game.process_event(GameEvent::GameSetup(settings::GameSettings {
+            variant: options.variant,
+            victor_dealer_option: victor_dealer_option(options.variant),
+            is_manual_scoring: options.is_manual_scoring,
+            is_underpegging: options.is_underpegging,
+            is_muggins: options.is_muggins,
+            is_lowball: false,
+            is_back_up_ten: options.is_back_up_ten,
+            auction: if options.is_auction {
+                Some(settings::AuctionRules {
+                    minimum_bid: 1,
+                    minimum_increment: 1,
+                    maximum_bid: 30,
+                })
+            } else {
+                None
+            },
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: settings::ScoringRules::default(),
+            time_limits: None,
+        }));
+        if let Err(e) = setup {
+            eprintln!("{}", explain(&e));
+            process::exit(2);
+        }
+
+        let viewer = match seats.iter().filter(|seat| is_human(seat)).count() {
+            1 => seats.iter().position(is_human).map(|index| index as u8),
+            _ => None,
+        };
+        Tui {
+            game,
+            seats,
+            names,
+            board: board::Board::for_variant(options.variant).ok(),
+            is_ascii: options.is_ascii,
+            viewer,
+        }
+    }
+
+    fn run(&mut self) {
+        loop {
+            let state = self.game.state();
+            match state {
+                GameState::GameStart => unreachable!("The game is set up before it is run"),
+                GameState::CutInitial => {
+                    println!("Cutting for the deal");
+                    self.send_confirmation();
+                }
+                GameState::Deal => {
+                    let dealer = self.game.index_dealer().unwrap();
+                    println!();
+                    println!("{} deals", self.names[dealer as usize]);
+                    self.send_confirmation();
+                }
+                GameState::Sort
+                | GameState::CribSort
+                | GameState::ResetPlay
+                | GameState::CutStarter => self.send_confirmation(),
+                GameState::Auction => self.auction(),
+                GameState::Discard => self.discard(),
+                GameState::NibsCheck => self.nibs(),
+                GameState::PlayWaitForCard => self.play(),
+                GameState::PlayScore => self.count(Count::Play),
+                GameState::PlayGoDecision => self.go_decision(),
+                GameState::ShowScore if self.is_manual_scoring() => self.count(Count::Show),
+                GameState::CribScore if self.is_manual_scoring() => self.count(Count::Crib),
+                GameState::ShowScore | GameState::CribScore => self.send_confirmation(),
+                GameState::Win => self.win(),
+                GameState::End => {
+                    self.print_standings();
+                    return;
+                }
+                GameState::PlayMuggins => self.muggins(Count::Play),
+                GameState::ShowMuggins => self.muggins(Count::Show),
+                GameState::CribMuggins => self.muggins(Count::Crib),
+            }
+        }
+    }
+
+    fn is_manual_scoring(&self) -> bool {
+        self.game.settings().unwrap().is_manual_scoring
+    }
+
+    fn send_confirmation(&mut self) {
+        self.send_bot(GameEvent::Confirmation);
+    }
+
+    // Sends an event chosen by the program or a bot, which the game should never reject
+    fn send_bot(&mut self, event: GameEvent) -> game_process_return::Success {
+        match self.send(event) {
+            Ok(success) => success,
+            Err(e) => {
+                eprintln!("The game rejected a move it should have accepted: {:?}", e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // Sends an event and prints what happened
+    fn send(
+        &mut self,
+        event: GameEvent,
+    ) -> Result<game_process_return::Success, game_process_return::Error> {
+        let state = self.game.state();
+        let index_active = self.game.index_active();
+        let index_dealer = self.game.index_dealer();
+        let hand = index_active.map(|index| self.game.players()[index as usize].hand.clone());
+
+        let success = self.game.process_event(event)?;
+        self.report(&success, state, index_active, index_dealer, hand);
+        Ok(success)
+    }
+
+    fn report(
+        &mut self,
+        success: &game_process_return::Success,
+        state: GameState,
+        index_active: Option<u8>,
+        index_dealer: Option<u8>,
+        hand: Option<Vec<deck::Card>>,
+    ) {
+        use game_process_return::Success;
+
+        let name = |index: Option<u8>| self.names[index.unwrap() as usize].clone();
+        match success {
+            Success::InitialCut(game_process_return::InitialCutReturn::CutTie) => {
+                println!("The cut is tied so the deck is cut again");
+            }
+            Success::InitialCut(game_process_return::InitialCutReturn::DealerChosen) => {
+                println!("{} wins the cut", name(self.game.index_dealer()));
+            }
+            Success::Auction(game_process_return::AuctionReturn::CribTaken(winner, bid)) => {
+                println!(
+                    "{} takes the crib for {} points",
+                    self.names[*winner as usize], bid
+                );
+            }
+            Success::Discard => println!("The crib is laid away"),
+            Success::StarterCut(starter_cut_return) => {
+                println!(
+                    "The starter is {}",
+                    self.card_text(self.game.starter_card().unwrap())
+                );
+                if *starter_cut_return == game_process_return::StarterCutReturn::AutoNibs {
+                    println!("{} pegs 2 for his heels", name(index_dealer));
+                }
+            }
+            Success::NibsCheck(game_process_return::NibsCheckReturn::Nibs) => {
+                println!("{} pegs 2 for his heels", name(index_dealer));
+            }
+            Success::PlayWaitForCard(
+                game_process_return::PlayWaitForCardReturn::AutomaticScoring(score_events),
+            ) => self.print_score_events(score_events),
+            Success::PlayScore(score_events) | Success::ShowScore(score_events) => {
+                if state == GameState::ShowScore {
+                    println!(
+                        "{} shows {}",
+                        name(index_active),
+                        self.cards_text(&hand.unwrap_or_default())
+                    );
+                }
+                self.print_score_events(score_events);
+                if state == GameState::ShowScore {
+                    println!("  {}", score::spoken::spoken_count(score_events));
+                }
+            }
+            Success::CribScore(score_events) => {
+                println!(
+                    "{}'s crib is {}",
+                    name(index_dealer),
+                    self.cards_text(self.game.crib())
+                );
+                self.print_score_events(score_events);
+                println!("  {}", score::spoken::spoken_count(score_events));
+                // The board is printed with the result when the crib wins the game
+                if self.game.state() != GameState::Win {
+                    self.print_board();
+                }
+            }
+            Success::GoDecision(game_process_return::GoDecisionReturn::PeggedOne(score_event)) => {
+                self.print_score_events(std::slice::from_ref(score_event));
+            }
+            Success::GoDecision(game_process_return::GoDecisionReturn::MovedBack(
+                player_index,
+                holes,
+            )) => {
+                println!(
+                    "{} moves {} back {} holes",
+                    name(index_active),
+                    self.names[*player_index as usize],
+                    holes
+                );
+            }
+            Success::Muggins(game_process_return::MugginsReturn::Called(score_events))
+                if !score_events.is_empty() =>
+            {
+                println!(
+                    "{} calls muggins",
+                    self.names[score_events[0].player_index as usize]
+                );
+                self.print_score_events(score_events);
+            }
+            Success::ResetPlay(game_process_return::ResetPlayReturn::NewPlayGroup) => {
+                println!("The count starts again from 0");
+            }
+            Success::ResetPlay(game_process_return::ResetPlayReturn::PlayComplete) => {
+                println!("The play is over; on to the show");
+            }
+            _ => {}
+        }
+
+        if self.game.state() == GameState::Win && state != GameState::Win {
+            self.print_result();
+        }
+    }
+
+    fn print_score_events(&self, score_events: &[score::ScoreEvent]) {
+        for score_event in score_events {
+            if score_event.point_value == 0 && !is_wild(score_event) {
+                continue;
+            }
+            println!(
+                "  {}: {}",
+                self.names[score_event.player_index as usize],
+                score::spoken::describe(score_event)
+            );
+        }
+    }
+
+    // Clears the terminal for the next person to act when people are sharing the terminal such
+    // that nobody sees another's hand
+    fn hand_over(&mut self, seat: u8) {
+        if self.viewer == Some(seat) {
+            return;
+        }
+        if self.viewer.is_some() || self.seats.iter().filter(|seat| is_human(seat)).count() > 1 {
+            self.read_line(&format!(
+                "Pass the terminal to {} and press Enter",
+                self.names[seat as usize]
+            ));
+            if !self.is_ascii {
+                print!("\x1b[2J\x1b[H");
+            }
+        }
+        self.viewer = Some(seat);
+    }
+
+    fn auction(&mut self) {
+        let index_active = self.game.index_active().unwrap();
+        if !is_human(&self.seats[index_active as usize]) {
+            println!("{} passes", self.names[index_active as usize]);
+            self.send_bot(GameEvent::Pass);
+            return;
+        }
+
+        self.hand_over(index_active);
+        self.print_table(index_active);
+        if let Some((bidder, bid)) = self.game.auction().and_then(|auction| auction.high_bid()) {
+            println!("The high bid is {} by {}", bid, self.names[bidder as usize]);
+        }
+        loop {
+            let line = self.read_line("Bid points for the crib or pass:");
+            let event = match line.as_str() {
+                "pass" | "p" => GameEvent::Pass,
+                bid => match bid.parse() {
+                    Ok(bid) => GameEvent::Bid(bid),
+                    Err(_) => {
+                        println!("Enter a number of points or pass");
+                        continue;
+                    }
+                },
+            };
+            match self.send(event) {
+                Ok(_) => return,
+                Err(e) => println!("{}", explain(&e)),
+            }
+        }
+    }
+
+    fn discard(&mut self) {
+        let index_dealer = self.game.index_dealer().unwrap();
+        let preset = self.game.settings().unwrap().variant.preset();
+
+        loop {
+            let mut selections = Vec::with_capacity(self.seats.len());
+            for seat in 0..self.seats.len() as u8 {
+                let num_discards = preset.discards_by(seat, index_dealer) as usize;
+                let hand = self.game.players()[seat as usize].hand.clone();
+                let selection = match &mut self.seats[seat as usize] {
+                    _ if num_discards == 0 => Vec::new(),
+                    Seat::Bot(strategy) => {
+                        strategy.choose_discard(&hand, num_discards, seat == index_dealer)
+                    }
+                    Seat::Human => self.choose_discard(seat, num_discards),
+                };
+                selections.push(selection);
+            }
+            match self.send(GameEvent::DiscardSelection(selections)) {
+                Ok(_) => return,
+                Err(e) => println!("{}", explain(&e)),
+            }
+        }
+    }
+
+    fn choose_discard(&mut self, seat: u8, num_discards: usize) -> Vec<u8> {
+        self.hand_over(seat);
+        self.print_table(seat);
+        let whose = if Some(seat) == self.game.index_dealer() {
+            String::from("your")
+        } else {
+            format!(
+                "{}'s",
+                self.names[self.game.index_dealer().unwrap() as usize]
+            )
+        };
+        let hand_len = self.game.players()[seat as usize].hand.len();
+        loop {
+            let line = self.read_line(&format!(
+                "Choose {} card{} for {} crib by number:",
+                num_discards,
+                if num_discards == 1 { "" } else { "s" },
+                whose
+            ));
+            match parse_indices(&line, hand_len) {
+                Some(indices) if indices.len() == num_discards => return indices,
+                _ => println!(
+                    "Enter {} different numbers from 1 to {}",
+                    num_discards, hand_len
+                ),
+            }
+        }
+    }
+
+    fn nibs(&mut self) {
+        let index_dealer = self.game.index_dealer().unwrap();
+        let settings = self.game.settings().unwrap();
+        let is_heels = settings.is_heels(self.game.starter_card().unwrap());
+        let call = score::ScoreEvent {
+            score_type: score::ScoreType::Play(score::PlayScoreType::Nibs),
+            player_index: index_dealer,
+            point_value: 2,
+        };
+
+        if !is_heels {
+            self.send_bot(GameEvent::Nibs(None));
+            return;
+        }
+        if !is_human(&self.seats[index_dealer as usize]) {
+            self.send_bot(GameEvent::Nibs(Some(call)));
+            return;
+        }
+
+        self.hand_over(index_dealer);
+        loop {
+            let line = self.read_line("A jack is cut. Peg 2 for his heels? (y/n)");
+            let event = GameEvent::Nibs(if is_yes(&line) {
+                Some(call.clone())
+            } else {
+                None
+            });
+            match self.send(event) {
+                Ok(_) => return,
+                Err(e) => println!("{}", explain(&e)),
+            }
+        }
+    }
+
+    fn play(&mut self) {
+        let index_active = self.game.index_active().unwrap();
+        let hand = self.game.players()[index_active as usize].hand.clone();
+        let is_played: Vec<bool> = (0..hand.len() as u8)
+            .map(|index| self.game.has_card_been_played(index_active, index))
+            .collect();
+        let total = self.game.play_groups().last().unwrap().total();
+        let settings = self.game.settings().unwrap();
+        let has_play = hand
+            .iter()
+            .enumerate()
+            .any(|(index, card)| !is_played[index] && total + settings.play_value(*card) <= 31);
+
+        let turn = match &mut self.seats[index_active as usize] {
+            Seat::Bot(strategy) => {
+                let play_group = self.game.play_groups().last().unwrap();
+                strategy.choose_play(&hand, &is_played, play_group, &settings)
+            }
+            Seat::Human if !has_play => PlayTurn::Go,
+            Seat::Human => {
+                self.hand_over(index_active);
+                self.print_table(index_active);
+                loop {
+                    let line = self.read_line("Play a card by number:");
+                    match parse_indices(&line, hand.len()).as_deref() {
+                        Some([index]) => {
+                            match self.send_play(index_active, PlayTurn::CardSelected(*index)) {
+                                Ok(()) => return,
+                                Err(e) => println!("{}", explain(&e)),
+                            }
+                        }
+                        _ => println!("Enter the number of one card"),
+                    }
+                }
+            }
+        };
+
+        if let Err(e) = self.send_play(index_active, turn) {
+            eprintln!("The game rejected a move it should have accepted: {:?}", e);
+            process::exit(1);
+        }
+    }
+
+    // Plays the turn and announces the card played and the count or the go
+    fn send_play(
+        &mut self,
+        index_active: u8,
+        turn: PlayTurn,
+    ) -> Result<(), game_process_return::Error> {
+        let name = self.names[index_active as usize].clone();
+        match turn {
+            PlayTurn::Go => println!("{}: Go", name),
+            PlayTurn::CardSelected(index) => {
+                let card = self.game.players()[index_active as usize].hand[index as usize];
+                let total = self.game.play_groups().last().unwrap().total()
+                    + self.game.settings().unwrap().play_value(card);
+                if total <= 31 && !self.game.has_card_been_played(index_active, index) {
+                    println!("{}: {}, {}", name, self.card_text(card), total);
+                }
+            }
+        }
+        self.send(GameEvent::Play(turn)).map(|_| ())
+    }
+
+    fn go_decision(&mut self) {
+        let index_active = self.game.index_active().unwrap();
+        if !is_human(&self.seats[index_active as usize]) {
+            self.send_bot(GameEvent::GoDecision(GoDecision::PegOne));
+            return;
+        }
+
+        self.hand_over(index_active);
+        let opponents: Vec<u8> = (0..self.seats.len() as u8)
+            .filter(|index| {
+                *index != index_active
+                    && self.game.players()[index_active as usize].partner_index != Some(*index)
+            })
+            .collect();
+        println!("[1] Peg one for the go");
+        for (number, opponent) in opponents.iter().enumerate() {
+            println!(
+                "[{}] Move {} back ten holes",
+                number + 2,
+                self.names[*opponent as usize]
+            );
+        }
+        loop {
+            let line = self.read_line("Choose by number:");
+            let event = match line.parse::<usize>() {
+                Ok(1) => GameEvent::GoDecision(GoDecision::PegOne),
+                Ok(number) if number >= 2 && number - 2 < opponents.len() => {
+                    GameEvent::GoDecision(GoDecision::BackUpTen(opponents[number - 2]))
+                }
+                _ => {
+                    println!("Enter a number from 1 to {}", opponents.len() + 1);
+                    continue;
+                }
+            };
+            match self.send(event) {
+                Ok(_) => return,
+                Err(e) => println!("{}", explain(&e)),
+            }
+        }
+    }
+
+    // Counts the play, a hand or the crib with manual scoring. Bots claim the full count, and people
+    // are asked for their claims until the game accepts them
+    fn count(&mut self, count: Count) {
+        let index_scorer = match count {
+            Count::Crib => self.game.index_dealer().unwrap(),
+            Count::Play | Count::Show => self.game.index_active().unwrap(),
+        };
+
+        if !is_human(&self.seats[index_scorer as usize]) {
+            let claims = self.game.optimal_scoring();
+            self.send_bot(GameEvent::ManScoreSelection(claims));
+            return;
+        }
+
+        self.hand_over(index_scorer);
+        let starter = self.game.starter_card().unwrap();
+        match count {
+            Count::Play => {
+                let play_group = self.game.play_groups().last().unwrap();
+                println!(
+                    "The count is {}: {}",
+                    play_group.total(),
+                    self.cards_text(play_group.cards())
+                );
+            }
+            Count::Show => {
+                let hand = &self.game.players()[index_scorer as usize].hand;
+                println!(
+                    "Your hand is {} with the starter {}",
+                    self.cards_text(hand),
+                    self.card_text(starter)
+                );
+            }
+            Count::Crib => println!(
+                "Your crib is {} with the starter {}",
+                self.cards_text(self.game.crib()),
+                self.card_text(starter)
+            ),
+        }
+
+        loop {
+            let line = self.read_line("Your count (? for help):");
+            if line == "?" {
+                println!(
+                    "{}",
+                    if count == Count::Play {
+                        PLAY_CLAIM_HELP
+                    } else {
+                        SHOW_CLAIM_HELP
+                    }
+                );
+                continue;
+            }
+            let claims = match self.parse_claims(&line, count, index_scorer) {
+                Ok(claims) => claims,
+                Err(message) => {
+                    println!("{}", message);
+                    continue;
+                }
+            };
+
+            match self.send(GameEvent::ManScoreSelection(claims)) {
+                Ok(_) => return,
+                Err(e) => println!("{}", explain(&e)),
+            }
+        }
+    }
+
+    // Gives each opponent of the player who counted the chance to call the points they missed, in
+    // turn from their left. Bots call every point missed, and people are asked for their calls
+    // until the game accepts them
+    fn muggins(&mut self, count: Count) {
+        let index_counted = match count {
+            Count::Crib => self.game.index_dealer().unwrap(),
+            Count::Play | Count::Show => self.game.index_active().unwrap(),
+        };
+        let num_players = self.seats.len() as u8;
+        let partner_index = self.game.players()[index_counted as usize].partner_index;
+        let callers: Vec<u8> = (1..num_players)
+            .map(|offset| (index_counted + offset) % num_players)
+            .filter(|seat| Some(*seat) != partner_index)
+            .collect();
+
+        for seat in callers {
+            if self.game.state() == GameState::Win {
+                return;
+            }
+            if !is_human(&self.seats[seat as usize]) {
+                let calls: Vec<score::ScoreEvent> = self
+                    .game
+                    .missed_scoring()
+                    .iter()
+                    .map(|score_event| score::ScoreEvent {
+                        player_index: seat,
+                        ..score_event.clone()
+                    })
+                    .collect();
+                if !calls.is_empty() {
+                    self.send_bot(GameEvent::Muggins(Some(calls)));
+                }
+                continue;
+            }
+            // Bots always claim the full count so there is never anything to call on them
+            if !is_human(&self.seats[index_counted as usize]) {
+                continue;
+            }
+
+            self.hand_over(seat);
+            loop {
+                let line = self.read_line(&format!(
+                    "Call muggins on {}'s count? List the points missed or leave it blank (? for \
+                     help):",
+                    self.names[index_counted as usize]
+                ));
+                if line == "?" {
+                    println!(
+                        "{}",
+                        if count == Count::Play {
+                            PLAY_CLAIM_HELP
+                        } else {
+                            SHOW_CLAIM_HELP
+                        }
+                    );
+                    continue;
+                }
+                let calls: Vec<score::ScoreEvent> =
+                    match self.parse_claims(&line, count, index_counted) {
+                        Ok(claims) => claims
+                            .into_iter()
+                            .map(|claim| score::ScoreEvent {
+                                player_index: seat,
+                                ..claim
+                            })
+                            .collect(),
+                        Err(message) => {
+                            println!("{}", message);
+                            continue;
+                        }
+                    };
+                if calls.is_empty() {
+                    break;
+                }
+                match self.send(GameEvent::Muggins(Some(calls))) {
+                    Ok(_) => break,
+                    Err(e) => println!("{}", explain(&e)),
+                }
+            }
+        }
+
+        if self.game.state() != GameState::Win {
+            self.send_bot(GameEvent::Muggins(None));
+        }
+    }
+
+    fn parse_claims(
+        &self,
+        line: &str,
+        count: Count,
+        player_index: u8,
+    ) -> Result<Vec<score::ScoreEvent>, String> {
+        let settings = self.game.settings().unwrap();
+        let mut claims = Vec::new();
+        for claim in line
+            .split(',')
+            .map(str::trim)
+            .filter(|claim| !claim.is_empty())
+        {
+            let mut words: Vec<String> = claim.split_whitespace().map(str::to_lowercase).collect();
+            // A number at the end of a show claim gives its points
+            let points: Option<u8> = match words.last() {
+                Some(word) if count != Count::Play && words.len() > 1 => word.parse().ok(),
+                _ => None,
+            };
+            if points.is_some() {
+                words.pop();
+            }
+            let kind = words.remove(0);
+
+            let (score_type, point_value) = if count == Count::Play {
+                let play_score_type = match (kind.as_str(), words.first()) {
+                    ("15" | "fifteen", _) => score::PlayScoreType::Fifteen,
+                    ("31" | "thirty-one", _) => score::PlayScoreType::ThirtyOne,
+                    ("pair", _) => score::PlayScoreType::Pair,
+                    ("triple", _) => score::PlayScoreType::Triple,
+                    ("quad", _) => score::PlayScoreType::Quadruple,
+                    ("run", Some(length)) => match length.parse() {
+                        Ok(length) => score::PlayScoreType::Straight(length),
+                        Err(_) => return Err(format!("Give the length of the run in {}", claim)),
+                    },
+                    ("go" | "last", _) => score::PlayScoreType::LastCard,
+                    _ => return Err(format!("Unknown claim {}; enter ? for help", claim)),
+                };
+                let point_value = match play_score_type {
+                    score::PlayScoreType::Pair
+                    | score::PlayScoreType::Fifteen
+                    | score::PlayScoreType::ThirtyOne
+                    | score::PlayScoreType::Nibs => 2,
+                    score::PlayScoreType::Triple => 6,
+                    score::PlayScoreType::Quadruple => 12,
+                    score::PlayScoreType::Straight(length) => length,
+                    score::PlayScoreType::LastCard => settings.scoring_rules.go_points(),
+                };
+                (score::ScoreType::Play(play_score_type), point_value)
+            } else {
+                let mut cards = Vec::with_capacity(words.len());
+                for word in &words {
+                    cards.push(word.parse::<deck::Card>()?);
+                }
+                let starter = self.game.starter_card().unwrap();
+                let (show_score_type, point_value) = match kind.as_str() {
+                    "15" | "fifteen" => (score::ShowScoreType::Fifteen(cards), 2),
+                    "pair" => (score::ShowScoreType::Pair(cards), 2),
+                    "triple" => (score::ShowScoreType::Triple(cards), 6),
+                    "quad" => (score::ShowScoreType::Quadruple(cards), 12),
+                    "run" => {
+                        let length = cards.len() as u8;
+                        (score::ShowScoreType::Straight(cards), length)
+                    }
+                    "flush" if cards.len() == 5 => (score::ShowScoreType::FiveFlush(cards), 5),
+                    "flush" => (score::ShowScoreType::FourFlush(cards), 4),
+                    "nobs" => {
+                        cards.push(starter);
+                        (score::ShowScoreType::Nobs(cards), 1)
+                    }
+                    "wild" => (score::ShowScoreType::Wild(cards), 0),
+                    "nineteen" => {
+                        let mut cards = if count == Count::Crib {
+                            self.game.crib().to_vec()
+                        } else {
+                            self.game.players()[player_index as usize].hand.clone()
+                        };
+                        cards.push(starter);
+                        cards.sort();
+                        (
+                            score::ShowScoreType::Nineteen(cards),
+                            settings.scoring_rules.nineteen_bonus,
+                        )
+                    }
+                    _ => return Err(format!("Unknown claim {}; enter ? for help", claim)),
+                };
+                (score::ScoreType::Show(show_score_type), point_value)
+            };
+
+            claims.push(score::ScoreEvent {
+                score_type,
+                player_index,
+                point_value: points.unwrap_or(point_value),
+            });
+        }
+        Ok(claims)
+    }
+
+    fn win(&mut self) {
+        let first_human = self.seats.iter().position(is_human);
+        let event = match first_human {
+            Some(_) => {
+                let line = self.read_line("Play another game? (y/n)");
+                if is_yes(&line) {
+                    GameEvent::Confirmation
+                } else {
+                    GameEvent::Denial
+                }
+            }
+            None => GameEvent::Denial,
+        };
+        self.send_bot(event);
+    }
+
+    fn print_result(&self) {
+        let result = match self.game.match_standings().results.last() {
+            Some(result) => result,
+            None => return,
+        };
+        println!();
+        self.print_board();
+        let winners: Vec<&str> = result
+            .winners
+            .iter()
+            .map(|winner| self.names[*winner as usize].as_str())
+            .collect();
+        println!(
+            "{} win{} the game for {} game point{}",
+            winners.join(" and "),
+            if winners.len() == 1 { "s" } else { "" },
+            result.game_points,
+            if result.game_points == 1 { "" } else { "s" }
+        );
+        for double_skunked in &result.double_skunked {
+            println!("{} is double skunked", self.names[*double_skunked as usize]);
+        }
+        for skunked in &result.skunked {
+            if !result.double_skunked.contains(skunked) {
+                println!("{} is skunked", self.names[*skunked as usize]);
+            }
+        }
+    }
+
+    fn print_standings(&self) {
+        let standings = self.game.match_standings();
+        println!();
+        println!("Final standings");
+        for (index, name) in self.names.iter().enumerate() {
+            println!(
+                "  {:<24} {:>3} game points, {:>2} games won",
+                name,
+                standings.game_points.get(index).copied().unwrap_or(0),
+                standings.games_won.get(index).copied().unwrap_or(0)
+            );
+        }
+    }
+
+    // Prints everything the given player may see: the scores, the board, the starter, the count
+    // and their own hand
+    fn print_table(&self, viewer: u8) {
+        println!();
+        self.print_board();
+        let index_dealer = self.game.index_dealer().unwrap();
+        println!(
+            "Dealer: {}{}",
+            self.names[index_dealer as usize],
+            match self.game.starter_card() {
+                Some(starter) => format!("   Starter: {}", self.card_text(starter)),
+                None => String::new(),
+            }
+        );
+        if self.game.state() == GameState::PlayWaitForCard {
+            let play_group = self.game.play_groups().last().unwrap();
+            println!(
+                "Count: {}   {}",
+                play_group.total(),
+                self.cards_text(play_group.cards())
+            );
+        }
+
+        let hand = &self.game.players()[viewer as usize].hand;
+        let cards: Vec<String> = hand
+            .iter()
+            .enumerate()
+            .map(|(index, card)| {
+                if self.game.state() == GameState::PlayWaitForCard
+                    && self.game.has_card_been_played(viewer, index as u8)
+                {
+                    format!("[{}] ({})", index + 1, self.card_text(*card))
+                } else {
+                    format!("[{}] {}", index + 1, self.card_text(*card))
+                }
+            })
+            .collect();
+        println!(
+            "{}, your hand: {}",
+            self.names[viewer as usize],
+            cards.join("  ")
+        );
+    }
+
+    // Prints the score of each player and the pegs on the board; each street is drawn as a row of
+    // its thirty holes with the front peg, the back peg and the skunk lines of each track marked
+    fn print_board(&self) {
+        let players = self.game.players();
+        let (front_peg, back_peg, empty, line) = if self.is_ascii {
+            ('*', 'o', '.', ':')
+        } else {
+            ('●', '○', '·', '┊')
+        };
+
+        let board = match &self.board {
+            Some(board) => board,
+            None => {
+                for (index, player) in players.iter().enumerate() {
+                    println!("  {:<24} {:>3}", self.names[index], player.front_peg_pos);
+                }
+                return;
+            }
+        };
+
+        let mut tracks: Vec<(String, board::Pegs)> =
+            vec![(String::new(), board::Pegs::default()); board.num_tracks() as usize];
+        for (index, player) in players.iter().enumerate() {
+            let track = board.track(index as u8).unwrap() as usize;
+            if tracks[track].0.is_empty() {
+                tracks[track].1 = board::Pegs::from(player);
+            } else {
+                tracks[track].0.push_str(" & ");
+            }
+            tracks[track].0.push_str(&self.names[index]);
+        }
+        for (track, (name, pegs)) in tracks.iter().enumerate() {
+            println!(
+                "  {} {:<36} {:>3} of {}",
+                track_label(track),
+                name,
+                pegs.front,
+                board.target_score(track as u8)
+            );
+        }
+
+        for street in 0..board.size().num_streets() {
+            let first = street * board::HOLES_PER_STREET + 1;
+            for (track, (_, pegs)) in tracks.iter().enumerate() {
+                let skunk_lines = board.skunk_lines(track as u8);
+                let mut row = String::new();
+                for position in first..first + board::HOLES_PER_STREET {
+                    if position > first && (position - first) % board::HOLES_PER_GROUP == 0 {
+                        row.push(' ');
+                    }
+                    row.push(if position == pegs.front {
+                        front_peg
+                    } else if position == pegs.back {
+                        back_peg
+                    } else if position == skunk_lines.skunk || position == skunk_lines.double_skunk
+                    {
+                        line
+                    } else {
+                        empty
+                    });
+                }
+                let label = if track == 0 {
+                    format!("{:>3}-{:<3}", first, first + board::HOLES_PER_STREET - 1)
+                } else {
+                    String::from("       ")
+                };
+                println!("  {} {} {}", label, track_label(track), row);
+            }
+        }
+
+        let in_hole = |position: u8| -> String {
+            let labels: Vec<String> = tracks
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, pegs))| {
+                    board.hole(pegs.front) == board.hole(position)
+                        || board.hole(pegs.back) == board.hole(position)
+                })
+                .map(|(track, _)| track_label(track).to_string())
+                .collect();
+            if labels.is_empty() {
+                String::from("-")
+            } else {
+                labels.join(" ")
+            }
+        };
+        println!(
+            "  Start: {}   Game: {}",
+            in_hole(0),
+            in_hole(board.size().num_holes())
+        );
+    }
+
+    fn card_text(&self, card: deck::Card) -> String {
+        if self.is_ascii {
+            return card.to_string();
+        }
+        let suit = match card.suit {
+            deck::CardSuit::Hearts => '♥',
+            deck::CardSuit::Diamonds => '♦',
+            deck::CardSuit::Clubs => '♣',
+            deck::CardSuit::Spades => '♠',
+        };
+        let value = match card.value {
+            deck::CardValue::Ten => String::from("10"),
+            deck::CardValue::Joker => String::from("★"),
+            _ => card.to_string()[..1].to_string(),
+        };
+        match card.suit {
+            deck::CardSuit::Hearts | deck::CardSuit::Diamonds => {
+                format!("\x1b[31m{}{}\x1b[0m", value, suit)
+            }
+            deck::CardSuit::Clubs | deck::CardSuit::Spades => format!("{}{}", value, suit),
+        }
+    }
+
+    fn cards_text(&self, cards: &[deck::Card]) -> String {
+        let cards: Vec<String> = cards.iter().map(|card| self.card_text(*card)).collect();
+        cards.join(" ")
+    }
+
+    // Prompts for and returns a trimmed line of input; the game ends when the input does
+    fn read_line(&self, prompt: &str) -> String {
+        print!("{} ", prompt);
+        io::stdout().flush().unwrap();
+        let mut line = String::new();
+        match io::stdin().read_line(&mut line) {
+            Ok(0) | Err(_) => {
+                println!();
+                process::exit(0);
+            }
+            Ok(_) => line.trim().to_string(),
+        }
+    }
+}
+
+// Returns a helpful message for an error returned by the game
+fn explain(error: &game_process_return::Error) -> String {
+    use game_process_return::Error;
+
+    match error {
+        Error::ExpectedEvent(events) => format!(
+            "That can't be done now; the game is waiting for {}",
+            events
+                .iter()
+                .map(|event| sentence(&format!("{:?}", event)).to_lowercase())
+                .collect::<Vec<String>>()
+                .join(" or ")
+        ),
+        Error::GameStartInvalidConfig(config_error) => format!(
+            "These settings can't be played together: {}",
+            sentence(&format!("{:?}", config_error)).to_lowercase()
+        ),
+        Error::DiscardErrors(discard_errors) => discard_errors
+            .iter()
+            .map(|discard_error| sentence(&format!("{:?}", discard_error)))
+            .collect::<Vec<String>>()
+            .join("; "),
+        Error::NibsCallError(
+            game_process_return::NibsError::NoNibsCallWhenUnderscoringIsDisabled,
+        ) => String::from("A jack cut as the starter must be pegged for his heels"),
+        Error::NibsCallError(nibs_error) => sentence(&format!("{:?}", nibs_error)),
+        Error::PlayWaitForCardError(game_process_return::PlayTurnError::MustPlayCardIfAble) => {
+            String::from("You have a card you can play so you can't say go")
+        }
+        Error::PlayWaitForCardError(
+            game_process_return::PlayTurnError::PlayGroupTotalMayNotExceed31,
+        ) => String::from("That card would take the count past 31"),
+        Error::PlayWaitForCardError(
+            game_process_return::PlayTurnError::CardHasAlreadyBeenPlayed,
+        ) => String::from("That card has already been played"),
+        Error::PlayWaitForCardError(_) => String::from("There is no card with that number"),
+        Error::AuctionError(auction_error) => sentence(&format!("{:?}", auction_error)),
+        Error::GoDecisionError(go_decision_error) => sentence(&format!("{:?}", go_decision_error)),
+        Error::PlayScoreError(game_process_return::PlayScoreError::OverpeggingIsNotAllowed(
+            score_diff,
+        ))
+        | Error::ShowScoreError(game_process_return::ShowScoreError::OverpeggingIsNotAllowed(
+            score_diff,
+        ))
+        | Error::CribScoreError(game_process_return::ShowScoreError::OverpeggingIsNotAllowed(
+            score_diff,
+        )) => {
+            let overclaimed: Vec<String> = score_diff
+                .overclaimed
+                .iter()
+                .map(score::spoken::describe)
+                .collect();
+            format!(
+                "That count claims too much; these don't score: {}",
+                overclaimed.join("; ")
+            )
+        }
+        Error::PlayScoreError(game_process_return::PlayScoreError::UnderpeggingIsNotAllowed(
+            score_diff,
+        ))
+        | Error::ShowScoreError(game_process_return::ShowScoreError::UnderpeggingIsNotAllowed(
+            score_diff,
+        ))
+        | Error::CribScoreError(game_process_return::ShowScoreError::UnderpeggingIsNotAllowed(
+            score_diff,
+        )) => format!(
+            "There's more to count; {} of {} points were claimed",
+            score_diff.claimed_points, score_diff.optimal_points
+        ),
+        Error::MugginsError(game_process_return::MugginsError::OnlyAnOpponentMayCallMuggins) => {
+            String::from("Only an opponent may call muggins")
+        }
+        Error::MugginsError(game_process_return::MugginsError::CalledPointsWereNotMissed(
+            score_diff,
+        )) => {
+            let overclaimed: Vec<String> = score_diff
+                .overclaimed
+                .iter()
+                .map(score::spoken::describe)
+                .collect();
+            format!(
+                "These weren't missed or have already been called: {}",
+                overclaimed.join("; ")
+            )
+        }
+        Error::MatchIsOver => String::from("The match is over"),
+        Error::NotYourTurn => String::from("It isn't your turn"),
+        Error::SeatNotAllowed => String::from("That isn't yours to do"),
+        Error::TimeHasNotRunOut => String::from("There is still time to decide"),
+        Error::ImplementationError(_)
+        | Error::InitialCutError
+        | Error::UnimplementedState
+        | Error::UnrecognizedState => format!("Something went wrong in the game: {:?}", error),
+    }
+}
+
+// Turns the name of an error such as "CardIndicesMayNotBeRepeated(1)" into a sentence such as
+// "Card indices may not be repeated for player 2"
+fn sentence(name: &str) -> String {
+    let (words, player_index) = match name.find('(') {
+        Some(open) => (
+            &name[..open],
+            name[open + 1..name.len() - 1].parse::<u8>().ok(),
+        ),
+        None => (name, None),
+    };
+
+    let mut sentence = String::new();
+    for (index, c) in words.chars().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            sentence.push(' ');
+            sentence.extend(c.to_lowercase());
+        } else {
+            sentence.push(c);
+        }
+    }
+    if let Some(player_index) = player_index {
+        sentence.push_str(&format!(" for player {}", player_index + 1));
+    }
+    sentence
+}
+
+// Returns the distinct zero based indices given as one based numbers separated by spaces or commas
+fn parse_indices(line: &str, hand_len: usize) -> Option<Vec<u8>> {
+    let mut indices = Vec::new();
+    for word in line.split(|c: char| c == ',' || c.is_whitespace()) {
+        if word.is_empty() {
+            continue;
+        }
+        match word.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= hand_len => {
+                let index = (number - 1) as u8;
+                if indices.contains(&index) {
+                    return None;
+                }
+                indices.push(index);
+            }
+            _ => return None,
+        }
+    }
+    Some(indices)
+}
+
+fn track_label(track: usize) -> char {
+    (b'A' + track as u8) as char
+}
+
+fn is_human(seat: &Seat) -> bool {
+    matches!(seat, Seat::Human)
+}
+
+fn is_wild(score_event: &score::ScoreEvent) -> bool {
+    matches!(
+        score_event.score_type,
+        score::ScoreType::Show(score::ShowScoreType::Wild(_))
+    )
+}
+
+fn is_yes(line: &str) -> bool {
+    matches!(line.to_lowercase().as_str(), "y" | "yes")
+}
+
+fn victor_dealer_option(variant: settings::RuleVariant) -> settings::VictorDealerOption {
+    let preset = variant.preset();
+    if preset.num_players == 2 {
+        settings::VictorDealerOption::TwoPlayers
+    } else if preset.captain_index().is_some() {
+        settings::VictorDealerOption::CaptainDeals
+    } else {
+        settings::VictorDealerOption::LosersDrawForDealer
+    }
+}
+
+fn parse<T: std::str::FromStr>(value: &str) -> T {
+    match value.parse() {
+        Ok(parsed) => parsed,
+        Err(_) => exit_with_usage(&format!("Invalid value {}", value)),
+    }
+}
+
+fn exit_with_usage(message: &str) -> ! {
+    eprintln!("{}", message);
+    eprintln!("{}", USAGE);
+    process::exit(2);
+}
@@ -0,0 +1,532 @@
+use crate::score::ScoreEvent;
+use crate::settings::{RuleVariant, SkunkLines};
+
+// The geometry of the classic cribbage boards for drawing the pegs
+//
+// A board is made up of straight streets of thirty holes set out in six groups of five. Every
+// player or partnership pegs along their own track, the tracks of a street run side by side, and
+// the streets are laid out left to right with the first running up the board, the second back
+// down and so on. Each track starts from a start hole below the first street and every track
+// finishes in a single game hole shared at the end of the last street. A 61 hole board has two
+// streets, a 121 hole board has four and a 151 hole board has five
+//
+// Coordinates are in units of the distance between neighbouring holes with the origin at the
+// bottom of the first hole of the first track; x increases to the right and y up the board
+
+// The number of holes along each street and in each group of holes within a street
+pub const HOLES_PER_STREET: u8 = 30;
+pub const HOLES_PER_GROUP: u8 = 5;
+
+#[cfg(test)]
+mod test {
+    use super::{Board, BoardError, BoardSize, Hole, Peg, PegMove, Pegs, Point};
+    use crate::score::{PlayScoreType, ScoreEvent, ScoreType, ShowScoreType};
+    use crate::settings::{RuleVariant, SkunkLines};
+
+    #[test]
+    fn tracks_for_variants() {
+        let tracks = |variant: RuleVariant| Board::for_variant(variant).unwrap().num_tracks();
+        assert_eq!(tracks(RuleVariant::TwoStandard), 2);
+        assert_eq!(tracks(RuleVariant::ThreeStandard), 3);
+        assert_eq!(tracks(RuleVariant::ThreeCaptain), 2);
+        assert_eq!(tracks(RuleVariant::FourIndividual), 4);
+        assert_eq!(tracks(RuleVariant::FourPairs), 2);
+        assert_eq!(tracks(RuleVariant::SixPairs), 3);
+        assert_eq!(
+            Board::for_variant(RuleVariant::FiveStandard),
+            Err(BoardError::NoBoardForThisManyTracks)
+        );
+
+        // Partners share a track
+        let board = Board::for_variant(RuleVariant::SixPairs).unwrap();
+        assert_eq!(board.track(0), Some(0));
+        assert_eq!(board.track(3), Some(0));
+        assert_eq!(board.track(2), Some(2));
+        assert_eq!(board.track(5), Some(2));
+        assert_eq!(board.track(6), None);
+
+        let board = Board::for_variant(RuleVariant::ThreeCaptain).unwrap();
+        assert_eq!(board.size(), BoardSize::Holes121);
+        assert_eq!(board.track(1), board.track(2));
+        assert_eq!(board.target_score(board.track(0).unwrap()), 61);
+        assert_eq!(board.target_score(board.track(1).unwrap()), 121);
+    }
+
+    #[test]
+    fn sizes() {
+        assert_eq!(
+            Board::for_variant(RuleVariant::TwoFiveCard).unwrap().size(),
+            BoardSize::Holes61
+        );
+        assert_eq!(
+            Board::for_variant(RuleVariant::TwoSevenCard)
+                .unwrap()
+                .size(),
+            BoardSize::Holes151
+        );
+        assert_eq!(BoardSize::Holes61.num_streets(), 2);
+        assert_eq!(BoardSize::Holes121.num_streets(), 4);
+        assert_eq!(BoardSize::Holes151.num_streets(), 5);
+        assert_eq!(BoardSize::from_target_score(100), None);
+
+        let custom = crate::settings::CustomVariant::new(2).target_score(100);
+        assert_eq!(
+            Board::for_variant(RuleVariant::Custom(custom)),
+            Err(BoardError::NoBoardForThisTargetScore)
+        );
+        assert_eq!(
+            Board::new(BoardSize::Holes121, 5),
+            Err(BoardError::NoBoardForThisManyTracks)
+        );
+    }
+
+    #[test]
+    fn holes_and_coordinates() {
+        let board = Board::new(BoardSize::Holes121, 2).unwrap();
+        assert_eq!(board.hole(0), Hole::Start);
+        assert_eq!(board.hole(1), Hole::Street { street: 0, hole: 0 });
+        assert_eq!(
+            board.hole(30),
+            Hole::Street {
+                street: 0,
+                hole: 29
+            }
+        );
+        assert_eq!(board.hole(31), Hole::Street { street: 1, hole: 0 });
+        assert_eq!(
+            board.hole(120),
+            Hole::Street {
+                street: 3,
+                hole: 29
+            }
+        );
+        assert_eq!(board.hole(121), Hole::Game);
+        assert_eq!(board.hole(130), Hole::Game);
+
+        // The first street runs up the board with a gap after every group of five holes
+        assert_eq!(board.coordinates(0, 1), Point { x: 0.0, y: 0.0 });
+        assert_eq!(board.coordinates(1, 5), Point { x: 1.0, y: 4.0 });
+        assert_eq!(board.coordinates(1, 6), Point { x: 1.0, y: 6.0 });
+        assert_eq!(board.coordinates(0, 30), Point { x: 0.0, y: 34.0 });
+        // The second street starts at the top one track's width to the right of the first
+        assert_eq!(board.coordinates(0, 31), Point { x: 3.0, y: 34.0 });
+        assert_eq!(board.coordinates(1, 60), Point { x: 4.0, y: 0.0 });
+        assert_eq!(board.coordinates(1, 0), Point { x: 1.0, y: -2.0 });
+        // The game hole is shared and centred past the end of the last street, which runs down
+        assert_eq!(board.coordinates(0, 121), Point { x: 9.5, y: -2.0 });
+        assert_eq!(board.coordinates(1, 121), board.coordinates(0, 121));
+
+        let board = Board::new(BoardSize::Holes151, 3).unwrap();
+        assert_eq!(board.coordinates(2, 150), Point { x: 18.0, y: 34.0 });
+        assert_eq!(board.coordinates(0, 151), Point { x: 17.0, y: 36.0 });
+    }
+
+    #[test]
+    fn skunk_lines() {
+        let board = Board::for_variant(RuleVariant::TwoStandard).unwrap();
+        assert_eq!(
+            board.skunk_lines(0),
+            SkunkLines {
+                skunk: 91,
+                double_skunk: 61
+            }
+        );
+        assert_eq!(board.hole(91), Hole::Street { street: 3, hole: 0 });
+
+        let board = Board::for_variant(RuleVariant::ThreeCaptain).unwrap();
+        assert_eq!(board.skunk_lines(0).skunk, 31);
+        assert_eq!(board.skunk_lines(1).skunk, 91);
+    }
+
+    #[test]
+    fn peg_moves() {
+        let board = Board::for_variant(RuleVariant::FourPairs).unwrap();
+        let mut pegs = vec![Pegs::default(); 2];
+        let events = vec![
+            ScoreEvent {
+                score_type: ScoreType::Play(PlayScoreType::Fifteen),
+                player_index: 2,
+                point_value: 2,
+            },
+            ScoreEvent {
+                score_type: ScoreType::Play(PlayScoreType::LastCard),
+                player_index: 3,
+                point_value: 1,
+            },
+            ScoreEvent {
+                score_type: ScoreType::Show(ShowScoreType::Fifteen(Vec::new())),
+                player_index: 0,
+                point_value: 0,
+            },
+            ScoreEvent {
+                score_type: ScoreType::Show(ShowScoreType::Pair(Vec::new())),
+                player_index: 0,
+                point_value: 2,
+            },
+        ];
+        let moves = board.animate(&mut pegs, &events);
+
+        // Nothing is moved for an event worth no points
+        assert_eq!(moves.len(), 3);
+        assert_eq!(
+            moves[0],
+            PegMove {
+                track: 0,
+                from: 0,
+                to: 2,
+                peg: Peg::Second,
+                path: vec![
+                    board.coordinates(0, 0),
+                    board.coordinates(0, 1),
+                    board.coordinates(0, 2)
+                ],
+            }
+        );
+        assert_eq!(moves[1].track, 1);
+        assert_eq!(moves[1].to, 1);
+        // The back peg leapfrogs the front peg
+        assert_eq!(moves[2].from, 0);
+        assert_eq!(moves[2].to, 4);
+        assert_eq!(moves[2].peg, Peg::First);
+        assert_eq!(moves[2].path.len(), 3);
+        assert_eq!((pegs[0].back, pegs[0].front), (2, 4));
+        assert_eq!(pegs[0].front_peg, Peg::First);
+        assert_eq!((pegs[1].back, pegs[1].front), (0, 1));
+
+        // A peg stops in the game hole
+        let mut pegs = vec![
+            Pegs {
+                back: 115,
+                front: 119,
+                front_peg: Peg::Second,
+            },
+            Pegs::default(),
+        ];
+        let moves = board.animate(
+            &mut pegs,
+            &[ScoreEvent {
+                score_type: ScoreType::Play(PlayScoreType::Fifteen),
+                player_index: 0,
+                point_value: 4,
+            }],
+        );
+        assert_eq!(moves[0].to, 121);
+        assert_eq!(moves[0].path.last(), Some(&board.coordinates(0, 121)));
+        assert_eq!(pegs[0].front, 121);
+
+        // The captain's pegs stop at the captain's target short of the game hole
+        let board = Board::for_variant(RuleVariant::ThreeCaptain).unwrap();
+        let mut pegs = vec![
+            Pegs {
+                back: 55,
+                front: 59,
+                front_peg: Peg::Second,
+            },
+            Pegs::default(),
+        ];
+        let moves = board.animate(
+            &mut pegs,
+            &[ScoreEvent {
+                score_type: ScoreType::Play(PlayScoreType::Fifteen),
+                player_index: 0,
+                point_value: 6,
+            }],
+        );
+        assert_eq!(moves[0].to, 61);
+        assert_eq!(moves[0].path.len(), 3);
+        assert_eq!(moves[0].path.last(), Some(&board.coordinates(0, 61)));
+        assert_eq!(pegs[0].front, 61);
+    }
+}
+
+// The number of holes along each track including the game hole
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BoardSize {
+    Holes61,
+    Holes121,
+    Holes151,
+}
+
+impl BoardSize {
+    // Returns the board played to the given target score
+    pub fn from_target_score(target_score: u8) -> Option<BoardSize> {
+        match target_score {
+            61 => Some(BoardSize::Holes61),
+            121 => Some(BoardSize::Holes121),
+            151 => Some(BoardSize::Holes151),
+            _ => None,
+        }
+    }
+
+    // Returns the number of the game hole
+    pub fn num_holes(self) -> u8 {
+        match self {
+            BoardSize::Holes61 => 61,
+            BoardSize::Holes121 => 121,
+            BoardSize::Holes151 => 151,
+        }
+    }
+
+    pub fn num_streets(self) -> u8 {
+        (self.num_holes() - 1) / HOLES_PER_STREET
+    }
+
+    // Returns the skunk lines marked on the board, which are those of a game to its game hole
+    pub fn skunk_lines(self) -> SkunkLines {
+        match self {
+            BoardSize::Holes61 => SkunkLines {
+                skunk: 31,
+                double_skunk: 16,
+            },
+            BoardSize::Holes121 => SkunkLines {
+                skunk: 91,
+                double_skunk: 61,
+            },
+            BoardSize::Holes151 => SkunkLines {
+                skunk: 121,
+                double_skunk: 91,
+            },
+        }
+    }
+}
+
+// Where a peg sits on its track
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Hole {
+    // Before the first point is scored
+    Start,
+    // The index of the street from the start and of the hole along it in the direction of play
+    Street { street: u8, hole: u8 },
+    // At or past the end of the last street
+    Game,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Point {
+    pub x: f32,
+    pub y: f32,
+}
+
+// The two pegs of a track as positions along it like the back_peg_pos and front_peg_pos of a
+// Player along with which of the physical pegs is in front
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Pegs {
+    pub back: u8,
+    pub front: u8,
+    pub front_peg: Peg,
+}
+
+impl From<&crate::player::Player> for Pegs {
+    fn from(player: &crate::player::Player) -> Pegs {
+        Pegs {
+            back: player.back_peg_pos,
+            front: player.front_peg_pos,
+            front_peg: Peg::First,
+        }
+    }
+}
+
+// The two physical pegs of a track so that they can be told apart as they leapfrog each other;
+// both start in the start hole with the first counted as in front so the second peg moves first
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Peg {
+    #[default]
+    First,
+    Second,
+}
+
+// The move of one peg for a score, jumping from the back peg's hole past the front peg; the path
+// is the coordinates of the hole it leaves followed by every hole counted so that a peg can be
+// animated along the track
+#[derive(Clone, Debug, PartialEq)]
+pub struct PegMove {
+    pub track: u8,
+    pub from: u8,
+    pub to: u8,
+    pub peg: Peg,
+    pub path: Vec<Point>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum BoardError {
+    // Boards are made with two, three or four tracks
+    NoBoardForThisManyTracks,
+    // Boards are made with 61, 121 or 151 holes
+    NoBoardForThisTargetScore,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Board {
+    size: BoardSize,
+    // The track of each player by player index
+    tracks: Vec<u8>,
+    // The target score and skunk lines of each track by track index
+    target_scores: Vec<u8>,
+    skunk_lines: Vec<SkunkLines>,
+}
+
+impl Board {
+    // Returns a board with a track for each player
+    pub fn new(size: BoardSize, num_tracks: u8) -> Result<Board, BoardError> {
+        if !(2..=4).contains(&num_tracks) {
+            return Err(BoardError::NoBoardForThisManyTracks);
+        }
+        let target_score = size.num_holes();
+        Ok(Board {
+            size,
+            tracks: (0..num_tracks).collect(),
+            target_scores: vec![target_score; num_tracks as usize],
+            skunk_lines: vec![size.skunk_lines(); num_tracks as usize],
+        })
+    }
+
+    // Returns the board for the seats of a variant; partners share a track and the tracks are
+    // ordered by their first player such that a captain has their own track beside the pair. Five
+    // handed cribbage has no board as there are no boards with five tracks
+    pub fn for_variant(variant: RuleVariant) -> Result<Board, BoardError> {
+        let preset = variant.preset();
+        let size = BoardSize::from_target_score(variant.target_score(true))
+            .ok_or(BoardError::NoBoardForThisTargetScore)?;
+        let mut board = Board::new(size, preset.num_sides())?;
+
+        board.tracks.clear();
+        board.target_scores.clear();
+        board.skunk_lines.clear();
+        for index in 0..preset.num_players {
+            match preset.partner_indices[index as usize] {
+                Some(partner_index) if partner_index < index => {
+                    let track = board.tracks[partner_index as usize];
+                    board.tracks.push(track);
+                }
+                partner_index => {
+                    let has_partner = partner_index.is_some();
+                    board.tracks.push(board.target_scores.len() as u8);
+                    board.target_scores.push(variant.target_score(has_partner));
+                    board.skunk_lines.push(variant.skunk_lines(has_partner));
+                }
+            }
+        }
+        Ok(board)
+    }
+
+    pub fn size(&self) -> BoardSize {
+        self.size
+    }
+
+    pub fn num_tracks(&self) -> u8 {
+        self.target_scores.len() as u8
+    }
+
+    // Returns the track pegged by the player
+    pub fn track(&self, player_index: u8) -> Option<u8> {
+        self.tracks.get(player_index as usize).copied()
+    }
+
+    // Returns the score the pegs of the track must reach to win, which is short of the game hole
+    // for a captain
+    pub fn target_score(&self, track: u8) -> u8 {
+        self.target_scores[track as usize]
+    }
+
+    pub fn skunk_lines(&self, track: u8) -> SkunkLines {
+        self.skunk_lines[track as usize]
+    }
+
+    // Returns the hole of a peg at the given position
+    pub fn hole(&self, position: u8) -> Hole {
+        if position == 0 {
+            Hole::Start
+        } else if position >= self.size.num_holes() {
+            Hole::Game
+        } else {
+            Hole::Street {
+                street: (position - 1) / HOLES_PER_STREET,
+                hole: (position - 1) % HOLES_PER_STREET,
+            }
+        }
+    }
+
+    // Returns the coordinates of the centre of the hole of a peg at the given position
+    pub fn coordinates(&self, track: u8, position: u8) -> Point {
+        let num_tracks = f32::from(self.num_tracks());
+        // Each street is as wide as its tracks with a gap of one track between streets
+        let street_x = |street: u8| f32::from(street) * (num_tracks + 1.0);
+        // The distance from the first hole to the last along a street including the group gaps
+        let street_length = f32::from(HOLES_PER_STREET + HOLES_PER_STREET / HOLES_PER_GROUP - 2);
+
+        match self.hole(position) {
+            Hole::Start => Point {
+                x: f32::from(track),
+                y: -2.0,
+            },
+            Hole::Street { street, hole } => {
+                let distance = f32::from(hole + hole / HOLES_PER_GROUP);
+                Point {
+                    x: street_x(street) + f32::from(track),
+                    y: if street % 2 == 0 {
+                        distance
+                    } else {
+                        street_length - distance
+                    },
+                }
+            }
+            Hole::Game => {
+                let last_street = self.size.num_streets() - 1;
+                Point {
+                    x: street_x(last_street) + (num_tracks - 1.0) / 2.0,
+                    y: if last_street % 2 == 0 {
+                        street_length + 2.0
+                    } else {
+                        -2.0
+                    },
+                }
+            }
+        }
+    }
+
+    // Returns the moves of the pegs for a stream of score events starting from the given pegs of
+    // each track, which are updated as the pegs are moved in the same way as Player::change_score;
+    // partners pegging for each other move the pegs of their one track, events worth no points
+    // move nothing and a peg stops at the target score of its track
+    pub fn animate(&self, pegs: &mut [Pegs], score_events: &[ScoreEvent]) -> Vec<PegMove> {
+        let mut peg_moves = Vec::new();
+        for event in score_events {
+            if event.point_value == 0 {
+                continue;
+            }
+            let track = match self.track(event.player_index) {
+                Some(track) => track,
+                None => continue,
+            };
+            let track_pegs = &mut pegs[track as usize];
+            let from = track_pegs.back;
+            let to = track_pegs
+                .front
+                .saturating_add(event.point_value)
+                .min(self.target_score(track));
+            let peg = match track_pegs.front_peg {
+                Peg::First => Peg::Second,
+                Peg::Second => Peg::First,
+            };
+
+            let mut path = vec![self.coordinates(track, from)];
+            path.extend(
+                (track_pegs.front + 1..=to).map(|position| self.coordinates(track, position)),
+            );
+            *track_pegs = Pegs {
+                back: track_pegs.front,
+                front: to,
+                front_peg: peg,
+            };
+
+            peg_moves.push(PegMove {
+                track,
+                from,
+                to,
+                peg,
+                path,
+            });
+        }
+        peg_moves
+    }
+}
@@ -0,0 +1,448 @@
+use crate::deck;
+use crate::score;
+use crate::solitaire::{Line, LineScore};
+
+// Cross cribbage, also known as cribbage squares, for any number of players
+//
+// Cards are called out one at a time from a single shuffled deck and every player places the same
+// card into an empty cell of their own five by five grid. Players place each card at the same time
+// and a placement is kept hidden from the other players until everyone has placed the card, so
+// nobody learns anything from another player's grid before committing to their own. Once all
+// twenty five cards are placed every row and column is scored as a five card hand without a
+// starter, so a flush needs all five cards and there is no nobs, and the players are ranked by
+// their totals
+
+// The number of rows and of columns of each grid
+pub const GRID_SIZE: usize = 5;
+
+#[cfg(test)]
+mod test {
+    use super::{CrossError, CrossEvent, CrossGame, CrossReturn, CrossStanding, CrossState};
+    use crate::solitaire::Line;
+    use crate::util::return_card;
+
+    fn place(game: &mut CrossGame, player_index: u8, row: u8, column: u8) -> CrossReturn {
+        game.process_event(CrossEvent::Place {
+            player_index,
+            row,
+            column,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn simultaneous_placement() {
+        let mut game = CrossGame::from_seed(3, 11).unwrap();
+        assert_eq!(game.state(), CrossState::Place);
+        let called = game.called_card().unwrap();
+
+        assert_eq!(place(&mut game, 2, 0, 0), CrossReturn::Placed);
+        // Placements are hidden until every player has placed the card
+        assert_eq!(game.grid(2)[0][0], None);
+        assert!(game.has_placed(2));
+        assert_eq!(
+            game.process_event(CrossEvent::Place {
+                player_index: 2,
+                row: 1,
+                column: 1
+            }),
+            Err(CrossError::PlayerHasAlreadyPlacedThisCard)
+        );
+        assert_eq!(
+            game.process_event(CrossEvent::Place {
+                player_index: 3,
+                row: 0,
+                column: 0
+            }),
+            Err(CrossError::PlayerIndexIsNotAtTheTable)
+        );
+        assert_eq!(
+            game.process_event(CrossEvent::Place {
+                player_index: 0,
+                row: 0,
+                column: 5
+            }),
+            Err(CrossError::CellIsOutsideTheGrid)
+        );
+
+        place(&mut game, 0, 4, 4);
+        let next = match place(&mut game, 1, 2, 3) {
+            CrossReturn::CardCalled(card) => card,
+            other => panic!("unexpected return {:?}", other),
+        };
+        assert_eq!(game.called_card(), Some(next));
+        assert_eq!(game.grid(0)[4][4], Some(called));
+        assert_eq!(game.grid(1)[2][3], Some(called));
+        assert_eq!(game.grid(2)[0][0], Some(called));
+        assert!(!game.has_placed(2));
+
+        assert_eq!(
+            game.process_event(CrossEvent::Place {
+                player_index: 0,
+                row: 4,
+                column: 4
+            }),
+            Err(CrossError::CellIsTaken)
+        );
+    }
+
+    #[test]
+    fn at_least_one_player() {
+        assert_eq!(
+            CrossGame::new(0).err(),
+            Some(CrossError::MustHaveAtLeastOnePlayer)
+        );
+    }
+
+    // Two players place the same cards: the first fills each row in turn and the second each column
+    // in turn, so the second player's grid is the first player's flipped along the diagonal and the
+    // two score the same. A third player scatters the cards and scores differently
+    #[test]
+    fn final_ranking() {
+        let mut game = CrossGame::from_seed(3, 5).unwrap();
+        let mut called = Vec::new();
+        for position in 0..25u8 {
+            called.push(game.called_card().unwrap());
+            let (row, column) = (position / 5, position % 5);
+            place(&mut game, 0, row, column);
+            place(&mut game, 1, column, row);
+            let result = place(&mut game, 2, (row + column) % 5, row);
+            if position == 24 {
+                assert_eq!(result, CrossReturn::Complete(game.ranking().to_vec()));
+            }
+        }
+        assert_eq!(game.state(), CrossState::Complete);
+        assert_eq!(game.called_card(), None);
+        assert_eq!(
+            game.process_event(CrossEvent::Place {
+                player_index: 0,
+                row: 0,
+                column: 0
+            }),
+            Err(CrossError::GameIsComplete)
+        );
+
+        let first = game.score(0).unwrap();
+        let second = game.score(1).unwrap();
+        assert_eq!(first.total, second.total);
+        assert_eq!(first.lines.len(), 10);
+        assert_eq!(first.lines[0].line, Line::Row(0));
+        assert_eq!(first.lines[0].cards, called[0..5].to_vec());
+        assert_eq!(second.lines[5].line, Line::Column(0));
+        assert_eq!(second.lines[5].cards, called[0..5].to_vec());
+        assert_eq!(
+            first.total,
+            first.lines.iter().map(|line| line.points()).sum::<u16>()
+        );
+        for line in &second.lines {
+            assert!(line
+                .score_events
+                .iter()
+                .all(|score_event| score_event.player_index == 1));
+        }
+
+        // Ties share a rank and the next rank skips the tied places
+        let ranking = game.ranking();
+        let third = game.score(2).unwrap().total;
+        let expected = if third > first.total {
+            vec![(2, third, 1), (0, first.total, 2), (1, first.total, 2)]
+        } else if third == first.total {
+            vec![(0, third, 1), (1, third, 1), (2, third, 1)]
+        } else {
+            vec![(0, first.total, 1), (1, first.total, 1), (2, third, 3)]
+        };
+        let expected: Vec<CrossStanding> = expected
+            .into_iter()
+            .map(|(player_index, total, rank)| CrossStanding {
+                player_index,
+                total,
+                rank,
+            })
+            .collect();
+        assert_eq!(ranking, &expected[..]);
+    }
+
+    // A five card hand scores a five card flush but a four card flush and a jack of the suit of
+    // another card score nothing without a starter
+    #[test]
+    fn line_scoring() {
+        let cards = |codes: &[&str]| -> Vec<crate::deck::Card> {
+            codes
+                .iter()
+                .map(|code| {
+                    let mut chars = code.chars();
+                    return_card(chars.next().unwrap(), chars.next().unwrap())
+                })
+                .collect()
+        };
+
+        let flush = super::score_line(0, Line::Row(0), cards(&["2H", "3H", "6H", "8H", "KH"]));
+        assert_eq!(flush.points(), 5 + 2);
+
+        let no_flush = super::score_line(0, Line::Row(0), cards(&["2H", "3H", "6H", "8H", "KS"]));
+        assert_eq!(no_flush.points(), 2);
+
+        let jack = super::score_line(0, Line::Row(0), cards(&["JH", "2H", "6C", "8S", "KD"]));
+        assert_eq!(jack.points(), 0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossEvent {
+    // Places the called card into the given cell of a player's grid, the row from the top and the
+    // column from the left
+    Place {
+        player_index: u8,
+        row: u8,
+        column: u8,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrossState {
+    Place,
+    Complete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrossError {
+    MustHaveAtLeastOnePlayer,
+    GameIsComplete,
+    PlayerIndexIsNotAtTheTable,
+    PlayerHasAlreadyPlacedThisCard,
+    CellIsOutsideTheGrid,
+    CellIsTaken,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CrossReturn {
+    // The placement is held until the other players have placed the card
+    Placed,
+    // Every player has placed the card and the next card is called
+    CardCalled(deck::Card),
+    // Every card has been placed and the players are ranked
+    Complete(Vec<CrossStanding>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GridScore {
+    // Each row from the top and then each column from the left
+    pub lines: Vec<LineScore>,
+    pub total: u16,
+}
+
+// A player's place in the final ranking; players with the same total share the best rank of them
+// such that totals of 40, 35, 35 and 20 are ranked 1, 2, 2 and 4
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CrossStanding {
+    pub player_index: u8,
+    pub total: u16,
+    pub rank: u8,
+}
+
+type Grid = [[Option<deck::Card>; GRID_SIZE]; GRID_SIZE];
+
+pub struct CrossGame {
+    deck: deck::Deck,
+    state: CrossState,
+    called_card: Option<deck::Card>,
+    cards_called: u8,
+    grids: Vec<Grid>,
+    // The cell each player has chosen for the called card, kept apart from the grids until every
+    // player has chosen
+    pending: Vec<Option<(u8, u8)>>,
+    scores: Vec<GridScore>,
+    ranking: Vec<CrossStanding>,
+}
+
+impl CrossGame {
+    pub fn new(num_players: u8) -> Result<CrossGame, CrossError> {
+        let mut deck = deck::Deck::new();
+        deck.reset_deck();
+        CrossGame::from_deck(num_players, deck)
+    }
+
+    // Returns a game whose shuffle is determined by the given seed
+    pub fn from_seed(num_players: u8, seed: u64) -> Result<CrossGame, CrossError> {
+        let mut deck = deck::Deck::from_seed(seed);
+        deck.reset_deck();
+        CrossGame::from_deck(num_players, deck)
+    }
+
+    // Starts a game with the deck as it is and calls the first card
+    fn from_deck(num_players: u8, mut deck: deck::Deck) -> Result<CrossGame, CrossError> {
+        if num_players == 0 {
+            return Err(CrossError::MustHaveAtLeastOnePlayer);
+        }
+        let called_card = Some(deck.deal());
+        Ok(CrossGame {
+            deck,
+            state: CrossState::Place,
+            called_card,
+            cards_called: 1,
+            grids: vec![[[None; GRID_SIZE]; GRID_SIZE]; num_players as usize],
+            pending: vec![None; num_players as usize],
+            scores: Vec::new(),
+            ranking: Vec::new(),
+        })
+    }
+
+    pub fn state(&self) -> CrossState {
+        self.state
+    }
+
+    pub fn num_players(&self) -> u8 {
+        self.grids.len() as u8
+    }
+
+    // The card every player is placing
+    pub fn called_card(&self) -> Option<deck::Card> {
+        self.called_card
+    }
+
+    // A player's grid without the placement of the called card
+    pub fn grid(&self, player_index: u8) -> &Grid {
+        &self.grids[player_index as usize]
+    }
+
+    // Whether a player has chosen a cell for the called card
+    pub fn has_placed(&self, player_index: u8) -> bool {
+        self.pending[player_index as usize].is_some()
+    }
+
+    // The breakdown of a player's grid once the game is complete
+    pub fn score(&self, player_index: u8) -> Option<&GridScore> {
+        self.scores.get(player_index as usize)
+    }
+
+    // The players from best to worst once the game is complete
+    pub fn ranking(&self) -> &[CrossStanding] {
+        &self.ranking
+    }
+
+    pub fn process_event(&mut self, event: CrossEvent) -> Result<CrossReturn, CrossError> {
+        match (self.state, event) {
+            (
+                CrossState::Place,
+                CrossEvent::Place {
+                    player_index,
+                    row,
+                    column,
+                },
+            ) => self.process_placement(player_index, row, column),
+            (CrossState::Complete, _) => Err(CrossError::GameIsComplete),
+        }
+    }
+
+    // Holds a player's choice of cell and, once every player has chosen, places the called card
+    // into every grid and either calls the next card or scores the grids
+    fn process_placement(
+        &mut self,
+        player_index: u8,
+        row: u8,
+        column: u8,
+    ) -> Result<CrossReturn, CrossError> {
+        let grid = match self.grids.get(player_index as usize) {
+            Some(grid) => grid,
+            None => return Err(CrossError::PlayerIndexIsNotAtTheTable),
+        };
+        if self.pending[player_index as usize].is_some() {
+            return Err(CrossError::PlayerHasAlreadyPlacedThisCard);
+        }
+        match grid
+            .get(row as usize)
+            .and_then(|cells| cells.get(column as usize))
+        {
+            None => return Err(CrossError::CellIsOutsideTheGrid),
+            Some(Some(_)) => return Err(CrossError::CellIsTaken),
+            Some(None) => {}
+        }
+        self.pending[player_index as usize] = Some((row, column));
+
+        if self.pending.iter().any(Option::is_none) {
+            return Ok(CrossReturn::Placed);
+        }
+
+        let card = self.called_card.take();
+        for (grid, pending) in self.grids.iter_mut().zip(self.pending.iter_mut()) {
+            let (row, column) = pending.take().unwrap();
+            grid[row as usize][column as usize] = card;
+        }
+
+        if usize::from(self.cards_called) < GRID_SIZE * GRID_SIZE {
+            let next = self.deck.deal();
+            self.called_card = Some(next);
+            self.cards_called += 1;
+            return Ok(CrossReturn::CardCalled(next));
+        }
+
+        self.scores = self
+            .grids
+            .iter()
+            .enumerate()
+            .map(|(player_index, grid)| score_grid(player_index as u8, grid))
+            .collect();
+        self.ranking = rank(&self.scores);
+        self.state = CrossState::Complete;
+        Ok(CrossReturn::Complete(self.ranking.clone()))
+    }
+}
+
+fn score_grid(player_index: u8, grid: &Grid) -> GridScore {
+    let mut lines = Vec::with_capacity(2 * GRID_SIZE);
+    for (row, cells) in grid.iter().enumerate() {
+        let cards = cells.iter().flatten().copied().collect();
+        lines.push(score_line(player_index, Line::Row(row as u8), cards));
+    }
+    for column in 0..GRID_SIZE {
+        let cards = grid.iter().filter_map(|cells| cells[column]).collect();
+        lines.push(score_line(player_index, Line::Column(column as u8), cards));
+    }
+
+    let total = lines.iter().map(LineScore::points).sum();
+    GridScore { lines, total }
+}
+
+// Scores five cards without a starter by scoring the first four as a crib with the last as its
+// starter, so that a flush must include all five cards, and dropping nobs; every other score type
+// doesn't depend on which card is the starter
+fn score_line(player_index: u8, line: Line, cards: Vec<deck::Card>) -> LineScore {
+    let score_events = match cards.split_last() {
+        Some((last, first)) => score::show::score_hand(player_index, first, *last, true)
+            .into_iter()
+            .filter(|score_event| {
+                !matches!(
+                    score_event.score_type,
+                    score::ScoreType::Show(score::ShowScoreType::Nobs(_))
+                )
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    LineScore {
+        line,
+        cards,
+        score_events,
+    }
+}
+
+// Orders the players by total, keeping the order of the players when tied
+fn rank(scores: &[GridScore]) -> Vec<CrossStanding> {
+    let mut order: Vec<u8> = (0..scores.len() as u8).collect();
+    order.sort_by(|a, b| scores[*b as usize].total.cmp(&scores[*a as usize].total));
+
+    let mut ranking: Vec<CrossStanding> = Vec::with_capacity(order.len());
+    for (place, player_index) in order.into_iter().enumerate() {
+        let total = scores[player_index as usize].total;
+        let rank = match ranking.last() {
+            Some(previous) if previous.total == total => previous.rank,
+            _ => place as u8 + 1,
+        };
+        ranking.push(CrossStanding {
+            player_index,
+            total,
+            rank,
+        });
+    }
+    ranking
+}
@@ -1,7 +1,8 @@
 extern crate rand;
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
-use rand::thread_rng;
-use std::cmp::Ordering;
+use rand::SeedableRng;
+use serde::{Deserialize, Serialize};
 
 #[cfg(test)]
 mod test {
@@ -78,6 +79,36 @@ mod test {
         assert_eq!(super::return_value(crate::util::return_card('K', 'H')), 13);
     }
 
+    // A joker has no rank and no play value of its own
+    #[test]
+    fn joker_values() {
+        assert_eq!(super::return_value(super::RED_JOKER), 0);
+        assert_eq!(super::return_play_value(super::BLACK_JOKER), 0);
+        assert!(super::RED_JOKER.is_joker());
+        assert!(!crate::util::return_card('K', 'H').is_joker());
+    }
+
+    // Jokers are added below the rest of the deck so that an organized deck deals them last
+    #[test]
+    fn jokers() {
+        let mut deck = super::Deck::new();
+        deck.set_num_jokers(2);
+        for _ in 0..52 {
+            assert!(!deck.deal().is_joker());
+        }
+        assert_eq!(deck.deal(), super::BLACK_JOKER);
+        assert_eq!(deck.deal(), super::RED_JOKER);
+
+        deck.reset_deck();
+        let mut num_jokers = 0;
+        for _ in 0..54 {
+            if deck.deal().is_joker() {
+                num_jokers += 1;
+            }
+        }
+        assert_eq!(num_jokers, 2);
+    }
+
     #[test]
     fn reset_deck() {
         let mut deck = super::Deck::new();
@@ -87,6 +118,19 @@ mod test {
         assert!(deck != first_deck);
     }
 
+    #[test]
+    fn seeded_shuffle() {
+        let mut first_deck = super::Deck::from_seed(7);
+        let mut second_deck = super::Deck::from_seed(7);
+        first_deck.reset_deck();
+        second_deck.reset_deck();
+        assert_eq!(first_deck, second_deck);
+
+        let mut other_deck = super::Deck::from_seed(8);
+        other_deck.reset_deck();
+        assert!(first_deck != other_deck);
+    }
+
     #[test]
     fn deal() {
         let mut deck = super::Deck::new();
@@ -96,6 +140,25 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_card() {
+        let parse = |text: &str| text.parse::<super::Card>();
+        assert_eq!(parse("5H"), Ok(crate::util::return_card('5', 'H')));
+        assert_eq!(parse("th"), Ok(crate::util::return_card('T', 'H')));
+        assert_eq!(parse("10S"), Ok(crate::util::return_card('T', 'S')));
+        assert_eq!(parse("AC"), Ok(crate::util::return_card('A', 'C')));
+        assert_eq!(parse("XS"), Ok(super::BLACK_JOKER));
+        assert!(parse("1H").is_err());
+        assert!(parse("5").is_err());
+        assert!(parse("5HH").is_err());
+        assert!(parse("ZH").is_err());
+
+        for card in super::standard_cards() {
+            assert_eq!(parse(&card.to_string()), Ok(card));
+        }
+        assert_eq!(super::RED_JOKER.to_string(), "XH");
+    }
+
     #[test]
     #[should_panic(expected = "Dealt more than 52 cards")]
     fn deal_past_52() {
@@ -106,7 +169,7 @@ mod test {
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CardSuit {
     Hearts,
     Diamonds,
@@ -114,7 +177,7 @@ pub enum CardSuit {
     Spades,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum CardValue {
     Ace,
     Two,
@@ -129,8 +192,20 @@ pub enum CardValue {
     Jack,
     Queen,
     King,
+    // A wild card in the show; see score::show::score_hand
+    Joker,
 }
 
+// The jokers of a deck; a joker has no suit of its own and the suit only tells the two apart
+pub const RED_JOKER: Card = Card {
+    value: CardValue::Joker,
+    suit: CardSuit::Hearts,
+};
+pub const BLACK_JOKER: Card = Card {
+    value: CardValue::Joker,
+    suit: CardSuit::Spades,
+};
+
 pub fn return_value(card: Card) -> u8 {
     match card.value {
         CardValue::Ace => 1,
@@ -146,6 +221,7 @@ pub fn return_value(card: Card) -> u8 {
         CardValue::Jack => 11,
         CardValue::Queen => 12,
         CardValue::King => 13,
+        CardValue::Joker => 0,
     }
 }
 
@@ -164,29 +240,144 @@ pub fn return_play_value(card: Card) -> u8 {
         CardValue::Jack => 10,
         CardValue::Queen => 10,
         CardValue::King => 10,
+        // The play value of a joker is given by the JokerRules of the game; see
+        // settings::GameSettings::play_value
+        CardValue::Joker => 0,
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Card {
     pub value: CardValue,
     pub suit: CardSuit,
 }
 
-#[derive(Debug, Clone, PartialEq)]
+impl Card {
+    pub fn is_joker(&self) -> bool {
+        self.value == CardValue::Joker
+    }
+}
+
+// Parses a card in short notation, the value followed by the suit such as "5H", "TD" or "10D" for
+// the ten of diamonds, "QS" and "XH" for the red joker; letters may be in either case
+impl std::str::FromStr for Card {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Card, String> {
+        let upper = text.to_uppercase();
+        let (value, suit) = match upper.char_indices().last() {
+            Some((index, _)) if index > 0 => upper.split_at(index),
+            _ => return Err(format!("Unknown card {}", text)),
+        };
+
+        let value = match value {
+            "A" => CardValue::Ace,
+            "2" => CardValue::Two,
+            "3" => CardValue::Three,
+            "4" => CardValue::Four,
+            "5" => CardValue::Five,
+            "6" => CardValue::Six,
+            "7" => CardValue::Seven,
+            "8" => CardValue::Eight,
+            "9" => CardValue::Nine,
+            "T" | "10" => CardValue::Ten,
+            "J" => CardValue::Jack,
+            "Q" => CardValue::Queen,
+            "K" => CardValue::King,
+            "X" => CardValue::Joker,
+            _ => return Err(format!("Unknown card value in {}", text)),
+        };
+        let suit = match suit {
+            "H" => CardSuit::Hearts,
+            "D" => CardSuit::Diamonds,
+            "C" => CardSuit::Clubs,
+            "S" => CardSuit::Spades,
+            _ => return Err(format!("Unknown card suit in {}", text)),
+        };
+
+        Ok(Card { value, suit })
+    }
+}
+
+// Writes the card in the short notation parsed by from_str with T for a ten
+impl std::fmt::Display for Card {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let value = match self.value {
+            CardValue::Ace => 'A',
+            CardValue::Two => '2',
+            CardValue::Three => '3',
+            CardValue::Four => '4',
+            CardValue::Five => '5',
+            CardValue::Six => '6',
+            CardValue::Seven => '7',
+            CardValue::Eight => '8',
+            CardValue::Nine => '9',
+            CardValue::Ten => 'T',
+            CardValue::Jack => 'J',
+            CardValue::Queen => 'Q',
+            CardValue::King => 'K',
+            CardValue::Joker => 'X',
+        };
+        let suit = match self.suit {
+            CardSuit::Hearts => 'H',
+            CardSuit::Diamonds => 'D',
+            CardSuit::Clubs => 'C',
+            CardSuit::Spades => 'S',
+        };
+        write!(f, "{}{}", value, suit)
+    }
+}
+
+// Returns every card of a deck without jokers, the ace to the king of hearts first and the king of
+// spades last
+pub(crate) fn standard_cards() -> Vec<Card> {
+    let mut deck = Deck::from_seed(0);
+    deck.card_vector.reverse();
+    deck.card_vector
+}
+
+#[derive(Debug, Clone)]
 pub struct Deck {
     card_vector: Vec<Card>,
+
+    // The number of jokers added to the 52 cards, at most two
+    num_jokers: u8,
+
+    // The source of randomness used to shuffle; seeded from entropy unless the deck is created with
+    // from_seed such that a game may be replayed exactly
+    rng: StdRng,
+}
+
+// Decks are equal when they contain the same cards in the same order regardless of the state of
+// their random number generators
+impl PartialEq for Deck {
+    fn eq(&self, other: &Deck) -> bool {
+        self.card_vector == other.card_vector
+    }
+}
+
+impl Default for Deck {
+    fn default() -> Deck {
+        Deck::new()
+    }
 }
 
 impl Deck {
     // Returns a Deck from a given deck of cards for debug purposes
+    #[cfg(test)]
     pub(crate) fn from_vec(cards: Vec<Card>) -> Deck {
-        Deck { card_vector: cards }
+        Deck {
+            card_vector: cards,
+            num_jokers: 0,
+            rng: StdRng::from_entropy(),
+        }
     }
 
     pub fn new() -> Deck {
         let mut deck = Deck {
             card_vector: Vec::new(),
+            num_jokers: 0,
+            rng: StdRng::from_entropy(),
         };
 
         deck.populate();
@@ -194,11 +385,43 @@ impl Deck {
         deck
     }
 
+    // Returns an organized deck whose shuffles are determined by the given seed
+    pub fn from_seed(seed: u64) -> Deck {
+        let mut deck = Deck {
+            card_vector: Vec::new(),
+            num_jokers: 0,
+            rng: StdRng::seed_from_u64(seed),
+        };
+
+        deck.populate();
+
+        deck
+    }
+
+    // Sets the number of jokers in the deck, at most two, and returns the deck to an organized
+    // state including them
+    pub fn set_num_jokers(&mut self, num_jokers: u8) {
+        self.num_jokers = num_jokers.min(2);
+        self.populate();
+    }
+
+    pub fn num_jokers(&self) -> u8 {
+        self.num_jokers
+    }
+
     // Clears the card vector of the deck and populates it with an organized deck of cards
     fn populate(&mut self) {
         // Ensures that the deck does not have cards in it when it's populated
         self.card_vector.clear();
 
+        // The jokers go to the bottom of the deck
+        for joker in [RED_JOKER, BLACK_JOKER]
+            .iter()
+            .take(self.num_jokers as usize)
+        {
+            self.card_vector.push(*joker);
+        }
+
         // For each possible suit
         for suit_loop in (0..4).rev() {
             let active_suit = match suit_loop {
@@ -241,8 +464,7 @@ impl Deck {
 
     // Randomizes the order of the deck
     fn shuffle(&mut self) {
-        let mut rng = thread_rng();
-        self.card_vector.shuffle(&mut rng);
+        self.card_vector.shuffle(&mut self.rng);
     }
 
     // Resets the deck to a full and shuffled state
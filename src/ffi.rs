@@ -0,0 +1,796 @@
+// The rules for the pointers passed to the unsafe functions are given once in the module comment
+#![allow(clippy::missing_safety_doc)]
+
+use crate::game_process_return;
+use crate::{Game, GameEvent, GoDecision, PlayTurn};
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+// A C interface to Game for clients not written in Rust, built into the cdylib of the crate with
+// its header at ffi/cribbage.h as written by the cribbage-ffi-header binary; ffi/test_cribbage.c
+// shows its use
+//
+// A game is an opaque CribbageGame made with cribbage_game_create and freed with
+// cribbage_game_destroy. Events are sent either as a CribbageEvent, which covers the events made
+// of a few numbers, or as the JSON of a GameEvent, which covers every event. Every call returns a
+// status code, CRIBBAGE_OK or one per variant of game_process_return::Error followed by the errors
+// of the interface itself, and the full result of the last event may be read as JSON. Strings
+// returned by the library belong to the caller and are freed with cribbage_string_free
+//
+// No pointer passed to a function may be used by another thread during the call, and a game or
+// string may not be used once it has been freed
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const GAME_SETUP: &str = r#"{"GameSetup":{"variant":"TwoStandard","victor_dealer_option":"TwoPlayers","is_manual_scoring":false,"is_underpegging":false,"is_muggins":false,"is_lowball":false,"is_back_up_ten":false,"auction":null,"skunk_lines":null,"jokers":null,"scoring_rules":{"is_run_across_reset":false,"is_go_worth_two":false,"is_crib_four_flush":false,"is_seven_card_heels":true,"nineteen_bonus":0}}}"#;
+
+    fn event(kind: u8, value: u8) -> CribbageEvent {
+        CribbageEvent {
+            kind,
+            value,
+            num_discards: 0,
+            discards: [0; CRIBBAGE_MAX_DISCARDS],
+        }
+    }
+
+    // Returns a string from the library and frees it
+    unsafe fn take_string(string: *mut c_char) -> String {
+        assert!(!string.is_null());
+        let taken = CStr::from_ptr(string).to_str().unwrap().to_string();
+        cribbage_string_free(string);
+        taken
+    }
+
+    #[test]
+    fn game_through_the_interface() {
+        unsafe {
+            let game = cribbage_game_create_from_seed(7);
+            let game_setup = CString::new(GAME_SETUP).unwrap();
+            assert_eq!(
+                cribbage_game_process_event_json(game, CRIBBAGE_ANY_SEAT, game_setup.as_ptr()),
+                CRIBBAGE_OK
+            );
+            assert_eq!(cribbage_game_num_players(game), 2);
+            assert_eq!(cribbage_game_index_dealer(game), -1);
+
+            let confirmation = event(CRIBBAGE_EVENT_CONFIRMATION, 0);
+            assert_eq!(
+                cribbage_game_process_event(game, 2, &confirmation),
+                CRIBBAGE_SEAT_NOT_ALLOWED
+            );
+            let last_result = take_string(cribbage_game_last_result_json(game));
+            assert_eq!(last_result, r#"{"Err":"SeatNotAllowed"}"#);
+            while cribbage_game_index_dealer(game) == -1 {
+                assert_eq!(
+                    cribbage_game_process_event(game, 0, &confirmation),
+                    CRIBBAGE_OK
+                );
+            }
+
+            // Only the dealer deals and a seat discards for itself alone
+            let dealer = cribbage_game_index_dealer(game) as u8;
+            assert_eq!(
+                cribbage_game_process_event(game, 1 - dealer, &confirmation),
+                CRIBBAGE_NOT_YOUR_TURN
+            );
+            assert_eq!(
+                cribbage_game_process_event(game, dealer, &confirmation),
+                CRIBBAGE_OK
+            );
+            assert_eq!(
+                cribbage_game_process_event(game, dealer, &confirmation),
+                CRIBBAGE_OK
+            );
+            let mut discard = event(CRIBBAGE_EVENT_DISCARD, 0);
+            discard.num_discards = 2;
+            discard.discards[1] = 1;
+            assert_eq!(
+                cribbage_game_process_event(game, dealer, &discard),
+                CRIBBAGE_OK
+            );
+            assert_eq!(
+                take_string(cribbage_game_last_result_json(game)),
+                r#"{"Ok":"DiscardHeld"}"#
+            );
+            discard.num_discards = 5;
+            assert_eq!(
+                cribbage_game_process_event(game, 1 - dealer, &discard),
+                CRIBBAGE_INVALID_EVENT
+            );
+            discard.num_discards = 2;
+            discard.discards[1] = 0;
+            assert_eq!(
+                cribbage_game_process_event(game, 1 - dealer, &discard),
+                CRIBBAGE_DISCARD_ERRORS
+            );
+
+            let view = take_string(cribbage_game_view_json(game, dealer));
+            let view: serde_json::Value = serde_json::from_str(&view).unwrap();
+            assert_eq!(view["seat"], u64::from(dealer));
+            assert_eq!(view["state"], "Discard");
+            assert_eq!(
+                view["sequence"].as_u64(),
+                Some(cribbage_game_sequence(game))
+            );
+            assert!(cribbage_game_view_json(game, 2).is_null());
+
+            cribbage_game_destroy(game);
+        }
+    }
+
+    #[test]
+    fn bad_input() {
+        unsafe {
+            let confirmation = event(CRIBBAGE_EVENT_CONFIRMATION, 0);
+            assert_eq!(
+                cribbage_game_process_event(ptr::null_mut(), 0, &confirmation),
+                CRIBBAGE_NULL_POINTER
+            );
+            assert_eq!(cribbage_game_sequence(ptr::null()), 0);
+            cribbage_game_destroy(ptr::null_mut());
+            cribbage_string_free(ptr::null_mut());
+
+            let game = cribbage_game_create();
+            assert!(cribbage_game_last_result_json(game).is_null());
+            let json = CString::new(r#"{"Play":"#).unwrap();
+            assert_eq!(
+                cribbage_game_process_event_json(game, 0, json.as_ptr()),
+                CRIBBAGE_INVALID_JSON
+            );
+            assert_eq!(
+                cribbage_game_process_event(game, 0, &event(200, 0)),
+                CRIBBAGE_INVALID_EVENT
+            );
+            assert_eq!(
+                cribbage_game_process_event(game, 0, &confirmation),
+                CRIBBAGE_EXPECTED_EVENT
+            );
+            cribbage_game_destroy(game);
+        }
+    }
+
+    #[test]
+    fn status_codes_are_unique() {
+        for (index, (name, code, _)) in STATUS_CODES.iter().enumerate() {
+            assert!(
+                STATUS_CODES[index + 1..]
+                    .iter()
+                    .all(|(other_name, other_code, _)| other_name != name && other_code != code),
+                "{} is not unique",
+                name
+            );
+        }
+    }
+
+    // Returns the C declaration of an exported function from its Rust signature, such as
+    // "cribbage_game_sequence(game: *const CribbageGame) -> u64"
+    fn c_declaration(signature: &str) -> String {
+        let c_type = |rust_type: &str| match rust_type {
+            "" => "void ",
+            "u8" => "uint8_t ",
+            "u64" => "uint64_t ",
+            "i64" => "int64_t ",
+            "c_int" => "int ",
+            "*mut c_char" => "char *",
+            "*const c_char" => "const char *",
+            "*mut CribbageGame" => "CribbageGame *",
+            "*const CribbageGame" => "const CribbageGame *",
+            "*const CribbageEvent" => "const CribbageEvent *",
+            _ => panic!("No C type for {}", rust_type),
+        };
+        let (name, rest) = signature.split_once('(').unwrap();
+        let (params, return_type) = rest.rsplit_once(')').unwrap();
+        let return_type = return_type.trim().trim_start_matches("->").trim();
+        let params: Vec<String> = params
+            .split(',')
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .map(|param| {
+                let (param_name, param_type) = param.split_once(':').unwrap();
+                format!("{}{}", c_type(param_type.trim()), param_name)
+            })
+            .collect();
+        let params = if params.is_empty() {
+            String::from("void")
+        } else {
+            params.join(", ")
+        };
+        format!("{}{}({});", c_type(return_type), name, params)
+    }
+
+    // Every function the library exports is declared in FUNCTIONS, in order and with the same
+    // signature, by reading the signatures from this file
+    #[test]
+    fn functions_match_the_exports() {
+        let source = include_str!("ffi.rs");
+        let exports: Vec<String> = source
+            .split("#[no_mangle]\npub ")
+            .skip(1)
+            .map(|export| {
+                let signature = &export[export.find("fn ").unwrap() + 3..export.find('{').unwrap()];
+                c_declaration(
+                    &signature
+                        .split_whitespace()
+                        .collect::<Vec<&str>>()
+                        .join(" "),
+                )
+            })
+            .collect();
+        let declarations: Vec<&str> = FUNCTIONS
+            .iter()
+            .map(|(_, declaration)| *declaration)
+            .collect();
+        assert_eq!(exports, declarations);
+    }
+
+    // The header shipped with the crate is the one the crate writes
+    #[test]
+    fn header_is_up_to_date() {
+        assert_eq!(
+            include_str!("../ffi/cribbage.h"),
+            header(),
+            "Run cargo run --bin cribbage-ffi-header > ffi/cribbage.h"
+        );
+    }
+}
+
+// The event succeeded
+pub const CRIBBAGE_OK: c_int = 0;
+// One status code per variant of game_process_return::Error
+pub const CRIBBAGE_IMPLEMENTATION_ERROR: c_int = 1;
+pub const CRIBBAGE_EXPECTED_EVENT: c_int = 2;
+pub const CRIBBAGE_GAME_START_INVALID_CONFIG: c_int = 3;
+pub const CRIBBAGE_INITIAL_CUT_ERROR: c_int = 4;
+pub const CRIBBAGE_DISCARD_ERRORS: c_int = 5;
+pub const CRIBBAGE_NIBS_CALL_ERROR: c_int = 6;
+pub const CRIBBAGE_PLAY_WAIT_FOR_CARD_ERROR: c_int = 7;
+pub const CRIBBAGE_GO_DECISION_ERROR: c_int = 8;
+pub const CRIBBAGE_AUCTION_ERROR: c_int = 9;
+pub const CRIBBAGE_PLAY_SCORE_ERROR: c_int = 10;
+pub const CRIBBAGE_SHOW_SCORE_ERROR: c_int = 11;
+pub const CRIBBAGE_CRIB_SCORE_ERROR: c_int = 12;
+pub const CRIBBAGE_MATCH_IS_OVER: c_int = 13;
+pub const CRIBBAGE_TIME_HAS_NOT_RUN_OUT: c_int = 14;
+pub const CRIBBAGE_NOT_YOUR_TURN: c_int = 15;
+pub const CRIBBAGE_SEAT_NOT_ALLOWED: c_int = 16;
+pub const CRIBBAGE_UNIMPLEMENTED_STATE: c_int = 17;
+pub const CRIBBAGE_UNRECOGNIZED_STATE: c_int = 18;
+pub const CRIBBAGE_MUGGINS_ERROR: c_int = 19;
+// Errors of the interface itself
+pub const CRIBBAGE_NULL_POINTER: c_int = 100;
+pub const CRIBBAGE_INVALID_JSON: c_int = 101;
+pub const CRIBBAGE_INVALID_EVENT: c_int = 102;
+pub const CRIBBAGE_PANIC: c_int = 103;
+
+// Every status code with its name and description, from which the header is written
+pub const STATUS_CODES: [(&str, c_int, &str); 24] = [
+    ("CRIBBAGE_OK", CRIBBAGE_OK, "The event was accepted"),
+    (
+        "CRIBBAGE_IMPLEMENTATION_ERROR",
+        CRIBBAGE_IMPLEMENTATION_ERROR,
+        "Error::ImplementationError",
+    ),
+    (
+        "CRIBBAGE_EXPECTED_EVENT",
+        CRIBBAGE_EXPECTED_EVENT,
+        "Error::ExpectedEvent",
+    ),
+    (
+        "CRIBBAGE_GAME_START_INVALID_CONFIG",
+        CRIBBAGE_GAME_START_INVALID_CONFIG,
+        "Error::GameStartInvalidConfig",
+    ),
+    (
+        "CRIBBAGE_INITIAL_CUT_ERROR",
+        CRIBBAGE_INITIAL_CUT_ERROR,
+        "Error::InitialCutError",
+    ),
+    (
+        "CRIBBAGE_DISCARD_ERRORS",
+        CRIBBAGE_DISCARD_ERRORS,
+        "Error::DiscardErrors",
+    ),
+    (
+        "CRIBBAGE_NIBS_CALL_ERROR",
+        CRIBBAGE_NIBS_CALL_ERROR,
+        "Error::NibsCallError",
+    ),
+    (
+        "CRIBBAGE_PLAY_WAIT_FOR_CARD_ERROR",
+        CRIBBAGE_PLAY_WAIT_FOR_CARD_ERROR,
+        "Error::PlayWaitForCardError",
+    ),
+    (
+        "CRIBBAGE_GO_DECISION_ERROR",
+        CRIBBAGE_GO_DECISION_ERROR,
+        "Error::GoDecisionError",
+    ),
+    (
+        "CRIBBAGE_AUCTION_ERROR",
+        CRIBBAGE_AUCTION_ERROR,
+        "Error::AuctionError",
+    ),
+    (
+        "CRIBBAGE_PLAY_SCORE_ERROR",
+        CRIBBAGE_PLAY_SCORE_ERROR,
+        "Error::PlayScoreError",
+    ),
+    (
+        "CRIBBAGE_SHOW_SCORE_ERROR",
+        CRIBBAGE_SHOW_SCORE_ERROR,
+        "Error::ShowScoreError",
+    ),
+    (
+        "CRIBBAGE_CRIB_SCORE_ERROR",
+        CRIBBAGE_CRIB_SCORE_ERROR,
+        "Error::CribScoreError",
+    ),
+    (
+        "CRIBBAGE_MATCH_IS_OVER",
+        CRIBBAGE_MATCH_IS_OVER,
+        "Error::MatchIsOver",
+    ),
+    (
+        "CRIBBAGE_TIME_HAS_NOT_RUN_OUT",
+        CRIBBAGE_TIME_HAS_NOT_RUN_OUT,
+        "Error::TimeHasNotRunOut",
+    ),
+    (
+        "CRIBBAGE_NOT_YOUR_TURN",
+        CRIBBAGE_NOT_YOUR_TURN,
+        "Error::NotYourTurn",
+    ),
+    (
+        "CRIBBAGE_SEAT_NOT_ALLOWED",
+        CRIBBAGE_SEAT_NOT_ALLOWED,
+        "Error::SeatNotAllowed",
+    ),
+    (
+        "CRIBBAGE_UNIMPLEMENTED_STATE",
+        CRIBBAGE_UNIMPLEMENTED_STATE,
+        "Error::UnimplementedState",
+    ),
+    (
+        "CRIBBAGE_UNRECOGNIZED_STATE",
+        CRIBBAGE_UNRECOGNIZED_STATE,
+        "Error::UnrecognizedState",
+    ),
+    (
+        "CRIBBAGE_MUGGINS_ERROR",
+        CRIBBAGE_MUGGINS_ERROR,
+        "Error::MugginsError",
+    ),
+    (
+        "CRIBBAGE_NULL_POINTER",
+        CRIBBAGE_NULL_POINTER,
+        "A pointer passed was NULL",
+    ),
+    (
+        "CRIBBAGE_INVALID_JSON",
+        CRIBBAGE_INVALID_JSON,
+        "The JSON passed is not UTF-8 or not a GameEvent",
+    ),
+    (
+        "CRIBBAGE_INVALID_EVENT",
+        CRIBBAGE_INVALID_EVENT,
+        "The CribbageEvent passed has an unknown kind or too many discards",
+    ),
+    (
+        "CRIBBAGE_PANIC",
+        CRIBBAGE_PANIC,
+        "The library panicked; the game should be destroyed",
+    ),
+];
+
+// The kinds of CribbageEvent and the GameEvent each stands for
+pub const CRIBBAGE_EVENT_CONFIRMATION: u8 = 0;
+pub const CRIBBAGE_EVENT_DENIAL: u8 = 1;
+pub const CRIBBAGE_EVENT_TIMEOUT: u8 = 2;
+pub const CRIBBAGE_EVENT_PLAY_CARD: u8 = 3;
+pub const CRIBBAGE_EVENT_GO: u8 = 4;
+pub const CRIBBAGE_EVENT_BID: u8 = 5;
+pub const CRIBBAGE_EVENT_PASS: u8 = 6;
+pub const CRIBBAGE_EVENT_PEG_ONE: u8 = 7;
+pub const CRIBBAGE_EVENT_BACK_UP_TEN: u8 = 8;
+pub const CRIBBAGE_EVENT_DISCARD: u8 = 9;
+pub const CRIBBAGE_EVENT_NO_MUGGINS: u8 = 10;
+
+pub const EVENT_KINDS: [(&str, u8, &str); 11] = [
+    (
+        "CRIBBAGE_EVENT_CONFIRMATION",
+        CRIBBAGE_EVENT_CONFIRMATION,
+        "GameEvent::Confirmation",
+    ),
+    (
+        "CRIBBAGE_EVENT_DENIAL",
+        CRIBBAGE_EVENT_DENIAL,
+        "GameEvent::Denial",
+    ),
+    (
+        "CRIBBAGE_EVENT_TIMEOUT",
+        CRIBBAGE_EVENT_TIMEOUT,
+        "GameEvent::Timeout",
+    ),
+    (
+        "CRIBBAGE_EVENT_PLAY_CARD",
+        CRIBBAGE_EVENT_PLAY_CARD,
+        "GameEvent::Play(PlayTurn::CardSelected(value))",
+    ),
+    (
+        "CRIBBAGE_EVENT_GO",
+        CRIBBAGE_EVENT_GO,
+        "GameEvent::Play(PlayTurn::Go)",
+    ),
+    (
+        "CRIBBAGE_EVENT_BID",
+        CRIBBAGE_EVENT_BID,
+        "GameEvent::Bid(value)",
+    ),
+    (
+        "CRIBBAGE_EVENT_PASS",
+        CRIBBAGE_EVENT_PASS,
+        "GameEvent::Pass",
+    ),
+    (
+        "CRIBBAGE_EVENT_PEG_ONE",
+        CRIBBAGE_EVENT_PEG_ONE,
+        "GameEvent::GoDecision(GoDecision::PegOne)",
+    ),
+    (
+        "CRIBBAGE_EVENT_BACK_UP_TEN",
+        CRIBBAGE_EVENT_BACK_UP_TEN,
+        "GameEvent::GoDecision(GoDecision::BackUpTen(value))",
+    ),
+    (
+        "CRIBBAGE_EVENT_DISCARD",
+        CRIBBAGE_EVENT_DISCARD,
+        "GameEvent::DiscardSelection with the discards of the seat sending it",
+    ),
+    (
+        "CRIBBAGE_EVENT_NO_MUGGINS",
+        CRIBBAGE_EVENT_NO_MUGGINS,
+        "GameEvent::Muggins(None)",
+    ),
+];
+
+// The most discards a CribbageEvent holds; larger discards are sent as JSON
+pub const CRIBBAGE_MAX_DISCARDS: usize = 4;
+
+// The seat passed to process an event without checking which seat sent it, as with
+// Game::process_event
+pub const CRIBBAGE_ANY_SEAT: u8 = 255;
+
+// The events made of a few numbers; value is the card index, bid, or player index the kind needs
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct CribbageEvent {
+    pub kind: u8,
+    pub value: u8,
+    pub num_discards: u8,
+    pub discards: [u8; CRIBBAGE_MAX_DISCARDS],
+}
+
+// The game behind the opaque pointer given to C along with the result of the last event as JSON
+pub struct CribbageGame {
+    game: Game,
+    last_result: Option<CString>,
+}
+
+// Returns the status code of a result; an exhaustive match so that a new variant of Error needs a
+// status code of its own
+fn status_code(result: &Result<game_process_return::Success, game_process_return::Error>) -> c_int {
+    use game_process_return::Error;
+
+    match result {
+        Ok(_) => CRIBBAGE_OK,
+        Err(Error::ImplementationError(_)) => CRIBBAGE_IMPLEMENTATION_ERROR,
+        Err(Error::ExpectedEvent(_)) => CRIBBAGE_EXPECTED_EVENT,
+        Err(Error::GameStartInvalidConfig(_)) => CRIBBAGE_GAME_START_INVALID_CONFIG,
+        Err(Error::InitialCutError) => CRIBBAGE_INITIAL_CUT_ERROR,
+        Err(Error::DiscardErrors(_)) => CRIBBAGE_DISCARD_ERRORS,
+        Err(Error::NibsCallError(_)) => CRIBBAGE_NIBS_CALL_ERROR,
+        Err(Error::PlayWaitForCardError(_)) => CRIBBAGE_PLAY_WAIT_FOR_CARD_ERROR,
+        Err(Error::GoDecisionError(_)) => CRIBBAGE_GO_DECISION_ERROR,
+        Err(Error::AuctionError(_)) => CRIBBAGE_AUCTION_ERROR,
+        Err(Error::PlayScoreError(_)) => CRIBBAGE_PLAY_SCORE_ERROR,
+        Err(Error::ShowScoreError(_)) => CRIBBAGE_SHOW_SCORE_ERROR,
+        Err(Error::CribScoreError(_)) => CRIBBAGE_CRIB_SCORE_ERROR,
+        Err(Error::MugginsError(_)) => CRIBBAGE_MUGGINS_ERROR,
+        Err(Error::MatchIsOver) => CRIBBAGE_MATCH_IS_OVER,
+        Err(Error::TimeHasNotRunOut) => CRIBBAGE_TIME_HAS_NOT_RUN_OUT,
+        Err(Error::NotYourTurn) => CRIBBAGE_NOT_YOUR_TURN,
+        Err(Error::SeatNotAllowed) => CRIBBAGE_SEAT_NOT_ALLOWED,
+        Err(Error::UnimplementedState) => CRIBBAGE_UNIMPLEMENTED_STATE,
+        Err(Error::UnrecognizedState) => CRIBBAGE_UNRECOGNIZED_STATE,
+    }
+}
+
+// Returns the GameEvent a CribbageEvent stands for when sent from the given seat
+fn game_event(event: &CribbageEvent, seat: u8, num_players: usize) -> Option<GameEvent> {
+    let game_event = match event.kind {
+        CRIBBAGE_EVENT_CONFIRMATION => GameEvent::Confirmation,
+        CRIBBAGE_EVENT_DENIAL => GameEvent::Denial,
+        CRIBBAGE_EVENT_TIMEOUT => GameEvent::Timeout,
+        CRIBBAGE_EVENT_PLAY_CARD => GameEvent::Play(PlayTurn::CardSelected(event.value)),
+        CRIBBAGE_EVENT_GO => GameEvent::Play(PlayTurn::Go),
+        CRIBBAGE_EVENT_BID => GameEvent::Bid(event.value),
+        CRIBBAGE_EVENT_PASS => GameEvent::Pass,
+        CRIBBAGE_EVENT_NO_MUGGINS => GameEvent::Muggins(None),
+        CRIBBAGE_EVENT_PEG_ONE => GameEvent::GoDecision(GoDecision::PegOne),
+        CRIBBAGE_EVENT_BACK_UP_TEN => GameEvent::GoDecision(GoDecision::BackUpTen(event.value)),
+        CRIBBAGE_EVENT_DISCARD => {
+            let num_discards = event.num_discards as usize;
+            if num_discards > CRIBBAGE_MAX_DISCARDS || seat as usize >= num_players {
+                return None;
+            }
+            let mut discard_indices_group = vec![Vec::new(); num_players];
+            discard_indices_group[seat as usize] = event.discards[..num_discards].to_vec();
+            GameEvent::DiscardSelection(discard_indices_group)
+        }
+        _ => return None,
+    };
+    Some(game_event)
+}
+
+// Processes an event from a seat, or from any seat, and keeps its result
+fn process_event(game: &mut CribbageGame, seat: u8, event: GameEvent) -> c_int {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if seat == CRIBBAGE_ANY_SEAT {
+            game.game.process_event(event)
+        } else {
+            game.game.process_event_from(seat, event)
+        }
+    }));
+    match result {
+        Ok(result) => {
+            game.last_result = serde_json::to_string(&result)
+                .ok()
+                .and_then(|json| CString::new(json).ok());
+            status_code(&result)
+        }
+        Err(_) => {
+            game.last_result = None;
+            CRIBBAGE_PANIC
+        }
+    }
+}
+
+fn into_c_string(string: String) -> *mut c_char {
+    match CString::new(string) {
+        Ok(string) => string.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+// Makes a game to be freed with cribbage_game_destroy
+#[no_mangle]
+pub extern "C" fn cribbage_game_create() -> *mut CribbageGame {
+    Box::into_raw(Box::new(CribbageGame {
+        game: Game::new(),
+        last_result: None,
+    }))
+}
+
+// Makes a game whose every shuffle is decided by the seed
+#[no_mangle]
+pub extern "C" fn cribbage_game_create_from_seed(seed: u64) -> *mut CribbageGame {
+    Box::into_raw(Box::new(CribbageGame {
+        game: Game::from_seed(seed),
+        last_result: None,
+    }))
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_destroy(game: *mut CribbageGame) {
+    if !game.is_null() {
+        drop(Box::from_raw(game));
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_process_event(
+    game: *mut CribbageGame,
+    seat: u8,
+    event: *const CribbageEvent,
+) -> c_int {
+    let (game, event) = match (game.as_mut(), event.as_ref()) {
+        (Some(game), Some(event)) => (game, event),
+        _ => return CRIBBAGE_NULL_POINTER,
+    };
+    match game_event(event, seat, game.game.players().len()) {
+        Some(event) => process_event(game, seat, event),
+        None => CRIBBAGE_INVALID_EVENT,
+    }
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_process_event_json(
+    game: *mut CribbageGame,
+    seat: u8,
+    event_json: *const c_char,
+) -> c_int {
+    let game = match game.as_mut() {
+        Some(game) if !event_json.is_null() => game,
+        _ => return CRIBBAGE_NULL_POINTER,
+    };
+    let event = CStr::from_ptr(event_json)
+        .to_str()
+        .ok()
+        .and_then(|event_json| serde_json::from_str::<GameEvent>(event_json).ok());
+    match event {
+        Some(event) => process_event(game, seat, event),
+        None => CRIBBAGE_INVALID_JSON,
+    }
+}
+
+// Returns the Success or Error of the last event processed as JSON, or NULL before any event
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_last_result_json(game: *const CribbageGame) -> *mut c_char {
+    match game.as_ref().and_then(|game| game.last_result.as_ref()) {
+        Some(last_result) => last_result.clone().into_raw(),
+        None => ptr::null_mut(),
+    }
+}
+
+// Returns the SeatView of a seat as JSON, or NULL when the seat is not at the table
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_view_json(
+    game: *const CribbageGame,
+    seat: u8,
+) -> *mut c_char {
+    match game.as_ref() {
+        Some(game) if (seat as usize) < game.game.players().len() => {
+            match serde_json::to_string(&game.game.view(seat)) {
+                Ok(json) => into_c_string(json),
+                Err(_) => ptr::null_mut(),
+            }
+        }
+        _ => ptr::null_mut(),
+    }
+}
+
+// Returns the sequence number of the last accepted event, or zero
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_sequence(game: *const CribbageGame) -> u64 {
+    game.as_ref().map_or(0, |game| game.game.sequence())
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_num_players(game: *const CribbageGame) -> c_int {
+    game.as_ref()
+        .map_or(0, |game| game.game.players().len() as c_int)
+}
+
+// Returns the player index of the dealer, or -1 before the first dealer is cut for
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_index_dealer(game: *const CribbageGame) -> c_int {
+    game.as_ref()
+        .and_then(|game| game.game.index_dealer())
+        .map_or(-1, c_int::from)
+}
+
+// Returns the player index of the player playing or scoring, or -1 when there is none
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_index_active(game: *const CribbageGame) -> c_int {
+    game.as_ref()
+        .and_then(|game| game.game.index_active())
+        .map_or(-1, c_int::from)
+}
+
+// Returns the milliseconds left for the decision being waited on, or -1 when it has no time limit
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_game_time_remaining(game: *const CribbageGame) -> i64 {
+    game.as_ref()
+        .and_then(|game| game.game.time_remaining())
+        .map_or(-1, |millis| millis as i64)
+}
+
+// Frees a string returned by the library
+#[no_mangle]
+pub unsafe extern "C" fn cribbage_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+// The declarations of the functions above for the header, checked against their signatures by
+// test::functions_match_the_exports
+const FUNCTIONS: [(&str, &str); 13] = [
+    (
+        "Makes a game to be freed with cribbage_game_destroy",
+        "CribbageGame *cribbage_game_create(void);",
+    ),
+    (
+        "Makes a game whose every shuffle is decided by the seed",
+        "CribbageGame *cribbage_game_create_from_seed(uint64_t seed);",
+    ),
+    ("", "void cribbage_game_destroy(CribbageGame *game);"),
+    (
+        "Processes an event from a seat, or from CRIBBAGE_ANY_SEAT; returns a status code",
+        "int cribbage_game_process_event(CribbageGame *game, uint8_t seat, const CribbageEvent *event);",
+    ),
+    (
+        "Processes the JSON of a GameEvent from a seat, or from CRIBBAGE_ANY_SEAT; returns a status code",
+        "int cribbage_game_process_event_json(CribbageGame *game, uint8_t seat, const char *event_json);",
+    ),
+    (
+        "Returns the Success or Error of the last event processed as JSON, or NULL before any event",
+        "char *cribbage_game_last_result_json(const CribbageGame *game);",
+    ),
+    (
+        "Returns the SeatView of a seat as JSON, or NULL when the seat is not at the table",
+        "char *cribbage_game_view_json(const CribbageGame *game, uint8_t seat);",
+    ),
+    (
+        "Returns the sequence number of the last accepted event, or zero",
+        "uint64_t cribbage_game_sequence(const CribbageGame *game);",
+    ),
+    ("", "int cribbage_game_num_players(const CribbageGame *game);"),
+    (
+        "Returns the player index of the dealer, or -1 before the first dealer is cut for",
+        "int cribbage_game_index_dealer(const CribbageGame *game);",
+    ),
+    (
+        "Returns the player index of the player playing or scoring, or -1 when there is none",
+        "int cribbage_game_index_active(const CribbageGame *game);",
+    ),
+    (
+        "Returns the milliseconds left for the decision being waited on, or -1 without a time limit",
+        "int64_t cribbage_game_time_remaining(const CribbageGame *game);",
+    ),
+    (
+        "Frees a string returned by the library",
+        "void cribbage_string_free(char *string);",
+    ),
+];
+
+// Returns the C header for this module as written to ffi/cribbage.h
+pub fn header() -> String {
+    let mut header = String::from(
+        "/* The C interface of the cribbage library; written by cribbage-ffi-header, do not edit */\n\
+         \n\
+         #ifndef CRIBBAGE_H\n\
+         #define CRIBBAGE_H\n\
+         \n\
+         #include <stdint.h>\n\
+         \n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n\
+         /* Status codes returned when processing an event */\n",
+    );
+    for (name, code, description) in STATUS_CODES.iter() {
+        header += &format!("#define {} {} /* {} */\n", name, code, description);
+    }
+    header += "\n/* Kinds of CribbageEvent */\n";
+    for (name, kind, description) in EVENT_KINDS.iter() {
+        header += &format!("#define {} {} /* {} */\n", name, kind, description);
+    }
+    header += &format!(
+        "\n#define CRIBBAGE_MAX_DISCARDS {}\n#define CRIBBAGE_ANY_SEAT {}\n\n",
+        CRIBBAGE_MAX_DISCARDS, CRIBBAGE_ANY_SEAT
+    );
+    header += "typedef struct CribbageGame CribbageGame;\n\
+               \n\
+               typedef struct CribbageEvent {\n\
+               \x20   uint8_t kind;\n\
+               \x20   uint8_t value;\n\
+               \x20   uint8_t num_discards;\n\
+               \x20   uint8_t discards[CRIBBAGE_MAX_DISCARDS];\n\
+               } CribbageEvent;\n";
+    for (description, declaration) in FUNCTIONS.iter() {
+        header += "\n";
+        if !description.is_empty() {
+            header += &format!("/* {} */\n", description);
+        }
+        header += declaration;
+        header += "\n";
+    }
+    header += "\n#ifdef __cplusplus\n}\n#endif\n\n#endif /* CRIBBAGE_H */\n";
+    header
+}
@@ -1,4 +1,6 @@
-#[derive(Debug, PartialEq)]
+use serde::Serialize;
+
+#[derive(Debug, PartialEq, Serialize)]
 pub enum ConfigError {
     UnderpeggingEnabledWhenManualScoringIsDisabled,
     MugginsEnabledWhenManualScoringIsDisabled,
@@ -10,21 +12,44 @@ pub enum ConfigError {
     VDOIsNotLoserDrawsForDealerWhenVariantHasPairs,
     LowballEnabledWhenVariantIsNotTwoPlayersOrPairs,
     LowballIsEnabledWhenUnderpeggingIsEnabled,
+    DoubleSkunkLineIsAboveSkunkLine,
+    SkunkLineIsAboveTargetScore,
+    BackUpTenIsEnabledWhenLowballIsEnabled,
+    AuctionIsEnabledWhenLowballIsEnabled,
+    AuctionIsEnabledWhenSeatsAreDealtDifferently,
+    AuctionBidsAndIncrementsMustBeAtLeastOne,
+    AuctionMaximumBidIsBelowTheMinimumBid,
+    JokersMustNumberOneOrTwo,
+    JokerPlayValueIsOutOfRange,
+    AutoScoreIsOnlyForTheManualScoringTimeLimit,
+    ManualScoringTimeLimitWhenManualScoringIsDisabled,
+    // Errors with the description of a custom variant
+    CustomVariantMustHaveTwoToSixPlayers,
+    CustomVariantPartnershipsAreNotMutual,
+    CustomVariantHandsAreNotTheSameSize,
+    CustomVariantCribIsEmpty,
+    CustomVariantDealsMoreCardsThanTheDeck,
+    CustomVariantTargetScoreIsOutOfRange,
+    CustomVariantCaptainNeedsOnePlayerWithoutAPartner,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum Event {
     GameSetup,
     Confirmation,
+    Denial,
     DiscardSelection,
     Nibs,
     Play,
     ManScoreSelection,
     Muggins,
+    GoDecision,
+    Bid,
+    Pass,
 }
 
 // u8 attatched is the player index in which the error occurs
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum DiscardError {
     // Errors with number of indices given
     TwoCardsAreDiscardedWithTwoPlayers(u8),
@@ -41,12 +66,17 @@ pub enum DiscardError {
     IndicesAreBetween0And6InclusiveWithTwoSevenCard(u8),
     IndicesAreBetween0And4InclusiveWithTwoFiveCard(u8),
     IndicesAreBetween0And4InclusiveWithThreeOrMorePlayers(u8),
+
+    // Errors with custom variants where the number of discards is set for each seat
+    WrongNumberOfCardsAreDiscardedWithThisCustomVariant(u8),
+    CardIndicesMayNotBeRepeated(u8),
+    IndicesAreWithinTheHandWithThisCustomVariant(u8),
 }
 
 // These are errors that only programmers implementing a front end to the library should see. They
 // are things that will only appear from their errors, not a regular part of player input being
 // handled.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum ImplError {
     // If you're using the public interface this should never occur, this error is returned when
     // there if the GameImpl's settings variable is None.
@@ -59,32 +89,69 @@ pub enum ImplError {
     NibsCheckWhenNoStarterCard,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum NibsError {
     NoNibsCallWhenUnderscoringIsDisabled,
     NibsCallWhenNoCutJack,
     InvalidScoreEventToNibsCheck,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum PlayTurnError {
     CardHasAlreadyBeenPlayed,
     IndexIsBetween0And2InclusiveWithTwoFiveCard,
     IndexIsBetween0And4InclusiveWithTwoSevenCard,
     // This is true for all other variants
     IndexIsBetween0And3InclusiveWithThisRuleVariant,
+    IndexIsWithinTheHandWithThisCustomVariant,
     MustPlayCardIfAble,
     PlayGroupTotalMayNotExceed31,
 }
 
-#[derive(Debug, PartialEq)]
+// Errors for a bid in auction cribbage
+#[derive(Debug, PartialEq, Serialize)]
+pub enum AuctionError {
+    BidIsBelowTheMinimumBid,
+    BidDoesNotRaiseTheHighBidByTheMinimumIncrement,
+    BidIsMoreThanTheMaximumBid,
+}
+
+// Errors for the choice made after a go with Back Up 10
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum GoDecisionError {
+    PlayerIndexIsNotAtTheTable,
+    OnlyAnOpponentMayBeMovedBack,
+    // The opponent has already been moved back as far as the limit of the ScoringRules allows
+    OpponentIsAtTheBackUpTenLimit,
+}
+
+// Each error carries the difference between the claimed and the optimal scoring
+#[derive(Debug, PartialEq, Serialize)]
 pub enum PlayScoreError {
-    OverpeggingIsNotAllowed(crate::score::ScoreEvent),
+    OverpeggingIsNotAllowed(crate::score::manual::ScoreDiff),
     // Following will only be returned when underpegging is disabled in the game's settings
-    UnderpeggingIsNotAllowed,
+    UnderpeggingIsNotAllowed(crate::score::manual::ScoreDiff),
+}
+
+// Errors for manual scoring of a hand or the crib during the show
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ShowScoreError {
+    OverpeggingIsNotAllowed(crate::score::manual::ScoreDiff),
+    // Following will only be returned when underpegging is disabled in the game's settings
+    UnderpeggingIsNotAllowed(crate::score::manual::ScoreDiff),
+}
+
+// Errors for calling muggins on the points missed by a count
+#[derive(Debug, PartialEq, Serialize)]
+pub enum MugginsError {
+    // Only an opponent of the player whose count is in question may call muggins, and every
+    // ScoreEvent of a call must be claimed for the same player
+    OnlyAnOpponentMayCallMuggins,
+    // The diff is of the call against the points which were missed and not yet called
+    CalledPointsWereNotMissed(crate::score::manual::ScoreDiff),
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum Error {
     ImplementationError(ImplError),
     ExpectedEvent(Vec<Event>),
@@ -93,45 +160,117 @@ pub enum Error {
     DiscardErrors(Vec<DiscardError>),
     NibsCallError(NibsError),
     PlayWaitForCardError(PlayTurnError),
+    GoDecisionError(GoDecisionError),
+    AuctionError(AuctionError),
     PlayScoreError(PlayScoreError),
+    ShowScoreError(ShowScoreError),
+    CribScoreError(ShowScoreError),
+    MugginsError(MugginsError),
+    // Returned for any event once a match has ended
+    MatchIsOver,
+    // Returned for a Timeout event when the decision being waited on still has time or has no
+    // time limit
+    TimeHasNotRunOut,
+    // Returned by Game::process_event_from when the event is for another seat to send right now
+    NotYourTurn,
+    // Returned by Game::process_event_from when the seat is not at the table or may never send the
+    // event in this state, such as a player calling muggins on their own count
+    SeatNotAllowed,
     UnimplementedState,
     UnrecognizedState,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum InitialCutReturn {
     CutTie,
     DealerChosen,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum StarterCutReturn {
     ManualScoring,
     AutoNibs,
     AutoNoNibs,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum NibsCheckReturn {
     Nibs,
     NoNibs,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum PlayWaitForCardReturn {
     AutomaticScoring(Vec<crate::score::ScoreEvent>),
     ManualScoring,
+    // The play ended with a go under Back Up 10 so the last player to play must send a GoDecision
+    GoDecision,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum AuctionReturn {
+    // The bid or pass was made and the next player still in the auction is active
+    NextBidder,
+    // Every other player has passed so the given player takes the crib for the given bid, which
+    // is nothing when everyone passed and the dealer keeps their crib
+    CribTaken(u8, u8),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum GoDecisionReturn {
+    // The ScoreEvent for the go or last card
+    PeggedOne(crate::score::ScoreEvent),
+    // The opponent and the number of holes they were moved back, which is fewer than ten when
+    // they are closer than that to the start
+    MovedBack(u8, u8),
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum ResetPlayReturn {
+    // There are still cards to be played so a new PlayGroup has been started
+    NewPlayGroup,
+    // Every card has been played (or the variant only plays a single PlayGroup) so the game moves
+    // on to the show
+    PlayComplete,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum MugginsReturn {
+    // The points called by an opponent, who pegs them
+    Called(Vec<crate::score::ScoreEvent>),
+    // Every opponent is done calling muggins so the game moves on
+    Complete,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub enum WinReturn {
+    // The pegs are reset and the dealer of the next game is to be cut for
+    NextGame,
+    // The match is over and the final standings are available from the game
+    MatchComplete,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Serialize)]
 pub enum Success {
     GameStart,
     InitialCut(InitialCutReturn),
     Deal,
     Sort,
+    Auction(AuctionReturn),
     Discard,
+    // One seat's discards are held by Game::process_event_from until every seat has discarded
+    DiscardHeld,
     StarterCut(StarterCutReturn),
     NibsCheck(NibsCheckReturn),
     PlayWaitForCard(PlayWaitForCardReturn),
     PlayScore(Vec<crate::score::ScoreEvent>),
+    GoDecision(GoDecisionReturn),
+    ResetPlay(ResetPlayReturn),
+    ShowScore(Vec<crate::score::ScoreEvent>),
+    CribSort,
+    CribScore(Vec<crate::score::ScoreEvent>),
+    Muggins(MugginsReturn),
+    Win(WinReturn),
+    // The player with the given index ran out of time and forfeited the game
+    Forfeit(u8),
 }
@@ -0,0 +1,225 @@
+use crate::game_process_return;
+use crate::view;
+use crate::{Game, GameEvent};
+use futures::stream::{Stream, StreamExt};
+use futures::task::{Context, Poll};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+// An async interface to Game, built with the async feature, such that one task can own a game and
+// be driven by a stream of events from every seat without a mutex around the game
+//
+// Each event is processed with Game::process_event_from in the order it arrives and gives an
+// outcome for the seat which sent it; an accepted event also gives a fresh SeatView for every seat
+
+#[cfg(test)]
+mod test {
+    use super::{GameStream, GameUpdate};
+    use crate::game_process_return::Error;
+    use crate::settings::{GameSettings, RuleVariant, ScoringRules, VictorDealerOption};
+    use crate::view::SeatView;
+    use crate::{Game, GameEvent, GameState};
+    use futures::channel::mpsc;
+    use futures::executor::{self, LocalPool};
+    use futures::stream::{self, StreamExt};
+    use futures::task::LocalSpawnExt;
+
+    fn settings() -> GameSettings {
+        GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }
+    }
+
+    #[test]
+    fn events_are_processed_in_order() {
+        let events = stream::iter(vec![
+            (0, GameEvent::GameSetup(settings())),
+            (2, GameEvent::Confirmation),
+            (1, GameEvent::Confirmation),
+        ]);
+        let mut game_stream = GameStream::new(Game::from_seed(3), events);
+        let updates = executor::block_on((&mut game_stream).collect::<Vec<GameUpdate>>());
+        assert_eq!(updates.len(), 7);
+
+        // The set up is accepted and each seat is sent its view
+        assert!(matches!(
+            updates[0],
+            GameUpdate::Outcome {
+                seat: 0,
+                sequence: 1,
+                result: Ok(_)
+            }
+        ));
+        for (seat, update) in updates[1..3].iter().enumerate() {
+            assert!(matches!(
+                update,
+                GameUpdate::Notification { seat: to, view }
+                    if *to as usize == seat && view.seat as usize == seat && view.sequence == 1
+            ));
+        }
+
+        // A rejected event is only answered
+        assert_eq!(
+            updates[3],
+            GameUpdate::Outcome {
+                seat: 2,
+                sequence: 1,
+                result: Err(Error::SeatNotAllowed)
+            }
+        );
+        assert!(matches!(
+            updates[4],
+            GameUpdate::Outcome {
+                seat: 1,
+                sequence: 2,
+                result: Ok(_)
+            }
+        ));
+        assert!(matches!(
+            &updates[6],
+            GameUpdate::Notification { seat: 1, view } if view.sequence == 2
+        ));
+
+        let game = game_stream.into_game();
+        assert_eq!(game.sequence(), 2);
+        assert_ne!(game.state(), GameState::GameStart);
+    }
+
+    // One task owns the game and passes each seat its views over a channel of its own
+    #[test]
+    fn one_task_owns_the_game() {
+        let mut pool = LocalPool::new();
+        let (event_sender, event_receiver) = mpsc::unbounded();
+        let (view_senders, view_receivers): (Vec<_>, Vec<_>) =
+            (0..2).map(|_| mpsc::unbounded::<SeatView>()).unzip();
+
+        pool.spawner()
+            .spawn_local(async move {
+                let mut updates = GameStream::new(Game::from_seed(5), event_receiver);
+                while let Some(update) = updates.next().await {
+                    if let GameUpdate::Notification { seat, view } = update {
+                        view_senders[seat as usize].unbounded_send(*view).unwrap();
+                    }
+                }
+            })
+            .unwrap();
+
+        event_sender
+            .unbounded_send((0, GameEvent::GameSetup(settings())))
+            .unwrap();
+        let mut seat = 0;
+        for _ in 0..4 {
+            event_sender
+                .unbounded_send((seat, GameEvent::Confirmation))
+                .unwrap();
+            seat = 1 - seat;
+        }
+        drop(event_sender);
+        pool.run();
+
+        for (seat, view_receiver) in view_receivers.into_iter().enumerate() {
+            let views = executor::block_on(view_receiver.collect::<Vec<SeatView>>());
+            assert!(!views.is_empty());
+            assert!(views.iter().all(|view| view.seat as usize == seat));
+            let sequences: Vec<u64> = views.iter().map(|view| view.sequence).collect();
+            assert_eq!(sequences, (1..=views.len() as u64).collect::<Vec<u64>>());
+        }
+    }
+}
+
+// What a GameStream gives for each event taken from the stream of events
+#[derive(Debug, PartialEq, Serialize)]
+pub enum GameUpdate {
+    // The result of an event for the seat which sent it along with the sequence number of the last
+    // accepted event, which is the event itself when it was accepted
+    Outcome {
+        seat: u8,
+        sequence: u64,
+        result: Result<game_process_return::Success, game_process_return::Error>,
+    },
+    // The game as a seat sees it after an accepted event; given for every seat at the table
+    Notification {
+        seat: u8,
+        view: Box<view::SeatView>,
+    },
+}
+
+// A stream of GameUpdates made by processing a stream of events sent by seats; ends when the stream
+// of events ends
+pub struct GameStream<S> {
+    game: Game,
+    events: S,
+    pending: VecDeque<GameUpdate>,
+}
+
+impl<S> GameStream<S>
+where
+    S: Stream<Item = (u8, GameEvent)> + Unpin,
+{
+    pub fn new(game: Game, events: S) -> GameStream<S> {
+        GameStream {
+            game,
+            events,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    // Returns the game, such as once the stream of events has ended
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+
+    fn process_event(&mut self, seat: u8, event: GameEvent) {
+        let result = self.game.process_event_from(seat, event);
+        let is_accepted = result.is_ok();
+        self.pending.push_back(GameUpdate::Outcome {
+            seat,
+            sequence: self.game.sequence(),
+            result,
+        });
+        if is_accepted {
+            for seat in 0..self.game.players().len() as u8 {
+                self.pending.push_back(GameUpdate::Notification {
+                    seat,
+                    view: Box::new(self.game.view(seat)),
+                });
+            }
+        }
+    }
+}
+
+impl<S> Stream for GameStream<S>
+where
+    S: Stream<Item = (u8, GameEvent)> + Unpin,
+{
+    type Item = GameUpdate;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<GameUpdate>> {
+        loop {
+            if let Some(update) = self.pending.pop_front() {
+                return Poll::Ready(Some(update));
+            }
+            match self.events.poll_next_unpin(cx) {
+                Poll::Ready(Some((seat, event))) => self.process_event(seat, event),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
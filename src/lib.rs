@@ -1,23 +1,48 @@
+// Each module keeps its tests at the top of the file ahead of the code being tested
+#![allow(clippy::items_after_test_module)]
+
+pub mod analysis;
+pub mod arena;
+pub mod board;
+pub mod clock;
+pub mod cross;
 pub mod deck;
+pub mod ffi;
 pub mod game_process_return;
+#[cfg(feature = "async")]
+pub mod game_stream;
+pub mod observer;
 pub mod player;
+pub mod resync;
 pub mod score;
+pub mod server;
 pub mod settings;
+pub mod solitaire;
+pub mod standings;
+pub mod strategy;
+pub mod view;
 
 mod state_logic;
 
 mod util;
 
+use serde::{Deserialize, Serialize};
+use state_logic::auction;
+use state_logic::crib;
 use state_logic::cut_initial;
 use state_logic::cut_starter_and_nibs_check;
 use state_logic::deal;
 use state_logic::discard;
 use state_logic::game_start;
 use state_logic::play;
+use state_logic::seats;
+use state_logic::show;
 use state_logic::sort;
+use state_logic::timeout;
+use state_logic::win;
 
 // Enum sent to the process_turn function to advance the play of the game model
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum GameEvent {
     // Event containing the parameters to start the game
     GameSetup(settings::GameSettings),
@@ -34,11 +59,20 @@ pub enum GameEvent {
     // Event for whether a player calls muggins, a None value means that all players are done
     // making muggins selections and that the game is ready to move on
     Muggins(Option<Vec<score::ScoreEvent>>),
+    // Events for raising the high bid to the given number of points or dropping out of the auction
+    // in auction cribbage
+    Bid(u8),
+    Pass,
+    // Event for the choice of the last player to play after a go with Back Up 10
+    GoDecision(GoDecision),
     // Simple event for continuing to the next game state; used when player input is needed such as
     // when a player must cut the deck or simply when the timing of a state change is decided by
     // the program implementing this library
     Confirmation,
     Denial,
+    // Event claiming that the player being waited on has run out of time under the game's
+    // settings::TimeLimits; may be sent by anyone and is rejected while there is time left
+    Timeout,
 }
 
 // The public game object with appropriate abstraction that processes GameEvents
@@ -46,6 +80,12 @@ pub struct Game {
     game: GameImpl,
 }
 
+impl Default for Game {
+    fn default() -> Game {
+        Game::new()
+    }
+}
+
 impl Game {
     pub fn new() -> Game {
         Game {
@@ -53,14 +93,186 @@ impl Game {
         }
     }
 
+    // Returns a game whose every shuffle is determined by the given seed such that a game played
+    // with the same events is played identically
+    pub fn from_seed(seed: u64) -> Game {
+        let mut game = GameImpl::new();
+        game.deck = deck::Deck::from_seed(seed);
+        Game { game }
+    }
+
+    // The following functions give read only access to the model of the game such that a front
+    // end can display it and decide which events to send
+
+    pub fn state(&self) -> GameState {
+        self.game.state
+    }
+
+    pub fn settings(&self) -> Option<settings::GameSettings> {
+        self.game.settings
+    }
+
+    pub fn players(&self) -> &[player::Player] {
+        &self.game.players
+    }
+
+    pub fn index_dealer(&self) -> Option<u8> {
+        self.game.index_dealer
+    }
+
+    pub fn index_active(&self) -> Option<u8> {
+        self.game.index_active
+    }
+
+    pub fn starter_card(&self) -> Option<deck::Card> {
+        self.game.starter_card
+    }
+
+    pub fn crib(&self) -> &[deck::Card] {
+        &self.game.crib
+    }
+
+    pub fn play_groups(&self) -> &[PlayGroup] {
+        &self.game.play_groups
+    }
+
+    // The auction of the current hand in auction cribbage
+    pub fn auction(&self) -> Option<&Auction> {
+        self.game.auction.as_ref()
+    }
+
+    pub fn match_standings(&self) -> &standings::MatchStandings {
+        &self.game.match_standings
+    }
+
+    // Returns what the player in the given seat may see of the game; see view::SeatView
+    pub fn view(&self, seat: u8) -> view::SeatView {
+        view::seat_view(&self.game, seat)
+    }
+
+    // Returns what a spectator may see of the game with the given visibility of the hands
+    pub fn spectator_view(&self, visibility: view::SpectatorVisibility) -> view::SpectatorView {
+        view::spectator_view(&self.game, visibility)
+    }
+
+    // Returns every card of the last hand to have ended, whether by scoring its crib or by a player
+    // winning
+    pub fn hand_reveal(&self) -> Option<&view::HandReveal> {
+        self.game.last_hand.as_ref()
+    }
+
+    // Returns the full count of the play, hand, or crib waiting to be scored, such that a program
+    // can claim it for a bot with manual scoring; empty in any other state
+    pub fn optimal_scoring(&self) -> Vec<score::ScoreEvent> {
+        match self.game.state {
+            GameState::PlayScore => play::score::optimal_scoring(&self.game),
+            GameState::ShowScore => show::optimal_scoring(&self.game),
+            GameState::CribScore => crib::optimal_scoring(&self.game),
+            _ => Vec::new(),
+        }
+    }
+
+    // Returns the points left unclaimed by the count in question in the muggins states, which the
+    // opponents may call, such that a program can call muggins for a bot; empty in any other state
+    // and not to be shown to the players
+    pub fn missed_scoring(&self) -> &[score::ScoreEvent] {
+        match self.game.state {
+            GameState::PlayMuggins | GameState::ShowMuggins | GameState::CribMuggins => {
+                &self.game.missed_score_events
+            }
+            _ => &[],
+        }
+    }
+
+    // Returns whether the card at the given index of a player's hand has been played in any
+    // PlayGroup of the current hand
+    pub fn has_card_been_played(&self, player_index: u8, card_index: u8) -> bool {
+        let card = self.game.players[player_index as usize].hand[card_index as usize];
+        self.game
+            .play_groups
+            .iter()
+            .any(|play_group| play_group.cards.contains(&card))
+    }
+
+    // Processes a GameEvent sent by the player in the given seat, rejecting it with NotYourTurn or
+    // SeatNotAllowed when that seat may not send it now; see state_logic::seats
+    //
+    // A seat's DiscardSelection holds that seat's discards with an empty selection for every other
+    // seat, and the crib is laid away once every seat has discarded
+    pub fn process_event_from(
+        &mut self,
+        seat: u8,
+        event: GameEvent,
+    ) -> Result<game_process_return::Success, game_process_return::Error> {
+        seats::check_seat(&self.game, seat, &event)?;
+        let logged_event = event.clone();
+        let result = match (self.game.state, event) {
+            (GameState::Discard, GameEvent::DiscardSelection(mut player_discards)) => {
+                let discards = player_discards.swap_remove(seat as usize);
+                discard::process_player_discard(&mut self.game, seat, discards)
+            }
+            (_, event) => self.route_event(event),
+        };
+        if result.is_ok() {
+            self.game.event_log.record(Some(seat), logged_event);
+            timeout::update_deadline(&mut self.game);
+        }
+        result
+    }
+
     // Processes the GameEvent objects to progress the model of the game
     pub fn process_event(
         &mut self,
         event: GameEvent,
+    ) -> Result<game_process_return::Success, game_process_return::Error> {
+        let logged_event = event.clone();
+        let result = self.route_event(event);
+        if result.is_ok() {
+            self.game.event_log.record(None, logged_event);
+            timeout::update_deadline(&mut self.game);
+        }
+        result
+    }
+
+    // Registers an observer to be called as the game changes; see observer::GameObserver
+    pub fn add_observer(&mut self, observer: Box<dyn observer::GameObserver>) {
+        self.game.observers.push(observer);
+    }
+
+    // Replaces the clock used for time limits, which is the system clock unless replaced
+    pub fn set_clock(&mut self, clock: Box<dyn clock::Clock>) {
+        self.game.clock = clock;
+        timeout::update_deadline(&mut self.game);
+    }
+
+    // Returns the milliseconds left for the decision being waited on, or None when it has no time
+    // limit; zero once the time has run out and a Timeout event will be accepted
+    pub fn time_remaining(&self) -> Option<u64> {
+        timeout::time_remaining(&self.game)
+    }
+
+    // Returns the sequence number of the last accepted event; every accepted event is numbered one
+    // higher than the last starting from one
+    pub fn sequence(&self) -> u64 {
+        self.game.event_log.sequence
+    }
+
+    // Returns what the player in the given seat needs to catch up from the last sequence number
+    // they saw; see resync::Resync
+    pub fn resync(&self, seat: u8, last_sequence: u64) -> resync::Resync {
+        resync::resync(&self.game, seat, last_sequence)
+    }
+
+    fn route_event(
+        &mut self,
+        event: GameEvent,
     ) -> Result<game_process_return::Success, game_process_return::Error> {
         // Simply routes the game object to the right function depending on the game state and the
         // event passed to this function
         match (self.game.state, event) {
+            // Makes the move decided by the TimeoutPolicy for a player who has run out of time
+            (_, GameEvent::Timeout) => timeout::process_timeout(&mut self.game),
+
             // Accepts a GameSetup  event to continue to CutInitial
             (GameState::GameStart, GameEvent::GameSetup(settings)) => {
                 game_start::game_setup(&mut self.game, settings)
@@ -91,6 +303,14 @@ impl Game {
                 game_process_return::Event::Confirmation,
             ])),
 
+            // Bids for the crib in auction cribbage
+            (GameState::Auction, GameEvent::Bid(bid)) => auction::process_bid(&mut self.game, bid),
+            (GameState::Auction, GameEvent::Pass) => auction::process_pass(&mut self.game),
+            (GameState::Auction, _) => Err(game_process_return::Error::ExpectedEvent(vec![
+                game_process_return::Event::Bid,
+                game_process_return::Event::Pass,
+            ])),
+
             // Removes the chosen card(s) from each hand and places them in the crib
             (GameState::Discard, GameEvent::DiscardSelection(player_discards)) => {
                 discard::process_discard(&mut self.game, player_discards)
@@ -125,92 +345,109 @@ impl Game {
                     game_process_return::Event::Play,
                 ]))
             }
-            /*
-            // Prepares the game for the next PlayGroup or transitions state to show phase
-            (GameState::ResetPlay, GameEvent::Confirmation) => {
-                Err(game_process_return::Error::UnimplementedState)
-            }
-            (GameState::ResetPlay, _) => Err(game_process_return::Error::ExpectedEvent(vec![
-                game_process_return::Event::Confirmation,
-            ])),
-
-            // Scores the play phase of the game automatically or manually
-            (GameState::PlayScore, GameEvent::Confirmation) => {
-                Err(game_process_return::Error::UnimplementedState)
-            }
+            // Scores the last card played when manual scoring is enabled
             (GameState::PlayScore, GameEvent::ManScoreSelection(selection)) => {
-                Err(game_process_return::Error::UnimplementedState)
+                play::play_score(&mut self.game, selection)
             }
             (GameState::PlayScore, _) => Err(game_process_return::Error::ExpectedEvent(vec![
-                game_process_return::Event::Confirmation,
                 game_process_return::Event::ManScoreSelection,
             ])),
 
-            // Processes any calls of muggins for the play phase of the game
-            (GameState::PlayMuggins, GameEvent::Confirmation) => {
-                Err(game_process_return::Error::UnimplementedState)
-            }
-            (GameState::PlayMuggins, GameEvent::Muggins(selection)) => {
-                Err(game_process_return::Error::UnimplementedState)
+            // Pegs one for the go or moves an opponent back with Back Up 10
+            (GameState::PlayGoDecision, GameEvent::GoDecision(decision)) => {
+                play::process_go_decision(&mut self.game, decision)
             }
-            (GameState::PlayMuggins, _) => Err(game_process_return::Error::ExpectedEvent(vec![
+            (GameState::PlayGoDecision, _) => Err(game_process_return::Error::ExpectedEvent(vec![
+                game_process_return::Event::GoDecision,
+            ])),
+
+            // Prepares the game for the next PlayGroup or transitions state to show phase
+            (GameState::ResetPlay, GameEvent::Confirmation) => play::reset_play(&mut self.game),
+            (GameState::ResetPlay, _) => Err(game_process_return::Error::ExpectedEvent(vec![
                 game_process_return::Event::Confirmation,
-                game_process_return::Event::Muggins,
             ])),
+
             // Scores the show phase of the game automatically or manually
-            (GameState::ShowScore, GameEvent::Confirmation) => game_process_return::Error::UnimplementedState,
-            (GameState::ShowScore, GameEvent::ManScoreSelection(selection)) => game_process_return::Error::UnimplementedState,
-            (GameState::ShowScore, _) => game_process_return::Error::ExpectedEvent(vec![
+            (GameState::ShowScore, GameEvent::Confirmation) => {
+                show::process_show_score(&mut self.game, None)
+            }
+            (GameState::ShowScore, GameEvent::ManScoreSelection(selection)) => {
+                show::process_show_score(&mut self.game, Some(selection))
+            }
+            (GameState::ShowScore, _) => Err(game_process_return::Error::ExpectedEvent(vec![
                 game_process_return::Event::Confirmation,
                 game_process_return::Event::ManScoreSelection,
-            ]),
+            ])),
 
-            // Processes any call of muggins for the show phase of the game
-            (GameState::ShowMuggins, GameEvent::Confirmation) => game_process_return::Error::UnimplementedState,
-            (GameState::ShowMuggins, GameEvent::Muggins(selection)) => game_process_return::Error::UnimplementedState,
-            (GameState::ShowMuggins, _) => game_process_return::Error::ExpectedEvent(vec![
+            // Sorts the crib after confirmation allowing the players to see the crib before it is
+            // scored
+            (GameState::CribSort, GameEvent::Confirmation) => {
+                crib::process_crib_sort(&mut self.game)
+            }
+            (GameState::CribSort, _) => Err(game_process_return::Error::ExpectedEvent(vec![
                 game_process_return::Event::Confirmation,
-                game_process_return::Event::Muggins,
-            ]),
+            ])),
 
             // Processes the scoring of the crib automatically or manually
-            (GameState::CribScore, GameEvent::Confirmation) => game_process_return::Error::UnimplementedState,,
-            (GameState::CribScore, GameEvent::ManScoreSelection(selection)) => game_process_return::Error::UnimplementedState,
-            (GameState::CribScore, _) => game_process_return::Error::ExpectedEvent(vec![
+            (GameState::CribScore, GameEvent::Confirmation) => {
+                crib::process_crib_score(&mut self.game, None)
+            }
+            (GameState::CribScore, GameEvent::ManScoreSelection(selection)) => {
+                crib::process_crib_score(&mut self.game, Some(selection))
+            }
+            (GameState::CribScore, _) => Err(game_process_return::Error::ExpectedEvent(vec![
                 game_process_return::Event::Confirmation,
                 game_process_return::Event::ManScoreSelection,
-            ]),
+            ])),
 
-            // Processes any call of muggins for the crib
-            (GameState::CribMuggins, GameEvent::Confirmation) => game_process_return::Error::UnimplementedState,
-            (GameState::CribMuggins, GameEvent::Muggins(selection)) => game_process_return::Error::UnimplementedState,
-            (GameState::CribMuggins, _) => game_process_return::Error::ExpectedEvent(vec![
+            // Starts the next game of the match with a Confirmation event or ends the match with a
+            // Denial event
+            (GameState::Win, GameEvent::Confirmation) => win::process_next_game(&mut self.game),
+            (GameState::Win, GameEvent::Denial) => win::process_end_match(&mut self.game),
+            (GameState::Win, _) => Err(game_process_return::Error::ExpectedEvent(vec![
                 game_process_return::Event::Confirmation,
-                game_process_return::Event::Muggins,
-            ]),
-
-            /* Processes the end of a game
-            (GameState::Win, GameEvent::Confirmation) => Game::win(self, true),
-            (GameState::Win, GameEvent::Denial) => Game::win(self, false),
+                game_process_return::Event::Denial,
+            ])),
 
-            // TODO Processes the end of a match
-            */*/
+            (GameState::End, _) => Err(game_process_return::Error::MatchIsOver),
 
-            // For unexpected GameState
-            (_, _) => Err(game_process_return::Error::UnrecognizedState),
+            // Processes the calls of muggins on the count of the play, a hand, or the crib until
+            // every opponent is done calling with a Muggins(None) event
+            (GameState::PlayMuggins, GameEvent::Muggins(selections)) => {
+                play::play_muggins(&mut self.game, selections)
+            }
+            (GameState::ShowMuggins, GameEvent::Muggins(selections)) => {
+                show::process_show_muggins(&mut self.game, selections)
+            }
+            (GameState::CribMuggins, GameEvent::Muggins(selections)) => {
+                crib::process_crib_muggins(&mut self.game, selections)
+            }
+            (GameState::PlayMuggins, _)
+            | (GameState::ShowMuggins, _)
+            | (GameState::CribMuggins, _) => Err(game_process_return::Error::ExpectedEvent(vec![
+                game_process_return::Event::Muggins,
+            ])),
         }
     }
 }
 
 // Enum for the event sent during the play phase of the game; simply a selection of the card index to be
 // played or a Go if no card play is possible
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum PlayTurn {
     CardSelected(u8),
     Go,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+// Enum for the choice made by the player who would peg one for the go or last card with Back Up 10
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum GoDecision {
+    PegOne,
+    // Moves the player with the given index, and their partner, back ten holes
+    BackUpTen(u8),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub enum GameState {
     // Initializes the Game object based on the settings passed with the GameSetup event
     GameStart,
@@ -220,6 +457,9 @@ pub enum GameState {
     Deal,
     // Sorts the players' hands after receiving a Confirmation event
     Sort,
+    // Processes the Bid and Pass events of auction cribbage in turn from the player to the left of
+    // the dealer until one player is left to take the crib
+    Auction,
     // Compiles the discards into the crib after receiving a HandIndices event
     Discard,
     // Determine the starter card after receiving a Confirmation event
@@ -235,6 +475,9 @@ pub enum GameState {
     // Deals with the calling of muggins of the last card to be played in the PlayGroup with a
     // Muggins event
     PlayMuggins,
+    // Waits for the last player to play to choose between pegging one and moving an opponent back
+    // ten holes with a GoDecision event after a go with Back Up 10
+    PlayGoDecision,
     // Handles the creation of a new PlayGroup or the transition to the ShowScore state after the
     // play of cards is no longer possible for the current PlayGroup
     ResetPlay,
@@ -250,21 +493,54 @@ pub enum GameState {
     CribScore,
     // Deals with the calling of muggins for the crib with a Muggins event
     CribMuggins,
-    // State for when a player has won the game; routes back to Deal for the next game of the
-    // match or to End
-    // and scores, the crib, the starter card, etc.
+    // State for when a player has won the game; routes back to CutInitial for the next game of the
+    // match with a Confirmation event or to End with a Denial event
     Win,
-    // State for the end of play
+    // State for the end of the match
     End,
 }
 
 // Object representing the cards played in one of the groups of 31 or less
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct PlayGroup {
     total: u8,
     cards: Vec<deck::Card>,
 }
 
+impl PlayGroup {
+    pub fn total(&self) -> u8 {
+        self.total
+    }
+
+    pub fn cards(&self) -> &[deck::Card] {
+        &self.cards
+    }
+}
+
+// The bidding for the crib of the current hand in auction cribbage
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Auction {
+    // The player who dealt the hand; the deal passes to their left whoever takes the crib
+    index_dealer: u8,
+    // The player index and points of the highest bid so far
+    high_bid: Option<(u8, u8)>,
+    has_passed: Vec<bool>,
+}
+
+impl Auction {
+    pub fn index_dealer(&self) -> u8 {
+        self.index_dealer
+    }
+
+    pub fn high_bid(&self) -> Option<(u8, u8)> {
+        self.high_bid
+    }
+
+    pub fn has_passed(&self, player_index: u8) -> bool {
+        self.has_passed[player_index as usize]
+    }
+}
+
 // Game object implementation with public variables such as to allow library functions to
 // directly modify everything
 struct GameImpl {
@@ -300,17 +576,44 @@ struct GameImpl {
     // no more than 31
     pub play_groups: Vec<PlayGroup>,
 
-    // Vector to hold the ScoreEvents remaining for muggins
-    pub remaining_score_events: Vec<score::ScoreEvent>,
-
-    // Whether to reset the play phase of the game for when a 31 score is present
-    pub reset_play: bool,
-
     // The players who are cutting for first deal; it will generally start with all players and
     // reduce based on which players tie; if there are no ties than the player with the lowest card
     // value is the first dealer of the game
     pub initial_cut_between_players_with_these_indices: Vec<u8>,
 
+    // The bidding for the crib in auction cribbage; None without an auction or before the first
+    pub auction: Option<Auction>,
+
+    // The discards of each player sent on their own with Game::process_event_from, held until
+    // every player has discarded
+    pub held_discards: Vec<Option<Vec<u8>>>,
+
+    // The holes each player has been moved back by Back Up 10 this game by player index
+    pub holes_moved_back: Vec<u8>,
+
+    // Every accepted event by sequence number for resyncing clients
+    pub event_log: resync::EventLog,
+
+    // The cards each player laid away in the crib this hand by player index
+    pub discards: Vec<Vec<deck::Card>>,
+
+    // Every card of the last hand to have ended; None when it ended before the starter was cut
+    pub last_hand: Option<view::HandReveal>,
+
+    // The points left unclaimed by the last manual count which may still be called in the muggins
+    // states
+    pub missed_score_events: Vec<score::ScoreEvent>,
+
+    // The source of time for the time limits and when the decision being waited on runs out
+    pub clock: Box<dyn clock::Clock>,
+    pub deadline: Option<timeout::Deadline>,
+
+    // Called from within the state logic as the game changes
+    pub observers: Vec<Box<dyn observer::GameObserver>>,
+
+    // The game points earned in every finished game of the match
+    pub match_standings: standings::MatchStandings,
+
     // When active the deck will not reset itself such that one can manually enter values into the
     // deck
     pub is_debug: bool,
@@ -327,12 +630,21 @@ impl GameImpl {
             last_play_was_go: false,
             play_groups: Vec::new(),
             players: Vec::new(),
-            remaining_score_events: Vec::new(),
-            reset_play: false,
             settings: None,
             starter_card: None,
             state: GameState::GameStart,
             initial_cut_between_players_with_these_indices: Vec::new(),
+            auction: None,
+            held_discards: Vec::new(),
+            holes_moved_back: Vec::new(),
+            event_log: resync::EventLog::default(),
+            discards: Vec::new(),
+            last_hand: None,
+            missed_score_events: Vec::new(),
+            clock: Box::new(clock::SystemClock::new()),
+            deadline: None,
+            observers: Vec::new(),
+            match_standings: standings::MatchStandings::default(),
             is_debug: false,
         }
     }
@@ -0,0 +1,209 @@
+use crate::deck;
+use crate::score;
+use crate::standings;
+
+// Callbacks fired from within the state logic as the game changes such that a program can drive
+// animations, sounds, and logging without comparing the game before and after every event
+//
+// Every callback does nothing unless implemented so an observer only implements those it needs;
+// observers are registered with Game::add_observer and are called in the order they were added
+
+#[cfg(test)]
+mod test {
+    use super::GameObserver;
+    use crate::deck::Card;
+    use crate::score::ScoreEvent;
+    use crate::settings::{GameSettings, RuleVariant, ScoringRules, VictorDealerOption};
+    use crate::standings::GameResult;
+    use crate::util::return_next_event;
+    use crate::{Game, GameEvent, GameState};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Notification {
+        StateChange(GameState, GameState),
+        CardsDealt(Vec<usize>, usize),
+        CardPlayed(u8, Card, u8),
+        Score(ScoreEvent),
+        PegMove(u8, u8, u8),
+        GameWon(GameResult),
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        notifications: Arc<Mutex<Vec<Notification>>>,
+    }
+
+    impl Recorder {
+        fn push(&self, notification: Notification) {
+            self.notifications.lock().unwrap().push(notification);
+        }
+    }
+
+    impl GameObserver for Recorder {
+        fn on_state_change(&mut self, old: GameState, new: GameState) {
+            self.push(Notification::StateChange(old, new));
+        }
+        fn on_cards_dealt(&mut self, hands: &[Vec<Card>], crib: &[Card]) {
+            let hand_sizes = hands.iter().map(Vec::len).collect();
+            self.push(Notification::CardsDealt(hand_sizes, crib.len()));
+        }
+        fn on_card_played(&mut self, player_index: u8, card: Card, total: u8) {
+            self.push(Notification::CardPlayed(player_index, card, total));
+        }
+        fn on_score(&mut self, score_event: &ScoreEvent) {
+            self.push(Notification::Score(score_event.clone()));
+        }
+        fn on_peg_move(&mut self, player_index: u8, back_peg_pos: u8, front_peg_pos: u8) {
+            self.push(Notification::PegMove(
+                player_index,
+                back_peg_pos,
+                front_peg_pos,
+            ));
+        }
+        fn on_game_won(&mut self, result: &GameResult) {
+            self.push(Notification::GameWon(result.clone()));
+        }
+    }
+
+    fn settings() -> GameSettings {
+        GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }
+    }
+
+    #[test]
+    fn hand_is_observed() {
+        let recorder = Recorder::default();
+        let notifications = recorder.notifications.clone();
+        let mut game = Game::from_seed(4);
+        game.add_observer(Box::new(recorder));
+        game.process_event(GameEvent::GameSetup(settings()))
+            .unwrap();
+
+        // Every player plays their first playable card until the crib is scored
+        while game.hand_reveal().is_none() {
+            let event = return_next_event(&game);
+            game.process_event(event).unwrap();
+        }
+
+        let notifications = notifications.lock().unwrap();
+        assert_eq!(
+            notifications[0],
+            Notification::StateChange(GameState::GameStart, GameState::CutInitial)
+        );
+        assert_eq!(
+            notifications.last(),
+            Some(&Notification::StateChange(
+                GameState::CribScore,
+                GameState::Deal
+            ))
+        );
+        assert!(notifications.contains(&Notification::CardsDealt(vec![6, 6], 0)));
+        let cards_played = notifications
+            .iter()
+            .filter(|notification| matches!(notification, Notification::CardPlayed(..)))
+            .count();
+        assert_eq!(cards_played, 8);
+
+        // The points of the ScoreEvents and the last peg moves add up to where the pegs are
+        for (player_index, player) in game.players().iter().enumerate() {
+            let points: u8 = notifications
+                .iter()
+                .filter_map(|notification| match notification {
+                    Notification::Score(score_event)
+                        if score_event.player_index as usize == player_index =>
+                    {
+                        Some(score_event.point_value)
+                    }
+                    _ => None,
+                })
+                .sum();
+            assert_eq!(points, player.front_peg_pos);
+            let last_peg_move = notifications.iter().rev().find(|notification| {
+                matches!(notification, Notification::PegMove(index, ..) if *index as usize == player_index)
+            });
+            if player.front_peg_pos > 0 {
+                assert_eq!(
+                    last_peg_move,
+                    Some(&Notification::PegMove(
+                        player_index as u8,
+                        player.back_peg_pos,
+                        player.front_peg_pos
+                    ))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn game_won_is_observed() {
+        let recorder = Recorder::default();
+        let notifications = recorder.notifications.clone();
+        let mut game = crate::GameImpl::new();
+        crate::state_logic::game_start::game_setup(&mut game, settings()).unwrap();
+        game.observers.push(Box::new(recorder));
+        game.players[0].change_score(119, 121);
+
+        let score_event = ScoreEvent {
+            score_type: crate::score::ScoreType::Play(crate::score::PlayScoreType::Pair),
+            player_index: 0,
+            point_value: 2,
+        };
+        assert!(crate::util::process_score_events(
+            &mut game,
+            0,
+            std::slice::from_ref(&score_event)
+        ));
+        let result = game.match_standings.results[0].clone();
+        assert_eq!(
+            *notifications.lock().unwrap(),
+            vec![
+                Notification::Score(score_event),
+                Notification::PegMove(0, 119, 121),
+                Notification::GameWon(result),
+                Notification::StateChange(GameState::CutInitial, GameState::Win),
+            ]
+        );
+    }
+}
+
+pub trait GameObserver: Send {
+    // The game moved from one state to another; not called when an event leaves the state as it was
+    fn on_state_change(&mut self, _old: crate::GameState, _new: crate::GameState) {}
+
+    // The hand of every player by player index once dealt, and any cards dealt to the crib from the
+    // deck
+    fn on_cards_dealt(&mut self, _hands: &[Vec<deck::Card>], _crib: &[deck::Card]) {}
+
+    // A card was played, bringing the total of the current PlayGroup to the total given
+    fn on_card_played(&mut self, _player_index: u8, _card: deck::Card, _total: u8) {}
+
+    // Points are being scored; called for each ScoreEvent before the pegs are moved
+    fn on_score(&mut self, _score_event: &score::ScoreEvent) {}
+
+    // The pegs of a player moved, forward when scoring or back with Back Up 10 or an auction bid;
+    // called for a player's partner as well since they share pegs
+    fn on_peg_move(&mut self, _player_index: u8, _back_peg_pos: u8, _front_peg_pos: u8) {}
+
+    // A game was won by reaching the target score or by the forfeit of a player who ran out of time
+    fn on_game_won(&mut self, _result: &standings::GameResult) {}
+}
+
+// Calls every observer of the game in the order they were added
+pub(crate) fn notify(game: &mut crate::GameImpl, mut callback: impl FnMut(&mut dyn GameObserver)) {
+    for observer in &mut game.observers {
+        callback(observer.as_mut());
+    }
+}
@@ -1,5 +1,52 @@
 use crate::deck;
 
+#[cfg(test)]
+mod test {
+    fn set_up_player(back_peg_pos: u8, front_peg_pos: u8) -> super::Player {
+        super::Player {
+            back_peg_pos,
+            front_peg_pos,
+            hand: Vec::new(),
+            partner_index: None,
+        }
+    }
+
+    #[test]
+    fn positive_change() {
+        let mut player = set_up_player(3, 10);
+        player.change_score(5, 121);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (10, 15));
+
+        player.change_score(0, 121);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (10, 15));
+    }
+
+    #[test]
+    fn negative_change() {
+        let mut player = set_up_player(10, 15);
+        player.change_score(-3, 121);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (10, 12));
+
+        // The back peg follows the front peg back and neither goes past the start
+        player.change_score(-11, 121);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (1, 1));
+        player.change_score(i16::MIN, 121);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (0, 0));
+    }
+
+    // The front peg stops in the game hole rather than wrapping around past 255
+    #[test]
+    fn change_past_target() {
+        let mut player = set_up_player(110, 119);
+        player.change_score(29, 121);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (119, 121));
+
+        let mut player = set_up_player(240, 250);
+        player.change_score(i16::MAX, u8::MAX);
+        assert_eq!((player.back_peg_pos, player.front_peg_pos), (250, 255));
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Player {
     // The peg positions tracking score
@@ -13,12 +60,23 @@ pub struct Player {
 }
 
 impl Player {
-    pub fn change_score(&mut self, change: u8) {
+    // Moves the pegs by the change in score; the front peg never moves past the start or past the
+    // target score of the game, where the game is won
+    pub fn change_score(&mut self, change: i16, target_score: u8) {
+        let front_peg_pos = (i32::from(self.front_peg_pos) + i32::from(change))
+            .clamp(0, i32::from(target_score)) as u8;
+
         // Move the back peg up and then the front peg forward to simulate moving the back peg
         // forward past the front peg when the change is positive
         if change > 0 {
             self.back_peg_pos = self.front_peg_pos;
-            self.front_peg_pos += change as u8;
+            self.front_peg_pos = front_peg_pos;
+        }
+        // When the change is negative such as with Back Up 10, the front peg moves back and the
+        // back peg follows it if the front peg passes behind it
+        else if change < 0 {
+            self.front_peg_pos = front_peg_pos;
+            self.back_peg_pos = self.back_peg_pos.min(self.front_peg_pos);
         }
         // If no points are scored, the pegs should not be touched
     }
@@ -0,0 +1,247 @@
+use crate::view;
+use crate::GameEvent;
+use serde::Serialize;
+use std::collections::VecDeque;
+
+// Numbers every accepted event such that a client which lost its connection can catch up from the
+// last sequence number it saw, independent of how the client and the game are connected
+//
+// A client is always sent a fresh SeatView since the events alone don't hold what they led to,
+// such as the cards dealt or cut, along with the events it missed redacted for its seat while the
+// most recent events are still kept
+
+// The number of accepted events kept for resyncing; a hand takes around thirty events with two
+// players and around sixty with six
+pub const EVENT_LOG_LENGTH: usize = 256;
+
+#[cfg(test)]
+mod test {
+    use super::Resync;
+    use crate::settings::{GameSettings, RuleVariant, ScoringRules, VictorDealerOption};
+    use crate::util::return_next_event;
+    use crate::{Game, GameEvent, GameState};
+
+    fn set_up_game() -> Game {
+        let mut game = Game::from_seed(2);
+        game.process_event(GameEvent::GameSetup(GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }))
+        .unwrap();
+        game
+    }
+
+    // Sends the next event of the simple game played by return_next_event
+    fn step(game: &mut Game) {
+        let event = return_next_event(game);
+        game.process_event(event).unwrap();
+    }
+
+    #[test]
+    fn only_accepted_events_are_numbered() {
+        let mut game = set_up_game();
+        assert_eq!(game.sequence(), 1);
+        assert!(game.process_event(GameEvent::Denial).is_err());
+        assert_eq!(game.sequence(), 1);
+        game.process_event(GameEvent::Confirmation).unwrap();
+        assert_eq!(game.sequence(), 2);
+        assert_eq!(game.view(0).sequence, 2);
+    }
+
+    #[test]
+    fn missed_events_are_redacted() {
+        let mut game = set_up_game();
+        while game.state() != GameState::Discard {
+            step(&mut game);
+        }
+        let last_seen = game.sequence();
+        assert_eq!(
+            game.resync(1, last_seen).missed.map(|events| events.len()),
+            Some(0)
+        );
+
+        let dealer = game.index_dealer().unwrap();
+        let pone = 1 - dealer;
+        for (seat, discards) in [(dealer, vec![2, 3]), (pone, vec![0, 1])] {
+            let mut discard_indices_group = vec![vec![], vec![]];
+            discard_indices_group[seat as usize] = discards;
+            game.process_event_from(seat, GameEvent::DiscardSelection(discard_indices_group))
+                .unwrap();
+        }
+        step(&mut game);
+
+        let events = game.resync(dealer, last_seen).missed.unwrap();
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| event.sequence)
+                .collect::<Vec<u64>>(),
+            vec![last_seen + 1, last_seen + 2, last_seen + 3]
+        );
+        assert_eq!(events[0].seat, Some(dealer));
+        assert!(matches!(
+            &events[0].event,
+            GameEvent::DiscardSelection(discards) if discards[dealer as usize] == [2, 3]
+        ));
+        assert_eq!(events[2].seat, None);
+        assert!(matches!(events[2].event, GameEvent::Confirmation));
+
+        // Each seat sees that the other discarded but not which cards they discarded
+        assert!(matches!(
+            &events[1].event,
+            GameEvent::DiscardSelection(discards) if discards.iter().all(Vec::is_empty)
+        ));
+        let events = game.resync(pone, last_seen).missed.unwrap();
+        assert!(matches!(
+            &events[0].event,
+            GameEvent::DiscardSelection(discards) if discards.iter().all(Vec::is_empty)
+        ));
+        assert!(matches!(
+            &events[1].event,
+            GameEvent::DiscardSelection(discards) if discards[pone as usize] == [0, 1]
+        ));
+    }
+
+    #[test]
+    fn snapshot_when_too_much_was_missed() {
+        let mut game = set_up_game();
+        while game.sequence() <= super::EVENT_LOG_LENGTH as u64 + 1 {
+            step(&mut game);
+        }
+        let Resync { view, missed } = game.resync(0, 1);
+        assert_eq!(view.sequence, game.sequence());
+        assert_eq!(view.seat, 0);
+        assert!(missed.is_none());
+        assert_eq!(
+            game.resync(0, 2).missed.map(|events| events.len()),
+            Some(super::EVENT_LOG_LENGTH)
+        );
+
+        // A sequence number from the future can't be caught up from
+        assert!(game.resync(0, game.sequence() + 1).missed.is_none());
+    }
+
+    // A seat which missed part of the play is rebuilt as it sees the game now, with the cards it
+    // holds and without the cards held by the other seat
+    #[test]
+    fn view_is_rebuilt_mid_play() {
+        let mut game = set_up_game();
+        while game.state() != GameState::PlayWaitForCard {
+            step(&mut game);
+        }
+        let last_seen = game.sequence();
+        while game.play_groups().last().unwrap().cards().len() < 3 {
+            step(&mut game);
+        }
+        assert_eq!(game.state(), GameState::PlayWaitForCard);
+
+        for seat in 0..2 {
+            let Resync { view, missed } = game.resync(seat, last_seen);
+            assert_eq!(*view, game.view(seat));
+            assert_eq!(view.play_groups.last().unwrap().cards().len(), 3);
+            assert_eq!(view.players[seat as usize].hand.as_ref().unwrap().len(), 4);
+            assert_eq!(view.players[1 - seat as usize].hand, None);
+            let missed = missed.unwrap();
+            assert_eq!(missed.last().unwrap().sequence, game.sequence());
+            assert!(missed.iter().all(|event| event.sequence > last_seen));
+        }
+    }
+}
+
+// An accepted event as it may be shown to one seat
+#[derive(Debug, Clone, Serialize)]
+pub struct SeatEvent {
+    pub sequence: u64,
+    // The seat which sent the event when it was sent with Game::process_event_from
+    pub seat: Option<u8>,
+    pub event: GameEvent,
+}
+
+// What a client is sent to catch up to the game from the last sequence number it saw
+#[derive(Debug, Clone, Serialize)]
+pub struct Resync {
+    // The game as the seat sees it now, which the client takes in place of its own
+    pub view: Box<view::SeatView>,
+    // Every event accepted since, in order; empty when the client is up to date and None when
+    // some of the events missed are no longer kept
+    pub missed: Option<Vec<SeatEvent>>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct EventLog {
+    // The sequence number of the last accepted event, or zero before any event
+    pub sequence: u64,
+    pub events: VecDeque<SeatEvent>,
+}
+
+impl EventLog {
+    pub(crate) fn record(&mut self, seat: Option<u8>, event: GameEvent) {
+        self.sequence += 1;
+        if self.events.len() == EVENT_LOG_LENGTH {
+            self.events.pop_front();
+        }
+        self.events.push_back(SeatEvent {
+            sequence: self.sequence,
+            seat,
+            event,
+        });
+    }
+}
+
+pub(crate) fn resync(game: &crate::GameImpl, seat: u8, last_sequence: u64) -> Resync {
+    let log = &game.event_log;
+    let oldest = log
+        .events
+        .front()
+        .map_or(log.sequence + 1, |event| event.sequence);
+    let missed = if last_sequence > log.sequence || last_sequence + 1 < oldest {
+        None
+    } else {
+        Some(
+            log.events
+                .iter()
+                .filter(|event| event.sequence > last_sequence)
+                .map(|event| redact(event, seat))
+                .collect(),
+        )
+    };
+
+    Resync {
+        view: Box::new(view::seat_view(game, seat)),
+        missed,
+    }
+}
+
+// Hides the discards made by every other seat; every other event holds nothing a seat may not know
+// once it has been accepted
+fn redact(event: &SeatEvent, seat: u8) -> SeatEvent {
+    match &event.event {
+        GameEvent::DiscardSelection(discard_indices_group) => SeatEvent {
+            event: GameEvent::DiscardSelection(
+                discard_indices_group
+                    .iter()
+                    .enumerate()
+                    .map(|(player_index, discards)| {
+                        if player_index as u8 == seat {
+                            discards.clone()
+                        } else {
+                            Vec::new()
+                        }
+                    })
+                    .collect(),
+            ),
+            ..event.clone()
+        },
+        _ => event.clone(),
+    }
+}
@@ -0,0 +1,491 @@
+use super::PlayScoreType;
+use super::ScoreEvent;
+use super::ScoreType;
+use super::ShowScoreType;
+use crate::deck;
+use serde::Serialize;
+
+// Comparison of the ScoreEvents claimed with manual scoring against the optimal scoring such that
+// a player can be shown exactly what they missed or claimed wrongly, after bringing the claims to
+// the same form as the optimal scoring such that a correct count is never rejected for how it was
+// grouped
+
+#[cfg(test)]
+mod test {
+    use super::diff;
+    use super::normalize;
+    use super::ScoreDiff;
+    use crate::score::show::score_hand;
+    use crate::score::ShowScoreType;
+    use crate::util::return_card;
+
+    fn event(score_type: crate::score::PlayScoreType, point_value: u8) -> super::ScoreEvent {
+        super::ScoreEvent {
+            score_type: crate::score::ScoreType::Play(score_type),
+            player_index: 0,
+            point_value,
+        }
+    }
+
+    #[test]
+    fn exact() {
+        let optimal = vec![
+            event(crate::score::PlayScoreType::Fifteen, 2),
+            event(crate::score::PlayScoreType::Pair, 2),
+        ];
+        let claimed = vec![optimal[1].clone(), optimal[0].clone()];
+
+        let score_diff = diff(&claimed, &optimal);
+        assert!(score_diff.is_exact());
+        assert_eq!(
+            score_diff,
+            ScoreDiff {
+                overclaimed: vec![],
+                missed: vec![],
+                claimed_points: 4,
+                optimal_points: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn overclaimed_and_missed() {
+        let optimal = vec![
+            event(crate::score::PlayScoreType::Fifteen, 2),
+            event(crate::score::PlayScoreType::Straight(3), 3),
+        ];
+        let claimed = vec![
+            event(crate::score::PlayScoreType::Fifteen, 2),
+            event(crate::score::PlayScoreType::Pair, 2),
+        ];
+
+        let score_diff = diff(&claimed, &optimal);
+        assert!(!score_diff.is_exact());
+        assert_eq!(
+            score_diff,
+            ScoreDiff {
+                overclaimed: vec![event(crate::score::PlayScoreType::Pair, 2)],
+                missed: vec![event(crate::score::PlayScoreType::Straight(3), 3)],
+                claimed_points: 4,
+                optimal_points: 5,
+            }
+        );
+    }
+
+    // Claiming the same ScoreEvent twice only matches it once
+    #[test]
+    fn claimed_twice() {
+        let optimal = vec![event(crate::score::PlayScoreType::Fifteen, 2)];
+        let claimed = vec![optimal[0].clone(), optimal[0].clone()];
+
+        let score_diff = diff(&claimed, &optimal);
+        assert_eq!(score_diff.overclaimed, optimal);
+        assert!(score_diff.missed.is_empty());
+        assert_eq!(score_diff.claimed_points, 4);
+    }
+
+    fn show_event(score_type: ShowScoreType, point_value: u8) -> super::ScoreEvent {
+        super::ScoreEvent {
+            score_type: crate::score::ScoreType::Show(score_type),
+            player_index: 0,
+            point_value,
+        }
+    }
+
+    // Claims in the same form as the scoring functions are left alone
+    #[test]
+    fn normalize_optimal() {
+        let hand = vec![
+            return_card('3', 'H'),
+            return_card('3', 'D'),
+            return_card('4', 'C'),
+            return_card('5', 'S'),
+        ];
+        let optimal = score_hand(0, &hand, return_card('5', 'H'), false);
+        assert_eq!(normalize(&optimal), optimal);
+    }
+
+    #[test]
+    fn normalize_pairs_to_tuples() {
+        let (five_h, five_d, five_c, five_s) = (
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('5', 'S'),
+        );
+        let fifteen = show_event(ShowScoreType::Fifteen(vec![five_h, five_d, five_c]), 2);
+
+        let claims = vec![
+            show_event(ShowScoreType::Pair(vec![five_d, five_h]), 2),
+            fifteen.clone(),
+            show_event(ShowScoreType::Pair(vec![five_h, five_c]), 2),
+            show_event(ShowScoreType::Pair(vec![five_d, five_c]), 2),
+        ];
+        assert_eq!(
+            normalize(&claims),
+            vec![
+                show_event(ShowScoreType::Triple(vec![five_h, five_d, five_c]), 6),
+                fifteen,
+            ]
+        );
+
+        let all = [five_h, five_d, five_c, five_s];
+        let mut claims = Vec::new();
+        for (position, first) in all.iter().enumerate() {
+            for second in &all[position + 1..] {
+                claims.push(show_event(ShowScoreType::Pair(vec![*first, *second]), 2));
+            }
+        }
+        assert_eq!(
+            normalize(&claims),
+            vec![show_event(ShowScoreType::Quadruple(all.to_vec()), 12)]
+        );
+
+        // Two pairs aren't a triple, and the same pair claimed three times isn't either
+        let claims = vec![
+            show_event(ShowScoreType::Pair(vec![five_h, five_d]), 2),
+            show_event(ShowScoreType::Pair(vec![five_h, five_c]), 2),
+        ];
+        assert_eq!(normalize(&claims), claims);
+        let claims = vec![claims[0].clone(), claims[0].clone(), claims[0].clone()];
+        assert_eq!(normalize(&claims), claims);
+    }
+
+    #[test]
+    fn normalize_play_pairs() {
+        let claims = vec![
+            event(crate::score::PlayScoreType::Pair, 2),
+            event(crate::score::PlayScoreType::Pair, 2),
+            event(crate::score::PlayScoreType::Fifteen, 2),
+            event(crate::score::PlayScoreType::Pair, 2),
+        ];
+        assert_eq!(
+            normalize(&claims),
+            vec![
+                event(crate::score::PlayScoreType::Triple, 6),
+                event(crate::score::PlayScoreType::Fifteen, 2),
+            ]
+        );
+    }
+
+    // A double run claimed as one straight, with or without the points of its pair, is accepted
+    // for a hand scored with the double run split up
+    #[test]
+    fn normalize_combined_runs() {
+        let hand = vec![
+            return_card('4', 'D'),
+            return_card('3', 'H'),
+            return_card('5', 'S'),
+            return_card('4', 'C'),
+        ];
+        let starter = return_card('K', 'H');
+        let optimal = score_hand(0, &hand, starter, false);
+        let fifteen = show_event(
+            ShowScoreType::Fifteen(vec![return_card('5', 'S'), starter]),
+            2,
+        );
+        let pair = show_event(
+            ShowScoreType::Pair(vec![return_card('4', 'D'), return_card('4', 'C')]),
+            2,
+        );
+
+        let claims = vec![
+            fifteen.clone(),
+            show_event(ShowScoreType::Straight(hand.clone()), 8),
+        ];
+        assert!(diff(&normalize(&claims), &optimal).is_exact());
+
+        let claims = vec![
+            fifteen.clone(),
+            pair,
+            show_event(ShowScoreType::Straight(hand.clone()), 6),
+        ];
+        assert!(diff(&normalize(&claims), &optimal).is_exact());
+
+        // Claiming the pair twice or the wrong points for the run is still wrong
+        let claims = vec![fifteen, show_event(ShowScoreType::Straight(hand), 7)];
+        assert!(!diff(&normalize(&claims), &optimal).is_exact());
+    }
+
+    #[test]
+    fn normalize_triple_run() {
+        let hand = vec![
+            return_card('9', 'H'),
+            return_card('9', 'D'),
+            return_card('9', 'C'),
+            return_card('T', 'S'),
+        ];
+        let starter = return_card('J', 'H');
+        let mut cards = hand.clone();
+        cards.push(starter);
+
+        let claims = vec![show_event(ShowScoreType::Straight(cards), 15)];
+        assert!(diff(&normalize(&claims), &score_hand(0, &hand, starter, false)).is_exact());
+    }
+}
+
+// The difference between a manual scoring and the optimal scoring; each list keeps the order the
+// ScoreEvents were given in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ScoreDiff {
+    // Claimed ScoreEvents which aren't part of the optimal scoring, including any claimed more
+    // times than they occur
+    pub overclaimed: Vec<ScoreEvent>,
+    // ScoreEvents of the optimal scoring which weren't claimed
+    pub missed: Vec<ScoreEvent>,
+    pub claimed_points: u32,
+    pub optimal_points: u32,
+}
+
+impl ScoreDiff {
+    // Returns whether the claims match the optimal scoring exactly
+    pub fn is_exact(&self) -> bool {
+        self.overclaimed.is_empty() && self.missed.is_empty()
+    }
+}
+
+// Matches each claimed ScoreEvent to an equal ScoreEvent of the optimal scoring that hasn't already
+// been matched and returns whatever is left over on either side
+pub fn diff(claimed: &[ScoreEvent], optimal: &[ScoreEvent]) -> ScoreDiff {
+    let mut missed = optimal.to_vec();
+    let mut overclaimed = Vec::new();
+
+    for claim in claimed {
+        match missed.iter().position(|score_event| score_event == claim) {
+            Some(position) => {
+                missed.remove(position);
+            }
+            None => overclaimed.push(claim.clone()),
+        }
+    }
+
+    ScoreDiff {
+        overclaimed,
+        missed,
+        claimed_points: total_points(claimed),
+        optimal_points: total_points(optimal),
+    }
+}
+
+fn total_points(score_events: &[ScoreEvent]) -> u32 {
+    score_events
+        .iter()
+        .map(|score_event| u32::from(score_event.point_value))
+        .sum()
+}
+
+// Returns the claims in the form given by the scoring functions: the cards of each show ScoreEvent
+// sorted, a straight claimed over the cards of a double, triple or double double run split into its
+// straights along with the tuples within it when its points include them, and the pairs making up
+// a triple or quadruple joined into one ScoreEvent. Claims that don't match any of these are left
+// alone so that they're still reported when wrong, and the order of the claims is kept
+pub fn normalize(claims: &[ScoreEvent]) -> Vec<ScoreEvent> {
+    let mut expanded = Vec::new();
+    for claim in claims {
+        match &claim.score_type {
+            ScoreType::Show(show_score_type) => {
+                let claim = ScoreEvent {
+                    score_type: ScoreType::Show(sort_cards(show_score_type)),
+                    player_index: claim.player_index,
+                    point_value: claim.point_value,
+                };
+                match split_run(&claim) {
+                    Some(score_events) => expanded.extend(score_events),
+                    None => expanded.push(claim),
+                }
+            }
+            ScoreType::Play(_) => expanded.push(claim.clone()),
+        }
+    }
+
+    join_pairs(expanded)
+}
+
+// Returns a copy of the ShowScoreType with its cards sorted
+fn sort_cards(show_score_type: &ShowScoreType) -> ShowScoreType {
+    let sorted = |cards: &Vec<deck::Card>| {
+        let mut cards = cards.clone();
+        cards.sort();
+        cards
+    };
+    match show_score_type {
+        ShowScoreType::Fifteen(cards) => ShowScoreType::Fifteen(sorted(cards)),
+        ShowScoreType::Pair(cards) => ShowScoreType::Pair(sorted(cards)),
+        ShowScoreType::Triple(cards) => ShowScoreType::Triple(sorted(cards)),
+        ShowScoreType::Quadruple(cards) => ShowScoreType::Quadruple(sorted(cards)),
+        ShowScoreType::Straight(cards) => ShowScoreType::Straight(sorted(cards)),
+        ShowScoreType::FourFlush(cards) => ShowScoreType::FourFlush(sorted(cards)),
+        ShowScoreType::FiveFlush(cards) => ShowScoreType::FiveFlush(sorted(cards)),
+        ShowScoreType::Nobs(cards) => ShowScoreType::Nobs(sorted(cards)),
+        ShowScoreType::Nineteen(cards) => ShowScoreType::Nineteen(sorted(cards)),
+        // The joker comes before the card it was counted as
+        ShowScoreType::Wild(cards) => ShowScoreType::Wild(cards.clone()),
+    }
+}
+
+// Splits a show straight whose cards repeat values into each straight it holds, with the tuples
+// of the repeated values as well when the points claimed count them; returns None for any other
+// claim or when the points match neither
+fn split_run(claim: &ScoreEvent) -> Option<Vec<ScoreEvent>> {
+    let cards = match &claim.score_type {
+        ScoreType::Show(ShowScoreType::Straight(cards)) => cards,
+        _ => return None,
+    };
+
+    // The cards are sorted so each value's cards are together
+    let mut cards_of_value: Vec<Vec<deck::Card>> = Vec::new();
+    for card in cards {
+        match cards_of_value.last_mut() {
+            Some(group) if group[0].value == card.value => group.push(*card),
+            _ => cards_of_value.push(vec![*card]),
+        }
+    }
+    let is_consecutive = cards_of_value
+        .windows(2)
+        .all(|pair| deck::return_value(pair[1][0]) == deck::return_value(pair[0][0]) + 1);
+    if cards_of_value.len() < 3 || cards_of_value.len() == cards.len() || !is_consecutive {
+        return None;
+    }
+
+    let mut straights: Vec<Vec<deck::Card>> = vec![Vec::new()];
+    for group in &cards_of_value {
+        let mut extended = Vec::new();
+        for straight in &straights {
+            for card in group {
+                let mut next = straight.clone();
+                next.push(*card);
+                extended.push(next);
+            }
+        }
+        straights = extended;
+    }
+    let mut output: Vec<ScoreEvent> = straights
+        .into_iter()
+        .map(|straight| ScoreEvent {
+            point_value: straight.len() as u8,
+            score_type: ScoreType::Show(ShowScoreType::Straight(straight)),
+            player_index: claim.player_index,
+        })
+        .collect();
+    let run_points = total_points(&output);
+
+    let tuples: Vec<ScoreEvent> = cards_of_value
+        .iter()
+        .filter_map(|group| {
+            let (score_type, point_value) = match group.len() {
+                2 => (ShowScoreType::Pair(group.clone()), 2),
+                3 => (ShowScoreType::Triple(group.clone()), 6),
+                4 => (ShowScoreType::Quadruple(group.clone()), 12),
+                _ => return None,
+            };
+            Some(ScoreEvent {
+                score_type: ScoreType::Show(score_type),
+                player_index: claim.player_index,
+                point_value,
+            })
+        })
+        .collect();
+
+    let claimed_points = u32::from(claim.point_value);
+    if claimed_points == run_points {
+        Some(output)
+    } else if claimed_points == run_points + total_points(&tuples) {
+        output.extend(tuples);
+        Some(output)
+    } else {
+        None
+    }
+}
+
+// Joins pairs that together make up a triple or a quadruple: in the show the pairs of each set of
+// three or four cards of a value, and in the play three or six pairs claimed by the same player.
+// The joined ScoreEvent takes the place of the first of its pairs
+fn join_pairs(claims: Vec<ScoreEvent>) -> Vec<ScoreEvent> {
+    // The key shared by the pairs that may be joined together
+    let pair_key = |claim: &ScoreEvent| match &claim.score_type {
+        ScoreType::Show(ShowScoreType::Pair(cards))
+            if claim.point_value == 2 && cards.len() == 2 && cards[0].value == cards[1].value =>
+        {
+            Some((claim.player_index, Some(cards[0].value)))
+        }
+        ScoreType::Play(PlayScoreType::Pair) if claim.point_value == 2 => {
+            Some((claim.player_index, None))
+        }
+        _ => None,
+    };
+
+    let mut output = Vec::new();
+    let mut is_joined = vec![false; claims.len()];
+    for (position, claim) in claims.iter().enumerate() {
+        if is_joined[position] {
+            continue;
+        }
+        let key = match pair_key(claim) {
+            Some(key) => key,
+            None => {
+                output.push(claim.clone());
+                continue;
+            }
+        };
+
+        let positions: Vec<usize> = (position..claims.len())
+            .filter(|other| !is_joined[*other] && pair_key(&claims[*other]) == Some(key))
+            .collect();
+        let pairs: Vec<&ScoreEvent> = positions.iter().map(|other| &claims[*other]).collect();
+
+        match joined_tuple(&pairs) {
+            Some(tuple) => {
+                for other in positions {
+                    is_joined[other] = true;
+                }
+                output.push(tuple);
+            }
+            None => output.push(claim.clone()),
+        }
+    }
+    output
+}
+
+// Returns the triple or quadruple made up of exactly the given pairs, if there is one
+fn joined_tuple(pairs: &[&ScoreEvent]) -> Option<ScoreEvent> {
+    let player_index = pairs[0].player_index;
+    let tuple = |score_type, point_value| {
+        Some(ScoreEvent {
+            score_type,
+            player_index,
+            point_value,
+        })
+    };
+
+    if let ScoreType::Play(_) = pairs[0].score_type {
+        return match pairs.len() {
+            3 => tuple(ScoreType::Play(PlayScoreType::Triple), 6),
+            6 => tuple(ScoreType::Play(PlayScoreType::Quadruple), 12),
+            _ => None,
+        };
+    }
+
+    let mut cards: Vec<deck::Card> = Vec::new();
+    let mut card_pairs: Vec<&Vec<deck::Card>> = Vec::new();
+    for pair in pairs {
+        if let ScoreType::Show(ShowScoreType::Pair(pair_cards)) = &pair.score_type {
+            card_pairs.push(pair_cards);
+            cards.extend(pair_cards);
+        }
+    }
+    cards.sort();
+    cards.dedup();
+    card_pairs.sort();
+    card_pairs.dedup();
+
+    // Every pair of the cards must be claimed exactly once
+    let num_pairs = cards.len() * (cards.len() - 1) / 2;
+    if card_pairs.len() != pairs.len() || num_pairs != pairs.len() {
+        return None;
+    }
+    match cards.len() {
+        3 => tuple(ScoreType::Show(ShowScoreType::Triple(cards)), 6),
+        4 => tuple(ScoreType::Show(ShowScoreType::Quadruple(cards)), 12),
+        _ => None,
+    }
+}
@@ -1,13 +1,15 @@
+pub mod manual;
 pub mod play;
 pub mod show;
+pub mod spoken;
 
 use crate::deck;
-use std::cmp;
+use serde::{Deserialize, Serialize};
 
 // Enum indicating the type of scoring events encountered during the play phase
 // Scoring is based on the entire PlayGroup
 // TODO Log Nibs and LastCard event as needed in the relevant portion of the main file
-#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum PlayScoreType {
     Nibs,         // Jack as starter card; two points for dealer
     Pair,         // Two cards with the same value; 2pts
@@ -21,9 +23,9 @@ pub enum PlayScoreType {
 
 // Enum indicating the type of scoring events encoutered in the show phase
 // Enum's options contain the cards used to make up each score event
-// Allow manual scoring to count triples and quadruples as multiple pairs and to score double runs,
-// triple runs, and double double runs with one selection
-#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
+// Manual scoring may count triples and quadruples as multiple pairs and score double runs, triple
+// runs, and double double runs with one selection; see manual::normalize
+#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ShowScoreType {
     // Any combination of cards which add to 15; two pts
     Fifteen(Vec<deck::Card>),
@@ -41,10 +43,14 @@ pub enum ShowScoreType {
     FiveFlush(Vec<deck::Card>),
     // Jack in hand which matches suit of starter card; one pt
     Nobs(Vec<deck::Card>),
+    // A joker followed by the card it was counted as; worth nothing itself
+    Wild(Vec<deck::Card>),
+    // A hand and starter which would score nothing, when a bonus is given for it
+    Nineteen(Vec<deck::Card>),
 }
 
 // Enum for indicating whether a score event was made during the play phase or the show phase
-#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub enum ScoreType {
     Play(PlayScoreType),
     Show(ShowScoreType),
@@ -54,7 +60,7 @@ pub enum ScoreType {
 // Used in logs of the game, manual scoring selection/confirmation, and for automatic scoring
 // Vectors of ScoreEvents are returned by the scoring functions in this file to represent the
 // correct score of each hand or PlayGroup
-#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd)]
+#[derive(Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct ScoreEvent {
     pub score_type: ScoreType,
     pub player_index: u8,
@@ -27,6 +27,33 @@ mod test {
         assert_eq!(play_score(0, &play_group), expected);
     }
 
+    // A joker counts its play value towards fifteen and 31 but neither pairs nor makes a run
+    #[test]
+    fn check_joker() {
+        let play_group = crate::PlayGroup {
+            total: 15,
+            cards: vec![
+                return_card('3', 'H'),
+                return_card('2', 'S'),
+                return_card('X', 'H'),
+            ],
+        };
+        assert_eq!(
+            play_score(0, &play_group),
+            vec![super::ScoreEvent {
+                player_index: 0,
+                point_value: 2,
+                score_type: super::ScoreType::Play(super::PlayScoreType::Fifteen),
+            }]
+        );
+
+        let play_group = crate::PlayGroup {
+            total: 20,
+            cards: vec![return_card('X', 'H'), return_card('X', 'S')],
+        };
+        assert_eq!(play_score(0, &play_group), vec![]);
+    }
+
     #[test]
     fn check_thirty_one() {
         // Intentionally avoids a run or any tuples
@@ -61,7 +88,7 @@ mod test {
             let mut cards = Vec::new();
             let mut total = 0;
             for value in 1..length + 1 {
-                cards.push(return_card((value + 48 as u8) as char, 'H'));
+                cards.push(return_card((value + 48) as char, 'H'));
                 total += value;
             }
 
@@ -205,6 +232,36 @@ mod test {
 // Note: this function assumes that the PlayGroup total is correct, if you're getting bad totals,
 // it's because of the PlayWaitForCard state
 pub fn play_score(index: u8, play_group: &crate::PlayGroup) -> Vec<ScoreEvent> {
+    score_play(index, play_group, &play_group.cards)
+}
+
+// Returns the scoring of the latest addition to the last of the PlayGroups of the hand under the
+// given ScoringRules, where a run may include the cards of the earlier PlayGroups when the rules
+// allow runs across a reset
+pub fn play_score_with_rules(
+    index: u8,
+    play_groups: &[crate::PlayGroup],
+    rules: &crate::settings::ScoringRules,
+) -> Vec<ScoreEvent> {
+    let play_group = play_groups.last().unwrap();
+    if !rules.is_run_across_reset {
+        return play_score(index, play_group);
+    }
+
+    let run_cards: Vec<crate::deck::Card> = play_groups
+        .iter()
+        .flat_map(|play_group| play_group.cards.iter().copied())
+        .collect();
+    score_play(index, play_group, &run_cards)
+}
+
+// Scores the PlayGroup with the straights found among the last of run_cards, which end with the
+// cards of the PlayGroup
+fn score_play(
+    index: u8,
+    play_group: &crate::PlayGroup,
+    run_cards: &[crate::deck::Card],
+) -> Vec<ScoreEvent> {
     let mut output = Vec::new();
 
     if play_group.total == 15 {
@@ -223,19 +280,26 @@ pub fn play_score(index: u8, play_group: &crate::PlayGroup) -> Vec<ScoreEvent> {
 
     // Start checking for straights when there are at least three cards
     // One point per card, between three and seven cards
-    if play_group.cards.len() >= 3 {
+    if run_cards.len() >= 3 {
         // So given n cards, first we check all n cards, then the n-1 last cards, then the n-2 last
         // cards, down until the last three cards -- the minimum length of the run -- and adds a
         // Straight ScoreEvent for the largest straight found. But because a run may only be seven
         // cards at longest, we first check min(n, 7), then min(n-1, 6), and so on.
-        let length_or_7 = cmp::min(play_group.cards.len(), 7);
+        let length_or_7 = cmp::min(run_cards.len(), 7);
         for index_offset in (3..length_or_7 + 1).rev() {
-            let start_position: usize = play_group.cards.len() - index_offset;
+            let start_position: usize = run_cards.len() - index_offset;
             let mut is_value_present = [false; 13];
 
-            // For every card in the PlayGroup's cards at or after the start position calculated
-            // above
-            for card in &play_group.cards[start_position..] {
+            // A joker has no rank in the play so no straight can include it
+            if run_cards[start_position..]
+                .iter()
+                .any(crate::deck::Card::is_joker)
+            {
+                continue;
+            }
+
+            // For every card at or after the start position calculated above
+            for card in &run_cards[start_position..] {
                 is_value_present[(crate::deck::return_value(*card) as usize) - 1] = true;
             }
 
@@ -270,8 +334,9 @@ pub fn play_score(index: u8, play_group: &crate::PlayGroup) -> Vec<ScoreEvent> {
     }
 
     // Check for tuples
-    // Only begin checking when there are at least two cards
-    if play_group.cards.len() >= 2 {
+    // Only begin checking when there are at least two cards, and a joker doesn't pair with another
+    // joker in the play
+    if play_group.cards.len() >= 2 && !play_group.cards.last().unwrap().is_joker() {
         // Check the last four cards for a quadruple, then the last three for a triple, then the
         // last two for a double; if there are less than four cards only check starting from that
         // number
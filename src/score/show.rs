@@ -0,0 +1,622 @@
+use super::ScoreEvent;
+use super::ScoreType;
+use super::ShowScoreType;
+use crate::deck;
+
+#[cfg(test)]
+mod test {
+    use super::score_hand;
+    use crate::util::return_card;
+
+    fn total(events: &[super::ScoreEvent]) -> u8 {
+        events.iter().map(|event| event.point_value).sum()
+    }
+
+    #[test]
+    fn check_perfect_hand() {
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('J', 'S'),
+        ];
+        let events = score_hand(0, &hand, return_card('5', 'S'), false);
+
+        assert_eq!(total(&events), 29);
+        assert!(events.contains(&super::ScoreEvent {
+            score_type: super::ScoreType::Show(super::ShowScoreType::Nobs(vec![
+                return_card('5', 'S'),
+                return_card('J', 'S'),
+            ])),
+            player_index: 0,
+            point_value: 1,
+        }));
+        assert!(events.contains(&super::ScoreEvent {
+            score_type: super::ScoreType::Show(super::ShowScoreType::Quadruple(vec![
+                return_card('5', 'H'),
+                return_card('5', 'D'),
+                return_card('5', 'C'),
+                return_card('5', 'S'),
+            ])),
+            player_index: 0,
+            point_value: 12,
+        }));
+    }
+
+    // Returns the Wild ScoreEvent recording that the joker was counted as the given card
+    fn wild(joker: crate::deck::Card, counted_as: crate::deck::Card) -> super::ScoreEvent {
+        super::ScoreEvent {
+            score_type: super::ScoreType::Show(super::ShowScoreType::Wild(vec![joker, counted_as])),
+            player_index: 0,
+            point_value: 0,
+        }
+    }
+
+    // A joker in the hand, as the starter or both is counted as whichever card makes the perfect
+    // hand
+    #[test]
+    fn check_jokers() {
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('X', 'H'),
+        ];
+        let events = score_hand(0, &hand, return_card('5', 'S'), false);
+        assert_eq!(total(&events), 29);
+        assert!(events.contains(&wild(return_card('X', 'H'), return_card('J', 'S'))));
+
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('J', 'S'),
+        ];
+        let events = score_hand(0, &hand, return_card('X', 'S'), false);
+        assert_eq!(total(&events), 29);
+        assert!(events.contains(&wild(return_card('X', 'S'), return_card('5', 'S'))));
+
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('X', 'H'),
+            return_card('5', 'C'),
+            return_card('X', 'S'),
+        ];
+        let events = score_hand(0, &hand, return_card('5', 'D'), true);
+        assert_eq!(total(&events), 29);
+        // The first joker takes the jack of diamonds as it comes before the five of spades
+        assert!(events.contains(&wild(return_card('X', 'H'), return_card('J', 'D'))));
+        assert!(events.contains(&wild(return_card('X', 'S'), return_card('5', 'S'))));
+    }
+
+    // A joker is never counted as a card already in the hand, and of the cards scoring the most
+    // the first in the order of the deck is chosen
+    #[test]
+    fn check_joker_assignment() {
+        let hand = vec![
+            return_card('2', 'H'),
+            return_card('4', 'D'),
+            return_card('6', 'C'),
+            return_card('X', 'H'),
+        ];
+        let events = score_hand(0, &hand, return_card('8', 'S'), false);
+        let counted_as: Vec<crate::deck::Card> = events
+            .iter()
+            .filter_map(|event| match &event.score_type {
+                super::ScoreType::Show(super::ShowScoreType::Wild(cards)) => Some(cards[1]),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(counted_as.len(), 1);
+        assert!(!hand.contains(&counted_as[0]));
+        assert_eq!(events, score_hand(0, &hand, return_card('8', 'S'), false));
+    }
+
+    // A four card flush counts in the crib when the rules allow it and a hand scoring nothing is a
+    // nineteen when the rules give a bonus for it
+    #[test]
+    fn check_scoring_rules() {
+        let mut rules = crate::settings::ScoringRules::default();
+        let crib = vec![
+            return_card('2', 'H'),
+            return_card('4', 'H'),
+            return_card('6', 'H'),
+            return_card('8', 'H'),
+        ];
+        let starter = return_card('K', 'S');
+        assert_eq!(
+            super::score_hand_with_rules(0, &crib, starter, true, &rules),
+            vec![]
+        );
+        rules.is_crib_four_flush = true;
+        assert_eq!(
+            total(&super::score_hand_with_rules(
+                0, &crib, starter, true, &rules
+            )),
+            4
+        );
+
+        rules.nineteen_bonus = 3;
+        let hand = vec![
+            return_card('2', 'H'),
+            return_card('4', 'D'),
+            return_card('6', 'C'),
+            return_card('8', 'S'),
+        ];
+        assert_eq!(
+            super::score_hand_with_rules(1, &hand, starter, false, &rules),
+            vec![super::ScoreEvent {
+                score_type: super::ScoreType::Show(super::ShowScoreType::Nineteen(vec![
+                    return_card('2', 'H'),
+                    return_card('4', 'D'),
+                    return_card('6', 'C'),
+                    return_card('8', 'S'),
+                    return_card('K', 'S'),
+                ])),
+                player_index: 1,
+                point_value: 3,
+            }]
+        );
+        // A hand scoring anything isn't a nineteen
+        assert_eq!(
+            total(&super::score_hand_with_rules(
+                1,
+                &crib,
+                return_card('5', 'S'),
+                true,
+                &rules
+            )),
+            11
+        );
+    }
+
+    #[test]
+    fn check_zero_hand() {
+        let hand = vec![
+            return_card('2', 'H'),
+            return_card('4', 'D'),
+            return_card('6', 'C'),
+            return_card('8', 'S'),
+        ];
+
+        assert_eq!(score_hand(1, &hand, return_card('K', 'H'), false), vec![]);
+    }
+
+    // A double run of three counts as two separate straights and a pair rather than three
+    // overlapping straights
+    #[test]
+    fn check_double_run() {
+        let hand = vec![
+            return_card('3', 'H'),
+            return_card('4', 'D'),
+            return_card('4', 'C'),
+            return_card('5', 'S'),
+        ];
+        let events = score_hand(0, &hand, return_card('K', 'H'), false);
+
+        let straights = events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event.score_type,
+                    super::ScoreType::Show(super::ShowScoreType::Straight(_))
+                )
+            })
+            .count();
+        assert_eq!(straights, 2);
+        // Two runs of three, the pair of fours, and the fifteen of the five and king
+        assert_eq!(total(&events), 3 + 3 + 2 + 2);
+    }
+
+    // A run of four contains two runs of three which must not be counted separately
+    #[test]
+    fn check_run_of_four_is_not_split() {
+        let hand = vec![
+            return_card('9', 'H'),
+            return_card('T', 'D'),
+            return_card('J', 'C'),
+            return_card('Q', 'S'),
+        ];
+        let events = score_hand(0, &hand, return_card('2', 'H'), false);
+
+        assert_eq!(
+            events,
+            vec![super::ScoreEvent {
+                score_type: super::ScoreType::Show(super::ShowScoreType::Straight(vec![
+                    return_card('9', 'H'),
+                    return_card('T', 'D'),
+                    return_card('J', 'C'),
+                    return_card('Q', 'S'),
+                ])),
+                player_index: 0,
+                point_value: 4,
+            }]
+        );
+    }
+
+    // A four card flush counts in the hand but not in the crib where all five cards must match
+    #[test]
+    fn check_flushes() {
+        let hand = vec![
+            return_card('2', 'H'),
+            return_card('4', 'H'),
+            return_card('6', 'H'),
+            return_card('8', 'H'),
+        ];
+
+        assert_eq!(
+            total(&score_hand(0, &hand, return_card('Q', 'S'), false)),
+            4
+        );
+        assert_eq!(total(&score_hand(0, &hand, return_card('Q', 'S'), true)), 0);
+        assert_eq!(
+            total(&score_hand(0, &hand, return_card('Q', 'H'), false)),
+            5
+        );
+        assert_eq!(total(&score_hand(0, &hand, return_card('Q', 'H'), true)), 5);
+    }
+
+    #[test]
+    fn check_known_totals() {
+        // 4-5-6 with a pair of sixes and the starter five: double run with fifteens
+        let hand = vec![
+            return_card('4', 'H'),
+            return_card('5', 'D'),
+            return_card('6', 'C'),
+            return_card('6', 'S'),
+        ];
+        assert_eq!(
+            total(&score_hand(0, &hand, return_card('5', 'H'), false)),
+            24
+        );
+
+        // 7-7-8-8 with a starter of nine: double double run
+        let hand = vec![
+            return_card('7', 'H'),
+            return_card('7', 'D'),
+            return_card('8', 'C'),
+            return_card('8', 'S'),
+        ];
+        assert_eq!(
+            total(&score_hand(0, &hand, return_card('9', 'H'), false)),
+            24
+        );
+
+        // Three fives and a ten with a starter of ten
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('T', 'S'),
+        ];
+        assert_eq!(
+            total(&score_hand(0, &hand, return_card('T', 'H'), false)),
+            22
+        );
+    }
+
+    // Hands of three and five cards are scored for the five and seven card variants
+    #[test]
+    fn check_other_hand_sizes() {
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('T', 'H'),
+            return_card('K', 'H'),
+        ];
+        // Two fifteens and a flush of the hand and the starter
+        assert_eq!(
+            total(&score_hand(0, &hand, return_card('A', 'H'), false)),
+            4 + 4
+        );
+
+        let hand = vec![
+            return_card('A', 'H'),
+            return_card('2', 'D'),
+            return_card('3', 'C'),
+            return_card('9', 'S'),
+            return_card('T', 'S'),
+        ];
+        // A-2-3 and 9-T-J are two separate runs of three
+        let events = score_hand(0, &hand, return_card('J', 'H'), false);
+        let straights: Vec<u8> = events
+            .iter()
+            .filter_map(|event| match event.score_type {
+                super::ScoreType::Show(super::ShowScoreType::Straight(_)) => {
+                    Some(event.point_value)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(straights, vec![3, 3]);
+    }
+}
+
+// Returns a vector of ScoreEvents that is the perfect scoring of a hand or crib in the show
+//
+// Score types that this function will handle: Fifteen, Pair, Triple, Quadruple, Straight,
+// FourFlush, FiveFlush, and Nobs
+//
+// index is the index of the player whose hand is being scored, hand is every card of the hand or
+// crib not including the starter, and is_crib is set when scoring a crib such that a flush only
+// counts when it includes the starter
+//
+// The cards of every ScoreEvent are sorted and the ScoreEvents themselves are sorted such that
+// two scorings of the same hand can be compared directly. Triples and quadruples are given as a
+// single ScoreEvent rather than as their pairs and each straight of a double or triple run is
+// given as its own ScoreEvent. FourFlush is used for a flush of only the cards in the hand and
+// FiveFlush for a flush which includes the starter as well, whatever the size of the hand.
+//
+// A joker in the hand or as the starter is wild and is counted as whichever card gives the hand its
+// highest total; see score_wild_hand
+pub fn score_hand(
+    index: u8,
+    hand: &[deck::Card],
+    starter: deck::Card,
+    is_crib: bool,
+) -> Vec<ScoreEvent> {
+    if starter.is_joker() || hand.iter().any(deck::Card::is_joker) {
+        return score_wild_hand(index, hand, starter, is_crib);
+    }
+    score_natural_hand(index, hand, starter, is_crib)
+}
+
+// Returns the perfect scoring of a hand or crib under the given ScoringRules: a four card flush
+// counts in the crib when the rules allow it, and a hand or crib which would score nothing scores a
+// Nineteen of the hand and starter when the rules give a bonus for it
+pub fn score_hand_with_rules(
+    index: u8,
+    hand: &[deck::Card],
+    starter: deck::Card,
+    is_crib: bool,
+    rules: &crate::settings::ScoringRules,
+) -> Vec<ScoreEvent> {
+    let mut output = score_hand(index, hand, starter, is_crib && !rules.is_crib_four_flush);
+
+    if rules.nineteen_bonus > 0
+        && output
+            .iter()
+            .all(|score_event| score_event.point_value == 0)
+    {
+        let mut cards = hand.to_vec();
+        cards.push(starter);
+        cards.sort();
+        output.push(ScoreEvent {
+            score_type: ScoreType::Show(ShowScoreType::Nineteen(cards)),
+            player_index: index,
+            point_value: rules.nineteen_bonus,
+        });
+        output.sort();
+    }
+    output
+}
+
+// Scores a hand with jokers by trying every card each joker could be counted as and keeping the
+// highest scoring, with a Wild ScoreEvent for each joker recording the card it was counted as. A
+// joker may be counted as any card that isn't already in the hand or the starter, and of
+// assignments which score the same the first in the order of the deck is kept
+fn score_wild_hand(
+    index: u8,
+    hand: &[deck::Card],
+    starter: deck::Card,
+    is_crib: bool,
+) -> Vec<ScoreEvent> {
+    let mut cards = hand.to_vec();
+    cards.push(starter);
+    let joker_positions: Vec<usize> = (0..cards.len())
+        .filter(|position| cards[*position].is_joker())
+        .collect();
+
+    let mut best: Option<(u32, Vec<ScoreEvent>, Vec<deck::Card>)> = None;
+    assign_jokers(
+        &joker_positions,
+        &deck::standard_cards(),
+        &mut cards.clone(),
+        &mut |assigned| {
+            let (assigned_starter, assigned_hand) = assigned.split_last().unwrap();
+            let score_events = score_natural_hand(index, assigned_hand, *assigned_starter, is_crib);
+            let total: u32 = score_events
+                .iter()
+                .map(|score_event| u32::from(score_event.point_value))
+                .sum();
+            if best
+                .as_ref()
+                .map_or(true, |(best_total, _, _)| total > *best_total)
+            {
+                best = Some((total, score_events, assigned.to_vec()));
+            }
+        },
+    );
+
+    let (_, mut output, assigned) = best.unwrap();
+    for position in joker_positions {
+        output.push(ScoreEvent {
+            score_type: ScoreType::Show(ShowScoreType::Wild(vec![
+                cards[position],
+                assigned[position],
+            ])),
+            player_index: index,
+            point_value: 0,
+        });
+    }
+    output.sort();
+    output
+}
+
+// Calls visit with every way of replacing the cards at the given positions with distinct cards
+// that aren't already among the cards
+fn assign_jokers(
+    positions: &[usize],
+    candidates: &[deck::Card],
+    cards: &mut Vec<deck::Card>,
+    visit: &mut dyn FnMut(&[deck::Card]),
+) {
+    let (position, rest) = match positions.split_first() {
+        Some(split) => split,
+        None => {
+            visit(cards);
+            return;
+        }
+    };
+
+    let joker = cards[*position];
+    for candidate in candidates {
+        if cards.contains(candidate) {
+            continue;
+        }
+        cards[*position] = *candidate;
+        assign_jokers(rest, candidates, cards, visit);
+        cards[*position] = joker;
+    }
+}
+
+// Scores a hand without any jokers
+fn score_natural_hand(
+    index: u8,
+    hand: &[deck::Card],
+    starter: deck::Card,
+    is_crib: bool,
+) -> Vec<ScoreEvent> {
+    let mut output = Vec::new();
+
+    let mut all_cards: Vec<deck::Card> = hand.to_vec();
+    all_cards.push(starter);
+    all_cards.sort();
+
+    // Check every combination of two or more cards for fifteens using a bit mask where bit n
+    // represents whether the nth card is included
+    for mask in 1u32..(1 << all_cards.len()) {
+        if mask.count_ones() < 2 {
+            continue;
+        }
+
+        let mut sum = 0;
+        for (position, card) in all_cards.iter().enumerate() {
+            if mask & (1 << position) != 0 {
+                sum += deck::return_play_value(*card);
+            }
+        }
+
+        if sum == 15 {
+            output.push(ScoreEvent {
+                score_type: ScoreType::Show(ShowScoreType::Fifteen(select_cards(&all_cards, mask))),
+                player_index: index,
+                point_value: 2,
+            });
+        }
+    }
+
+    // Group the cards by value such as to find tuples and straights; cards_of_value[0] holds
+    // every ace and cards_of_value[12] every king
+    let mut cards_of_value: Vec<Vec<deck::Card>> = vec![Vec::new(); 13];
+    for card in &all_cards {
+        cards_of_value[deck::return_value(*card) as usize - 1].push(*card);
+    }
+
+    // Check for tuples; a triple or quadruple is given as a single ScoreEvent instead of the pairs
+    // it contains
+    for cards in &cards_of_value {
+        let score_type = match cards.len() {
+            2 => ShowScoreType::Pair(cards.clone()),
+            3 => ShowScoreType::Triple(cards.clone()),
+            4 => ShowScoreType::Quadruple(cards.clone()),
+            _ => continue,
+        };
+        let point_value = match cards.len() {
+            2 => 2,
+            3 => 6,
+            _ => 12,
+        };
+        output.push(ScoreEvent {
+            score_type: ScoreType::Show(score_type),
+            player_index: index,
+            point_value,
+        });
+    }
+
+    // Check for straights; each continuous section of three or more values present is a run and
+    // every way of picking one card of each value in that section is its own straight
+    let mut start = 0;
+    while start < 13 {
+        if cards_of_value[start].is_empty() {
+            start += 1;
+            continue;
+        }
+
+        let mut end = start;
+        while end < 13 && !cards_of_value[end].is_empty() {
+            end += 1;
+        }
+
+        if end - start >= 3 {
+            let mut straights: Vec<Vec<deck::Card>> = vec![Vec::new()];
+            for cards in &cards_of_value[start..end] {
+                let mut extended = Vec::with_capacity(straights.len() * cards.len());
+                for straight in &straights {
+                    for card in cards {
+                        let mut next = straight.clone();
+                        next.push(*card);
+                        extended.push(next);
+                    }
+                }
+                straights = extended;
+            }
+
+            for mut straight in straights {
+                straight.sort();
+                output.push(ScoreEvent {
+                    point_value: straight.len() as u8,
+                    score_type: ScoreType::Show(ShowScoreType::Straight(straight)),
+                    player_index: index,
+                });
+            }
+        }
+
+        start = end;
+    }
+
+    // Check for flushes; every card in the hand must share a suit and in the crib the starter must
+    // match as well
+    if !hand.is_empty() && hand.iter().all(|card| card.suit == hand[0].suit) {
+        if starter.suit == hand[0].suit {
+            output.push(ScoreEvent {
+                point_value: all_cards.len() as u8,
+                score_type: ScoreType::Show(ShowScoreType::FiveFlush(all_cards.clone())),
+                player_index: index,
+            });
+        } else if !is_crib {
+            let mut flush = hand.to_vec();
+            flush.sort();
+            output.push(ScoreEvent {
+                point_value: flush.len() as u8,
+                score_type: ScoreType::Show(ShowScoreType::FourFlush(flush)),
+                player_index: index,
+            });
+        }
+    }
+
+    // Check for nobs, the jack in hand matching the suit of the starter
+    for card in hand {
+        if card.value == deck::CardValue::Jack && card.suit == starter.suit {
+            let mut nobs = vec![*card, starter];
+            nobs.sort();
+            output.push(ScoreEvent {
+                score_type: ScoreType::Show(ShowScoreType::Nobs(nobs)),
+                player_index: index,
+                point_value: 1,
+            });
+        }
+    }
+
+    output.sort();
+    output
+}
+
+// Returns the cards of a sorted vector whose positions are set in the given bit mask
+fn select_cards(cards: &[deck::Card], mask: u32) -> Vec<deck::Card> {
+    cards
+        .iter()
+        .enumerate()
+        .filter(|(position, _)| mask & (1 << position) != 0)
+        .map(|(_, card)| *card)
+        .collect()
+}
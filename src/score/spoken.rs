@@ -0,0 +1,390 @@
+use super::PlayScoreType;
+use super::ScoreEvent;
+use super::ScoreType;
+use super::ShowScoreType;
+use crate::deck;
+
+// Turns ScoreEvents into words: the traditional spoken count of a hand or play, such as "fifteen
+// two, fifteen four, a pair is six, and a run of three is nine", and a description of each event
+// with the cards making it up, for tutorials and screen readers
+
+#[cfg(test)]
+mod test {
+    use super::spoken_count;
+    use crate::score::show::score_hand;
+    use crate::util::return_card;
+
+    fn count(hand: [(char, char); 4], starter: (char, char)) -> String {
+        let hand: Vec<crate::deck::Card> = hand
+            .iter()
+            .map(|(value, suit)| return_card(*value, *suit))
+            .collect();
+        spoken_count(&score_hand(
+            0,
+            &hand,
+            return_card(starter.0, starter.1),
+            false,
+        ))
+    }
+
+    #[test]
+    fn fifteens_pair_run_and_nobs() {
+        assert_eq!(
+            count([('5', 'H'), ('T', 'D'), ('J', 'S'), ('Q', 'C')], ('5', 'S')),
+            "fifteen two, fifteen four, fifteen six, fifteen eight, fifteen ten, fifteen twelve, \
+             a pair is fourteen, and a run of three is seventeen, and nobs is eighteen"
+        );
+    }
+
+    #[test]
+    fn double_runs_are_grouped() {
+        assert_eq!(
+            count([('3', 'H'), ('4', 'D'), ('4', 'C'), ('5', 'S')], ('K', 'H')),
+            "fifteen two, a double run of three is ten"
+        );
+        assert_eq!(
+            count([('3', 'H'), ('4', 'D'), ('5', 'C'), ('6', 'S')], ('6', 'H')),
+            "fifteen two, fifteen four, fifteen six, a double run of four is sixteen"
+        );
+        assert_eq!(
+            count([('3', 'H'), ('3', 'D'), ('4', 'C'), ('5', 'S')], ('5', 'H')),
+            "fifteen two, fifteen four, a double double run is twenty"
+        );
+        assert_eq!(
+            count([('9', 'H'), ('9', 'D'), ('9', 'C'), ('T', 'S')], ('J', 'H')),
+            "a triple run is fifteen"
+        );
+    }
+
+    // A pair that isn't part of the run is still called on its own
+    #[test]
+    fn pair_outside_run() {
+        assert_eq!(
+            count([('2', 'H'), ('3', 'D'), ('4', 'C'), ('K', 'S')], ('K', 'H')),
+            "fifteen two, fifteen four, a pair is six, and a run of three is nine"
+        );
+    }
+
+    #[test]
+    fn flushes() {
+        assert_eq!(
+            count([('2', 'H'), ('4', 'H'), ('6', 'H'), ('8', 'H')], ('Q', 'S')),
+            "a flush is four"
+        );
+        assert_eq!(
+            count([('2', 'H'), ('4', 'H'), ('6', 'H'), ('8', 'H')], ('Q', 'H')),
+            "a five card flush is five"
+        );
+    }
+
+    #[test]
+    fn no_points() {
+        assert_eq!(
+            count([('2', 'H'), ('4', 'D'), ('6', 'C'), ('8', 'S')], ('K', 'H')),
+            "no points"
+        );
+    }
+
+    #[test]
+    fn play() {
+        let play_group = crate::PlayGroup {
+            total: 15,
+            cards: vec![
+                return_card('4', 'H'),
+                return_card('5', 'D'),
+                return_card('6', 'C'),
+            ],
+        };
+        assert_eq!(
+            spoken_count(&crate::score::play::play_score(0, &play_group)),
+            "fifteen two, a run of three is five"
+        );
+    }
+
+    #[test]
+    fn describe() {
+        let events = score_hand(
+            0,
+            &[
+                return_card('5', 'H'),
+                return_card('J', 'S'),
+                return_card('2', 'C'),
+                return_card('8', 'D'),
+            ],
+            return_card('A', 'S'),
+            false,
+        );
+        let descriptions: Vec<String> = events.iter().map(super::describe).collect();
+        assert_eq!(
+            descriptions,
+            vec![
+                "Fifteen for 2: two of clubs, five of hearts, eight of diamonds",
+                "Fifteen for 2: five of hearts, jack of spades",
+                "Nobs for 1: ace of spades, jack of spades",
+            ]
+        );
+    }
+}
+
+// Returns the traditional spoken count of the given ScoreEvents with the running total after each
+// part. Straights with the same values are called together with the tuples within them as a
+// double, triple or double double run
+pub fn spoken_count(score_events: &[ScoreEvent]) -> String {
+    let mut phrases: Vec<String> = Vec::new();
+    let mut total: u32 = 0;
+    let mut has_named_part = false;
+
+    for part in group_runs(score_events) {
+        total += part.points;
+        let phrase = match part.name {
+            None => format!("fifteen {}", number_name(total)),
+            // Every named part after the first is joined with "and"
+            Some(name) if has_named_part => format!("and {} is {}", name, number_name(total)),
+            Some(name) => {
+                has_named_part = true;
+                format!("{} is {}", name, number_name(total))
+            }
+        };
+        phrases.push(phrase);
+    }
+
+    if phrases.is_empty() {
+        String::from("no points")
+    } else {
+        phrases.join(", ")
+    }
+}
+
+// Returns a description of a single ScoreEvent with its points and the cards making it up
+pub fn describe(score_event: &ScoreEvent) -> String {
+    let (name, cards): (&str, &[deck::Card]) = match &score_event.score_type {
+        ScoreType::Show(ShowScoreType::Fifteen(cards)) => ("Fifteen", cards),
+        ScoreType::Show(ShowScoreType::Pair(cards)) => ("Pair", cards),
+        ScoreType::Show(ShowScoreType::Triple(cards)) => ("Three of a kind", cards),
+        ScoreType::Show(ShowScoreType::Quadruple(cards)) => ("Four of a kind", cards),
+        ScoreType::Show(ShowScoreType::Straight(cards)) => ("Run", cards),
+        ScoreType::Show(ShowScoreType::FourFlush(cards)) => ("Flush", cards),
+        ScoreType::Show(ShowScoreType::FiveFlush(cards)) => ("Five card flush", cards),
+        ScoreType::Show(ShowScoreType::Nobs(cards)) => ("Nobs", cards),
+        ScoreType::Show(ShowScoreType::Nineteen(cards)) => ("Nineteen", cards),
+        ScoreType::Show(ShowScoreType::Wild(cards)) => {
+            let joker = card_name(cards[0]);
+            return format!(
+                "{}{} counted as the {}",
+                joker[..1].to_uppercase(),
+                &joker[1..],
+                card_name(cards[1])
+            );
+        }
+        ScoreType::Play(play_score_type) => {
+            return format!(
+                "{} for {}",
+                play_name(play_score_type),
+                score_event.point_value
+            )
+        }
+    };
+
+    let card_names: Vec<String> = cards.iter().map(|card| card_name(*card)).collect();
+    format!(
+        "{} for {}: {}",
+        name,
+        score_event.point_value,
+        card_names.join(", ")
+    )
+}
+
+// Returns the name of a card such as "five of hearts"
+pub fn card_name(card: deck::Card) -> String {
+    if card.is_joker() {
+        return match card.suit {
+            deck::CardSuit::Hearts | deck::CardSuit::Diamonds => String::from("red joker"),
+            deck::CardSuit::Clubs | deck::CardSuit::Spades => String::from("black joker"),
+        };
+    }
+
+    let value = match card.value {
+        deck::CardValue::Ace => "ace",
+        deck::CardValue::Two => "two",
+        deck::CardValue::Three => "three",
+        deck::CardValue::Four => "four",
+        deck::CardValue::Five => "five",
+        deck::CardValue::Six => "six",
+        deck::CardValue::Seven => "seven",
+        deck::CardValue::Eight => "eight",
+        deck::CardValue::Nine => "nine",
+        deck::CardValue::Ten => "ten",
+        deck::CardValue::Jack => "jack",
+        deck::CardValue::Queen => "queen",
+        deck::CardValue::King => "king",
+        deck::CardValue::Joker => "joker",
+    };
+    let suit = match card.suit {
+        deck::CardSuit::Hearts => "hearts",
+        deck::CardSuit::Diamonds => "diamonds",
+        deck::CardSuit::Clubs => "clubs",
+        deck::CardSuit::Spades => "spades",
+    };
+    format!("{} of {}", value, suit)
+}
+
+// One part of a spoken count; fifteens have no name as they're called by the running total alone
+struct Part {
+    name: Option<String>,
+    points: u32,
+}
+
+// Returns the parts of the count in the order of the ScoreEvents, with each set of straights
+// sharing the same values and the tuples among their cards called as a single part in place of the
+// first of those straights
+fn group_runs(score_events: &[ScoreEvent]) -> Vec<Part> {
+    // The values of each show straight; straights with the same values belong to the same run
+    let run_values = |score_event: &ScoreEvent| match &score_event.score_type {
+        ScoreType::Show(ShowScoreType::Straight(cards)) => {
+            let mut values: Vec<deck::CardValue> = cards.iter().map(|card| card.value).collect();
+            values.dedup();
+            Some(values)
+        }
+        _ => None,
+    };
+    let tuple_value = |score_event: &ScoreEvent| match &score_event.score_type {
+        ScoreType::Show(ShowScoreType::Pair(cards))
+        | ScoreType::Show(ShowScoreType::Triple(cards))
+        | ScoreType::Show(ShowScoreType::Quadruple(cards)) => Some(cards[0].value),
+        _ => None,
+    };
+
+    let mut parts = Vec::new();
+    let mut is_called = vec![false; score_events.len()];
+    for (position, score_event) in score_events.iter().enumerate() {
+        if is_called[position] {
+            continue;
+        }
+
+        let values = match run_values(score_event) {
+            Some(values) => values,
+            None => continue,
+        };
+        let mut num_straights = 0;
+        let mut points = 0;
+        for (other_position, other) in score_events.iter().enumerate() {
+            if run_values(other).as_ref() == Some(&values) {
+                num_straights += 1;
+                points += u32::from(other.point_value);
+                is_called[other_position] = true;
+            }
+        }
+        if num_straights > 1 {
+            for (other_position, other) in score_events.iter().enumerate() {
+                if let Some(value) = tuple_value(other) {
+                    if values.contains(&value) {
+                        points += u32::from(other.point_value);
+                        is_called[other_position] = true;
+                    }
+                }
+            }
+        }
+
+        let length = number_name(values.len() as u32);
+        let name = match num_straights {
+            1 => format!("a run of {}", length),
+            2 => format!("a double run of {}", length),
+            3 => String::from("a triple run"),
+            _ => String::from("a double double run"),
+        };
+        parts.push((position, name, points));
+    }
+
+    // Straights and the tuples within them are called once at the position of their first straight
+    let mut output = Vec::new();
+    for (position, score_event) in score_events.iter().enumerate() {
+        if let Some((_, name, points)) = parts.iter().find(|part| part.0 == position) {
+            output.push(Part {
+                name: Some(name.clone()),
+                points: *points,
+            });
+        } else if !is_called[position] {
+            let name = match &score_event.score_type {
+                ScoreType::Show(ShowScoreType::Fifteen(_))
+                | ScoreType::Play(PlayScoreType::Fifteen) => None,
+                ScoreType::Show(ShowScoreType::Pair(_)) => Some(String::from("a pair")),
+                ScoreType::Show(ShowScoreType::Triple(_)) => Some(String::from("three of a kind")),
+                ScoreType::Show(ShowScoreType::Quadruple(_)) => {
+                    Some(String::from("four of a kind"))
+                }
+                // Every straight has already been called as part of its run
+                ScoreType::Show(ShowScoreType::Straight(_)) => continue,
+                ScoreType::Show(ShowScoreType::FourFlush(_)) => Some(String::from("a flush")),
+                ScoreType::Show(ShowScoreType::FiveFlush(_)) => {
+                    Some(String::from("a five card flush"))
+                }
+                ScoreType::Show(ShowScoreType::Nobs(_)) => Some(String::from("nobs")),
+                ScoreType::Show(ShowScoreType::Nineteen(_)) => Some(String::from("nineteen")),
+                // What a joker counted as isn't called as it's worth nothing itself
+                ScoreType::Show(ShowScoreType::Wild(_)) => continue,
+                ScoreType::Play(play_score_type) => Some(play_name(play_score_type).to_lowercase()),
+            };
+            output.push(Part {
+                name,
+                points: u32::from(score_event.point_value),
+            });
+        }
+    }
+    output
+}
+
+// Returns the name of a scoring event of the play
+fn play_name(play_score_type: &PlayScoreType) -> String {
+    match play_score_type {
+        PlayScoreType::Nibs => String::from("His heels"),
+        PlayScoreType::Pair => String::from("A pair"),
+        PlayScoreType::Triple => String::from("Three of a kind"),
+        PlayScoreType::Quadruple => String::from("Four of a kind"),
+        PlayScoreType::Straight(length) => {
+            format!("A run of {}", number_name(u32::from(*length)))
+        }
+        PlayScoreType::Fifteen => String::from("Fifteen"),
+        PlayScoreType::ThirtyOne => String::from("Thirty-one"),
+        PlayScoreType::LastCard => String::from("One for last"),
+    }
+}
+
+// Returns a number in words, such as "twenty-nine"
+fn number_name(number: u32) -> String {
+    const ONES: [&str; 20] = [
+        "zero",
+        "one",
+        "two",
+        "three",
+        "four",
+        "five",
+        "six",
+        "seven",
+        "eight",
+        "nine",
+        "ten",
+        "eleven",
+        "twelve",
+        "thirteen",
+        "fourteen",
+        "fifteen",
+        "sixteen",
+        "seventeen",
+        "eighteen",
+        "nineteen",
+    ];
+    const TENS: [&str; 10] = [
+        "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+    ];
+
+    match number {
+        0..=19 => String::from(ONES[number as usize]),
+        20..=99 if number % 10 == 0 => String::from(TENS[number as usize / 10]),
+        20..=99 => format!(
+            "{}-{}",
+            TENS[number as usize / 10],
+            ONES[number as usize % 10]
+        ),
+        _ => number.to_string(),
+    }
+}
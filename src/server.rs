@@ -0,0 +1,623 @@
+use crate::game_process_return;
+use crate::resync;
+use crate::view;
+use crate::{Game, GameEvent};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+// Hosts any number of games over plain TCP, each connection sending and receiving one JSON object
+// per line
+//
+// A client creates a game with {"type":"create"} and becomes its host; the host is the only client
+// which may send the GameSetup event, after which clients take seats with
+// {"type":"join","game_id":N,"seat":N}. A seated client sends events for the game with
+// {"type":"event","game_id":N,"event":EVENT} where EVENT is a GameEvent as serde writes it, such as
+// "Confirmation" or {"DiscardSelection":[[0,1],[]]}, and which is only accepted from the seats
+// allowed to send it by Game::process_event_from. Every accepted event is answered with an
+// accepted message and followed by a state message to every seated client holding the game as seen
+// from their seat; a rejected event is answered with the game_process_return::Error. A client
+// leaves a game with {"type":"leave","game_id":N} or by disconnecting, which frees their seat for
+// another client and tells the rest of the table. Every state message holds the sequence number of
+// the last event accepted, and a client which rejoins its seat may ask for what it missed with
+// {"type":"resync","game_id":N,"sequence":N}
+//
+// Messages to a client are queued and written by a thread of its own so a slow client never holds
+// up the rest of the server; a client which can't be written to within WRITE_TIMEOUT is
+// disconnected
+
+#[cfg(test)]
+mod test {
+    use crate::settings::{GameSettings, RuleVariant, ScoringRules, VictorDealerOption};
+    use crate::GameEvent;
+    use serde_json::{json, Value};
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+    use std::thread;
+
+    struct Client {
+        reader: BufReader<TcpStream>,
+        writer: TcpStream,
+    }
+
+    impl Client {
+        fn connect(address: std::net::SocketAddr) -> Client {
+            let writer = TcpStream::connect(address).unwrap();
+            Client {
+                reader: BufReader::new(writer.try_clone().unwrap()),
+                writer,
+            }
+        }
+
+        fn send(&mut self, request: Value) {
+            writeln!(self.writer, "{}", request).unwrap();
+        }
+
+        fn receive(&mut self) -> Value {
+            let mut line = String::new();
+            self.reader.read_line(&mut line).unwrap();
+            serde_json::from_str(&line).unwrap()
+        }
+
+        fn request(&mut self, request: Value) -> Value {
+            self.send(request);
+            self.receive()
+        }
+    }
+
+    fn start_server() -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || super::serve(listener));
+        address
+    }
+
+    fn setup_event() -> Value {
+        serde_json::to_value(GameEvent::GameSetup(GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }))
+        .unwrap()
+    }
+
+    // Creates and sets up a two player game with a client in each seat
+    fn set_up_table(address: std::net::SocketAddr) -> (u64, Client, Client) {
+        let mut host = Client::connect(address);
+        let game_id = host.request(json!({"type": "create"}))["game_id"]
+            .as_u64()
+            .unwrap();
+        let reply =
+            host.request(json!({"type": "event", "game_id": game_id, "event": setup_event()}));
+        assert_eq!(reply["type"], "accepted");
+
+        let mut other = Client::connect(address);
+        for (client, seat) in [(&mut host, 0), (&mut other, 1)] {
+            let reply = client.request(json!({"type": "join", "game_id": game_id, "seat": seat}));
+            assert_eq!(
+                reply,
+                json!({"type": "joined", "game_id": game_id, "seat": seat})
+            );
+            assert_eq!(client.receive()["type"], "state");
+        }
+        (game_id, host, other)
+    }
+
+    #[test]
+    fn seats_receive_their_own_view() {
+        let address = start_server();
+        let (game_id, mut host, mut other) = set_up_table(address);
+
+        // A second game on the same server is independent of the first
+        let mut stranger = Client::connect(address);
+        let reply = stranger.request(json!({"type": "create"}));
+        assert_ne!(reply["game_id"], game_id);
+
+        let mut state = json!({"view": {"state": "CutInitial"}});
+        while state["view"]["state"] != "Discard" {
+            // Only the dealer may deal but either player may cut for the deal or sort
+            let sender = if state["view"]["state"] == "Deal" && state["view"]["index_dealer"] == 1 {
+                &mut other
+            } else {
+                &mut host
+            };
+            let reply = sender
+                .request(json!({"type": "event", "game_id": game_id, "event": "Confirmation"}));
+            assert_eq!(reply["type"], "accepted");
+            state = host.receive();
+            assert_eq!(other.receive()["view"]["state"], state["view"]["state"]);
+        }
+        assert_eq!(state["view"]["seat"], 0);
+        assert_eq!(
+            state["view"]["players"][0]["hand"]
+                .as_array()
+                .unwrap()
+                .len(),
+            6
+        );
+        assert_eq!(state["view"]["players"][1]["hand"], Value::Null);
+
+        // Each seat discards for itself and the crib is laid away once both have
+        let event = json!({"DiscardSelection": [[0, 1], []]});
+        let reply = host.request(json!({"type": "event", "game_id": game_id, "event": event}));
+        assert_eq!(reply["success"], "DiscardHeld");
+        assert_eq!(host.receive()["view"]["crib_size"], 0);
+        assert_eq!(other.receive()["view"]["crib_size"], 0);
+
+        let event = json!({"DiscardSelection": [[0, 1], [0, 1]]});
+        let reply = other.request(json!({"type": "event", "game_id": game_id, "event": event}));
+        assert_eq!(reply["type"], "rejected");
+        assert_eq!(reply["error"], "SeatNotAllowed");
+        let event = json!({"DiscardSelection": [[], [0, 1]]});
+        let reply = other.request(json!({"type": "event", "game_id": game_id, "event": event}));
+        assert_eq!(reply["type"], "accepted");
+        assert_eq!(reply["success"], "Discard");
+        let state = other.receive();
+        assert_eq!(state["view"]["seat"], 1);
+        assert_eq!(
+            state["view"]["players"][1]["hand"]
+                .as_array()
+                .unwrap()
+                .len(),
+            4
+        );
+        assert_eq!(state["view"]["players"][0]["hand"], Value::Null);
+        assert_eq!(state["view"]["crib_size"], 4);
+        assert_eq!(host.receive()["view"]["seat"], 0);
+    }
+
+    #[test]
+    fn errors_are_replied_to_the_sender() {
+        let address = start_server();
+        let (game_id, mut host, mut other) = set_up_table(address);
+
+        let reply = other.request(json!({"type": "join", "game_id": 99, "seat": 0}));
+        assert_eq!(reply["type"], "error");
+        assert_eq!(reply["error"], "NoSuchGame");
+
+        let mut stranger = Client::connect(address);
+        let reply = stranger.request(json!({"type": "join", "game_id": game_id, "seat": 1}));
+        assert_eq!(reply["error"], "SeatIsTaken");
+        let reply = stranger.request(json!({"type": "join", "game_id": game_id, "seat": 2}));
+        assert_eq!(reply["error"], "NoSuchSeat");
+        let reply =
+            stranger.request(json!({"type": "event", "game_id": game_id, "event": "Confirmation"}));
+        assert_eq!(reply["error"], "NotSeated");
+        let reply =
+            other.request(json!({"type": "event", "game_id": game_id, "event": setup_event()}));
+        assert_eq!(reply["error"], "NotTheHost");
+        let reply = stranger.request(json!({"type": "dance"}));
+        assert_eq!(reply["error"], "MalformedRequest");
+
+        // Errors from the game are passed on as they are returned by Game::process_event_from
+        let reply = host.request(json!({"type": "event", "game_id": game_id, "event": "Denial"}));
+        assert_eq!(reply["type"], "rejected");
+        assert_eq!(reply["error"]["ExpectedEvent"], json!(["Confirmation"]));
+    }
+
+    #[test]
+    fn disconnects_free_the_seat() {
+        let address = start_server();
+        let (game_id, host, mut other) = set_up_table(address);
+
+        drop(host);
+        assert_eq!(
+            other.receive(),
+            json!({"type": "left", "game_id": game_id, "seat": 0})
+        );
+
+        let mut newcomer = Client::connect(address);
+        let reply = newcomer.request(json!({"type": "join", "game_id": game_id, "seat": 0}));
+        assert_eq!(reply["type"], "joined");
+        assert_eq!(newcomer.receive()["view"]["seat"], 0);
+
+        let reply = other.request(json!({"type": "leave", "game_id": game_id}));
+        assert_eq!(
+            reply,
+            json!({"type": "left", "game_id": game_id, "seat": 1})
+        );
+        assert_eq!(newcomer.receive(), reply);
+    }
+
+    // A thread which panics while holding the server doesn't take down the other clients
+    #[test]
+    fn poisoned_server_is_still_served() {
+        let server = std::sync::Arc::new(std::sync::Mutex::new(super::Server::default()));
+        let poisoner = std::sync::Arc::clone(&server);
+        let _ = thread::spawn(move || {
+            let _server = poisoner.lock().unwrap();
+            panic!("poisoning the server");
+        })
+        .join();
+        assert!(server.is_poisoned());
+        super::lock(&server).next_game_id = 5;
+        assert_eq!(super::lock(&server).next_game_id, 5);
+    }
+
+    #[test]
+    fn rejoining_client_resyncs() {
+        let address = start_server();
+        let (game_id, mut host, mut other) = set_up_table(address);
+        let reply = other.request(json!({"type": "resync", "game_id": game_id, "sequence": 1}));
+        assert_eq!(reply["type"], "resync");
+        assert_eq!(reply["resync"]["missed"], json!([]));
+        assert_eq!(reply["resync"]["view"]["seat"], 1);
+        assert_eq!(reply["resync"]["view"]["sequence"], 1);
+
+        drop(other);
+        assert_eq!(host.receive()["type"], "left");
+        let reply =
+            host.request(json!({"type": "event", "game_id": game_id, "event": "Confirmation"}));
+        assert_eq!(reply["type"], "accepted");
+        assert_eq!(host.receive()["view"]["sequence"], 2);
+
+        let mut other = Client::connect(address);
+        other.request(json!({"type": "join", "game_id": game_id, "seat": 1}));
+        other.receive();
+        let reply = other.request(json!({"type": "resync", "game_id": game_id, "sequence": 1}));
+        assert_eq!(
+            reply["resync"]["missed"],
+            json!([{"sequence": 2, "seat": 0, "event": "Confirmation"}])
+        );
+    }
+}
+
+// A message from a client
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    Create,
+    Join { game_id: u64, seat: u8 },
+    Event { game_id: u64, event: GameEvent },
+    Leave { game_id: u64 },
+    // Asks for what was missed since the last sequence number seen; see resync::Resync
+    Resync { game_id: u64, sequence: u64 },
+}
+
+// A message to a client, either in reply to a request or pushed when the game changes
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Created {
+        game_id: u64,
+    },
+    Joined {
+        game_id: u64,
+        seat: u8,
+    },
+    Accepted {
+        game_id: u64,
+        success: game_process_return::Success,
+    },
+    Rejected {
+        game_id: u64,
+        error: game_process_return::Error,
+    },
+    State {
+        game_id: u64,
+        view: Box<view::SeatView>,
+    },
+    // Sent to the client leaving and to the rest of the table; seat is None when an unseated host
+    // leaves
+    Left {
+        game_id: u64,
+        seat: Option<u8>,
+    },
+    Resync {
+        game_id: u64,
+        resync: resync::Resync,
+    },
+    Error {
+        error: ServerError,
+        message: String,
+    },
+}
+
+// Errors with a request itself rather than with the event it carries
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum ServerError {
+    MalformedRequest,
+    NoSuchGame,
+    GameIsNotSetUp,
+    NoSuchSeat,
+    SeatIsTaken,
+    AlreadySeated,
+    NotSeated,
+    NotTheHost,
+}
+
+type ClientId = u64;
+
+// How long a write to a client may block before the client is disconnected
+const WRITE_TIMEOUT: Duration = Duration::from_secs(10);
+
+struct Table {
+    game: Game,
+    host: Option<ClientId>,
+    // The client in each seat by player index, empty until the game is set up
+    seats: Vec<Option<ClientId>>,
+}
+
+impl Table {
+    fn seat_of(&self, client: ClientId) -> Option<u8> {
+        self.seats
+            .iter()
+            .position(|seated| *seated == Some(client))
+            .map(|seat| seat as u8)
+    }
+
+    fn is_abandoned(&self) -> bool {
+        self.host.is_none() && self.seats.iter().all(Option::is_none)
+    }
+}
+
+#[derive(Default)]
+struct Server {
+    tables: HashMap<u64, Table>,
+    // The queue of lines to be written to each connection such that state can be pushed to any
+    // client without waiting on the connection
+    clients: HashMap<ClientId, Sender<String>>,
+    next_game_id: u64,
+    next_client_id: ClientId,
+}
+
+impl Server {
+    // Queues one message for a client; a client whose writing thread has stopped is dropped here
+    // and removed from its games when its reading thread sees the connection close
+    fn send(&mut self, client: ClientId, response: &Response) {
+        if let Some(lines) = self.clients.get(&client) {
+            let line = serde_json::to_string(response).expect("responses always serialize");
+            if lines.send(line).is_err() {
+                self.clients.remove(&client);
+            }
+        }
+    }
+
+    // Pushes the game as seen from each seat to the client sitting there
+    fn push_state(&mut self, game_id: u64) {
+        let views: Vec<(ClientId, view::SeatView)> = match self.tables.get(&game_id) {
+            Some(table) => table
+                .seats
+                .iter()
+                .enumerate()
+                .filter_map(|(seat, client)| {
+                    client.map(|client| (client, table.game.view(seat as u8)))
+                })
+                .collect(),
+            None => return,
+        };
+        for (client, view) in views {
+            self.send(
+                client,
+                &Response::State {
+                    game_id,
+                    view: Box::new(view),
+                },
+            );
+        }
+    }
+
+    fn handle(&mut self, client: ClientId, request: Request) -> Result<(), ServerError> {
+        match request {
+            Request::Create => {
+                let game_id = self.next_game_id;
+                self.next_game_id += 1;
+                self.tables.insert(
+                    game_id,
+                    Table {
+                        game: Game::new(),
+                        host: Some(client),
+                        seats: Vec::new(),
+                    },
+                );
+                self.send(client, &Response::Created { game_id });
+            }
+
+            Request::Join { game_id, seat } => {
+                let table = self
+                    .tables
+                    .get_mut(&game_id)
+                    .ok_or(ServerError::NoSuchGame)?;
+                if table.game.settings().is_none() {
+                    return Err(ServerError::GameIsNotSetUp);
+                }
+                if table.seat_of(client).is_some() {
+                    return Err(ServerError::AlreadySeated);
+                }
+                match table.seats.get_mut(seat as usize) {
+                    None => return Err(ServerError::NoSuchSeat),
+                    Some(Some(_)) => return Err(ServerError::SeatIsTaken),
+                    Some(seated) => *seated = Some(client),
+                }
+                let view = table.game.view(seat);
+                self.send(client, &Response::Joined { game_id, seat });
+                self.send(
+                    client,
+                    &Response::State {
+                        game_id,
+                        view: Box::new(view),
+                    },
+                );
+            }
+
+            Request::Event { game_id, event } => {
+                let table = self
+                    .tables
+                    .get_mut(&game_id)
+                    .ok_or(ServerError::NoSuchGame)?;
+                let is_setup = matches!(event, GameEvent::GameSetup(_));
+                if is_setup && table.host != Some(client) {
+                    return Err(ServerError::NotTheHost);
+                }
+                if !is_setup && table.seat_of(client).is_none() {
+                    return Err(ServerError::NotSeated);
+                }
+
+                let result = match table.seat_of(client) {
+                    Some(seat) if !is_setup => table.game.process_event_from(seat, event),
+                    _ => table.game.process_event(event),
+                };
+                match result {
+                    Ok(success) => {
+                        if is_setup {
+                            table.seats = vec![None; table.game.players().len()];
+                        }
+                        self.send(client, &Response::Accepted { game_id, success });
+                        self.push_state(game_id);
+                    }
+                    Err(error) => self.send(client, &Response::Rejected { game_id, error }),
+                }
+            }
+
+            Request::Resync { game_id, sequence } => {
+                let table = self.tables.get(&game_id).ok_or(ServerError::NoSuchGame)?;
+                let seat = table.seat_of(client).ok_or(ServerError::NotSeated)?;
+                let resync = table.game.resync(seat, sequence);
+                self.send(client, &Response::Resync { game_id, resync });
+            }
+
+            Request::Leave { game_id } => {
+                let table = self.tables.get(&game_id).ok_or(ServerError::NoSuchGame)?;
+                if table.host != Some(client) && table.seat_of(client).is_none() {
+                    return Err(ServerError::NotSeated);
+                }
+                self.leave(client, game_id);
+            }
+        }
+        Ok(())
+    }
+
+    // Removes a client from a game as its host and from its seat, telling everyone at the table
+    // including the client leaving; a game is closed once nobody is left
+    fn leave(&mut self, client: ClientId, game_id: u64) {
+        let table = match self.tables.get_mut(&game_id) {
+            Some(table) => table,
+            None => return,
+        };
+        let seat = table.seat_of(client);
+        if let Some(seat) = seat {
+            table.seats[seat as usize] = None;
+        }
+        if table.host == Some(client) {
+            table.host = None;
+        }
+
+        let mut recipients: Vec<ClientId> = table.seats.iter().flatten().copied().collect();
+        recipients.push(client);
+        if table.is_abandoned() {
+            self.tables.remove(&game_id);
+        }
+        for recipient in recipients {
+            self.send(recipient, &Response::Left { game_id, seat });
+        }
+    }
+
+    // Removes a closed connection from every game it was part of
+    fn disconnect(&mut self, client: ClientId) {
+        self.clients.remove(&client);
+        let game_ids: Vec<u64> = self
+            .tables
+            .iter()
+            .filter(|(_, table)| table.host == Some(client) || table.seat_of(client).is_some())
+            .map(|(game_id, _)| *game_id)
+            .collect();
+        for game_id in game_ids {
+            self.leave(client, game_id);
+        }
+    }
+}
+
+// Accepts connections, serving each on its own thread. A failed accept is logged and skipped so
+// that one bad connection doesn't stop the server
+pub fn serve(listener: TcpListener) -> io::Result<()> {
+    let server = Arc::new(Mutex::new(Server::default()));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("Failed to accept a connection: {}", e);
+                continue;
+            }
+        };
+        let server = Arc::clone(&server);
+        thread::spawn(move || {
+            let _ = serve_client(&server, stream);
+        });
+    }
+    Ok(())
+}
+
+// Locks the server, carrying on with it when another client's thread panicked while holding it
+fn lock(server: &Mutex<Server>) -> MutexGuard<'_, Server> {
+    server.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn serve_client(server: &Mutex<Server>, stream: TcpStream) -> io::Result<()> {
+    let writer = stream.try_clone()?;
+    writer.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let (sender, lines) = mpsc::channel();
+    thread::spawn(move || write_client(writer, lines));
+    let client = {
+        let mut server = lock(server);
+        let client = server.next_client_id;
+        server.next_client_id += 1;
+        server.clients.insert(client, sender);
+        client
+    };
+
+    let reader = BufReader::new(stream);
+    for line in reader.lines() {
+        let line = match line {
+            Ok(line) => line,
+            Err(_) => break,
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut server = lock(server);
+        let result = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => server
+                .handle(client, request)
+                .map_err(|error| Response::Error {
+                    error,
+                    message: format!("{:?}", error),
+                }),
+            Err(e) => Err(Response::Error {
+                error: ServerError::MalformedRequest,
+                message: e.to_string(),
+            }),
+        };
+        if let Err(response) = result {
+            server.send(client, &response);
+        }
+    }
+
+    lock(server).disconnect(client);
+    Ok(())
+}
+
+// Writes the lines queued for a client until the client is disconnected, closing the connection
+// when a write fails or times out so that its reading thread disconnects it
+fn write_client(mut stream: TcpStream, lines: Receiver<String>) {
+    for line in lines {
+        if writeln!(stream, "{}", line).is_err() {
+            let _ = stream.shutdown(Shutdown::Both);
+            return;
+        }
+    }
+}
@@ -1,4 +1,165 @@
-#[derive(PartialEq, Copy, Clone, Debug)]
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+mod test {
+    use super::{CustomVariant, RuleVariant};
+    use crate::game_process_return::ConfigError;
+
+    // A joker counts the play value of the JokerRules in the play and every other card its usual
+    // value
+    #[test]
+    fn joker_play_value() {
+        let mut settings = super::GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: super::VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: Some(super::JokerRules {
+                num_jokers: 1,
+                play_value: 5,
+            }),
+            scoring_rules: super::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert_eq!(settings.play_value(crate::deck::RED_JOKER), 5);
+        assert_eq!(settings.play_value(crate::util::return_card('K', 'H')), 10);
+
+        settings.jokers = None;
+        assert_eq!(settings.play_value(crate::util::return_card('7', 'H')), 7);
+    }
+
+    // His heels is left out of the seven card variant when the rules say so, and the go is worth
+    // two when the rules say so
+    #[test]
+    fn scoring_rules() {
+        let jack = crate::util::return_card('J', 'S');
+        let mut settings = super::GameSettings {
+            variant: RuleVariant::TwoSevenCard,
+            victor_dealer_option: super::VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: super::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert!(settings.is_heels(jack));
+        assert!(!settings.is_heels(crate::util::return_card('Q', 'S')));
+        assert_eq!(settings.scoring_rules.go_points(), 1);
+
+        settings.scoring_rules.is_seven_card_heels = false;
+        settings.scoring_rules.is_go_worth_two = true;
+        assert!(!settings.is_heels(jack));
+        assert_eq!(settings.scoring_rules.go_points(), 2);
+
+        settings.variant = RuleVariant::TwoStandard;
+        assert!(settings.is_heels(jack));
+    }
+
+    // Every preset is a valid description of the table it has always dealt
+    #[test]
+    fn presets() {
+        for variant in crate::util::return_variants() {
+            let preset = variant.preset();
+            assert_eq!(preset.validate(), Ok(()));
+            assert_eq!(preset.build(), Ok(RuleVariant::Custom(preset)));
+
+            let (num_players, hand_size, crib_size) = match variant {
+                RuleVariant::TwoStandard => (2, 4, 4),
+                RuleVariant::TwoFiveCard => (2, 3, 4),
+                RuleVariant::TwoSevenCard => (2, 5, 5),
+                RuleVariant::ThreeStandard | RuleVariant::ThreeCaptain => (3, 4, 4),
+                RuleVariant::FourIndividual | RuleVariant::FourPairs => (4, 4, 4),
+                RuleVariant::FiveStandard => (5, 4, 4),
+                _ => (6, 4, 4),
+            };
+            assert_eq!(preset.num_players, num_players);
+            assert_eq!(preset.hand_size(), hand_size);
+            assert_eq!(preset.crib_size(), crib_size);
+        }
+
+        let captain = RuleVariant::ThreeCaptain;
+        assert_eq!(captain.preset().captain_index(), Some(0));
+        assert_eq!(captain.target_score(false), 61);
+        assert_eq!(captain.target_score(true), 121);
+        assert_eq!(RuleVariant::SixPairs.preset().num_sides(), 3);
+        assert_eq!(RuleVariant::SixPairs.preset().cards_dealt_to(5, 2), 4);
+        assert_eq!(RuleVariant::SixPairs.preset().cards_dealt_to(4, 2), 5);
+    }
+
+    #[test]
+    fn custom_target_and_skunk_lines() {
+        let variant = CustomVariant::new(2).target_score(91).build().unwrap();
+        assert_eq!(variant.target_score(false), 91);
+        assert_eq!(variant.skunk_lines(false).skunk, 61);
+        assert_eq!(variant.skunk_lines(false).double_skunk, 31);
+    }
+
+    #[test]
+    fn custom_validation() {
+        for (variant, error) in vec![
+            (
+                CustomVariant::new(7),
+                ConfigError::CustomVariantMustHaveTwoToSixPlayers,
+            ),
+            (
+                CustomVariant::new(3).partners(1, 4),
+                ConfigError::CustomVariantPartnershipsAreNotMutual,
+            ),
+            (
+                CustomVariant::new(4).partners(0, 2).partners(2, 1),
+                ConfigError::CustomVariantPartnershipsAreNotMutual,
+            ),
+            (
+                CustomVariant::new(3).seat(1, 6, 1),
+                ConfigError::CustomVariantHandsAreNotTheSameSize,
+            ),
+            (
+                CustomVariant::new(2).discards(6),
+                ConfigError::CustomVariantHandsAreNotTheSameSize,
+            ),
+            (
+                CustomVariant::new(2).cards_dealt(4).discards(0),
+                ConfigError::CustomVariantCribIsEmpty,
+            ),
+            (
+                CustomVariant::new(6).cards_dealt(9).discards(2),
+                ConfigError::CustomVariantDealsMoreCardsThanTheDeck,
+            ),
+            (
+                CustomVariant::new(2).target_score(201),
+                ConfigError::CustomVariantTargetScoreIsOutOfRange,
+            ),
+            (
+                CustomVariant::new(3).captain_target_score(61),
+                ConfigError::CustomVariantCaptainNeedsOnePlayerWithoutAPartner,
+            ),
+        ] {
+            assert_eq!(variant.build(), Err(error));
+        }
+
+        // A captain may play against two pairs
+        assert!(CustomVariant::new(5)
+            .partners(1, 3)
+            .partners(2, 4)
+            .cards_dealt(5)
+            .discards(1)
+            .captain_target_score(61)
+            .build()
+            .is_ok());
+    }
+}
+
+#[derive(PartialEq, Copy, Clone, Debug, Serialize, Deserialize)]
 pub enum RuleVariant {
     // Two players to 121
     TwoStandard,
@@ -26,6 +187,434 @@ pub enum RuleVariant {
 
     // Three pairs to 121
     SixPairs,
+
+    // Any other table built with a CustomVariant; the standard rules of play and the show apply
+    Custom(CustomVariant),
+}
+
+// Parses the name of a variant as it is written in this enum such as "TwoStandard"
+impl std::str::FromStr for RuleVariant {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<RuleVariant, String> {
+        match name {
+            "TwoStandard" => Ok(RuleVariant::TwoStandard),
+            "TwoFiveCard" => Ok(RuleVariant::TwoFiveCard),
+            "TwoSevenCard" => Ok(RuleVariant::TwoSevenCard),
+            "ThreeStandard" => Ok(RuleVariant::ThreeStandard),
+            "ThreeCaptain" => Ok(RuleVariant::ThreeCaptain),
+            "FourIndividual" => Ok(RuleVariant::FourIndividual),
+            "FourPairs" => Ok(RuleVariant::FourPairs),
+            "FiveStandard" => Ok(RuleVariant::FiveStandard),
+            "SixPairs" => Ok(RuleVariant::SixPairs),
+            _ => Err(format!("Unknown rule variant {}", name)),
+        }
+    }
+}
+
+impl RuleVariant {
+    // Returns the description of the deal, partnerships and targets of this variant; the special
+    // rules of five card cribbage (three points for the pone and a single PlayGroup) are kept by
+    // TwoFiveCard itself rather than its preset
+    pub fn preset(self) -> CustomVariant {
+        match self {
+            RuleVariant::TwoStandard => CustomVariant::new(2).cards_dealt(6).discards(2),
+            RuleVariant::TwoFiveCard => CustomVariant::new(2)
+                .cards_dealt(5)
+                .discards(2)
+                .target_score(61),
+            RuleVariant::TwoSevenCard => CustomVariant::new(2)
+                .cards_dealt(7)
+                .discards(2)
+                .crib_cards_from_deck(1)
+                .target_score(151),
+            RuleVariant::ThreeStandard => CustomVariant::new(3)
+                .cards_dealt(5)
+                .discards(1)
+                .crib_cards_from_deck(1),
+            RuleVariant::ThreeCaptain => CustomVariant::new(3)
+                .partners(1, 2)
+                .cards_dealt(5)
+                .discards(1)
+                .crib_cards_from_deck(1)
+                .captain_target_score(61),
+            RuleVariant::FourIndividual => CustomVariant::new(4).cards_dealt(5).discards(1),
+            RuleVariant::FourPairs => CustomVariant::new(4)
+                .partners(0, 2)
+                .partners(1, 3)
+                .cards_dealt(5)
+                .discards(1),
+            RuleVariant::FiveStandard => CustomVariant::new(5)
+                .cards_dealt(5)
+                .discards(1)
+                .seat(0, 4, 0),
+            RuleVariant::SixPairs => CustomVariant::new(6)
+                .partners(0, 3)
+                .partners(1, 4)
+                .partners(2, 5)
+                .cards_dealt(5)
+                .discards(1)
+                .seat(0, 4, 0)
+                .seat(3, 4, 0),
+            RuleVariant::Custom(custom) => custom,
+        }
+    }
+
+    // Returns the score a player must reach to win the game; this depends on whether or not the
+    // player has a partner for captain's cribbage where the captain plays to 61
+    pub fn target_score(self, has_partner: bool) -> u8 {
+        let preset = self.preset();
+        match preset.captain_target_score {
+            Some(captain_target_score) if !has_partner => captain_target_score,
+            _ => preset.target_score,
+        }
+    }
+
+    // Returns the standard skunk lines for a player of this variant: 91 and 61 in a game to 121,
+    // 31 and 16 in a game to 61, and 121 and 91 in a game to 151; other targets have their lines
+    // 30 and 60 points short of the target
+    pub fn skunk_lines(self, has_partner: bool) -> SkunkLines {
+        match self.target_score(has_partner) {
+            61 => SkunkLines {
+                skunk: 31,
+                double_skunk: 16,
+            },
+            151 => SkunkLines {
+                skunk: 121,
+                double_skunk: 91,
+            },
+            target_score => SkunkLines {
+                skunk: target_score.saturating_sub(30),
+                double_skunk: target_score.saturating_sub(60),
+            },
+        }
+    }
+}
+
+// The most players at a table; six players already use 30 of the 52 cards
+pub const MAX_PLAYERS: usize = 6;
+
+// The highest target score allowed, leaving room in the u8 scores for the points scored past the
+// target in the last hand
+pub const MAX_TARGET_SCORE: u8 = 200;
+
+// A description of the table for a variant; built up from CustomVariant::new such as
+//
+//     CustomVariant::new(4).partners(0, 2).partners(1, 3).cards_dealt(5).discards(1).build()
+//
+// Every seat array is indexed by the seat's distance to the left of the dealer such that the
+// dealer is at 0, which is how the dealer in five handed cribbage receives one card fewer
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomVariant {
+    pub num_players: u8,
+    // The index of each player's partner; partnerships are between players and not seats so this
+    // is indexed by player index
+    pub partner_indices: [Option<u8>; MAX_PLAYERS],
+    // The cards dealt to and discarded into the crib by each seat relative to the dealer
+    pub cards_dealt: [u8; MAX_PLAYERS],
+    pub discards: [u8; MAX_PLAYERS],
+    // Cards dealt straight from the deck to the crib after the players' hands
+    pub crib_cards_from_deck: u8,
+    pub target_score: u8,
+    // The target of any player without a partner when the other players have partners, such as
+    // the captain who plays to 61 against a pair playing to 121
+    pub captain_target_score: Option<u8>,
+}
+
+impl CustomVariant {
+    // Starts a variant for the given number of players without partners where every seat is dealt
+    // six cards and discards two and the game is played to 121
+    pub fn new(num_players: u8) -> CustomVariant {
+        CustomVariant {
+            num_players,
+            partner_indices: [None; MAX_PLAYERS],
+            cards_dealt: [6; MAX_PLAYERS],
+            discards: [2; MAX_PLAYERS],
+            crib_cards_from_deck: 0,
+            target_score: 121,
+            captain_target_score: None,
+        }
+    }
+
+    // Makes partners of the two players
+    pub fn partners(mut self, first: u8, second: u8) -> CustomVariant {
+        if let Some(partner_index) = self.partner_indices.get_mut(first as usize) {
+            *partner_index = Some(second);
+        }
+        if let Some(partner_index) = self.partner_indices.get_mut(second as usize) {
+            *partner_index = Some(first);
+        }
+        self
+    }
+
+    // Sets the cards dealt to every seat
+    pub fn cards_dealt(mut self, cards_dealt: u8) -> CustomVariant {
+        self.cards_dealt = [cards_dealt; MAX_PLAYERS];
+        self
+    }
+
+    // Sets the cards discarded into the crib by every seat
+    pub fn discards(mut self, discards: u8) -> CustomVariant {
+        self.discards = [discards; MAX_PLAYERS];
+        self
+    }
+
+    // Sets the cards dealt to and discarded by the seat at the given distance to the left of the
+    // dealer
+    pub fn seat(mut self, seat: u8, cards_dealt: u8, discards: u8) -> CustomVariant {
+        if (seat as usize) < MAX_PLAYERS {
+            self.cards_dealt[seat as usize] = cards_dealt;
+            self.discards[seat as usize] = discards;
+        }
+        self
+    }
+
+    pub fn crib_cards_from_deck(mut self, crib_cards_from_deck: u8) -> CustomVariant {
+        self.crib_cards_from_deck = crib_cards_from_deck;
+        self
+    }
+
+    pub fn target_score(mut self, target_score: u8) -> CustomVariant {
+        self.target_score = target_score;
+        self
+    }
+
+    pub fn captain_target_score(mut self, captain_target_score: u8) -> CustomVariant {
+        self.captain_target_score = Some(captain_target_score);
+        self
+    }
+
+    // Returns the variant to be given to the GameSettings or the first problem with it
+    pub fn build(self) -> Result<RuleVariant, crate::game_process_return::ConfigError> {
+        self.validate()?;
+        Ok(RuleVariant::Custom(self))
+    }
+
+    // Returns the number of cards dealt to and discarded by the given player
+    pub fn cards_dealt_to(&self, player_index: u8, index_dealer: u8) -> u8 {
+        self.cards_dealt[self.seat_of(player_index, index_dealer)]
+    }
+
+    pub fn discards_by(&self, player_index: u8, index_dealer: u8) -> u8 {
+        self.discards[self.seat_of(player_index, index_dealer)]
+    }
+
+    // Returns the number of cards each player holds for the play and the show, which validation
+    // ensures is the same for every seat
+    pub fn hand_size(&self) -> u8 {
+        self.cards_dealt[0].saturating_sub(self.discards[0])
+    }
+
+    pub fn crib_size(&self) -> u8 {
+        self.discards[..self.num_players as usize]
+            .iter()
+            .sum::<u8>()
+            + self.crib_cards_from_deck
+    }
+
+    pub fn has_partners(&self) -> bool {
+        self.partner_indices[..self.num_players as usize]
+            .iter()
+            .any(|partner_index| partner_index.is_some())
+    }
+
+    // Returns the index of the captain, the first player without a partner when the variant has a
+    // captain's target
+    pub fn captain_index(&self) -> Option<u8> {
+        self.captain_target_score?;
+        (0..self.num_players).find(|index| self.partner_indices[*index as usize].is_none())
+    }
+
+    // Returns the number of players or partnerships competing
+    pub fn num_sides(&self) -> u8 {
+        (0..self.num_players)
+            .filter(|index| match self.partner_indices[*index as usize] {
+                Some(partner_index) => *index < partner_index,
+                None => true,
+            })
+            .count() as u8
+    }
+
+    // Returns the first problem with the variant
+    pub fn validate(&self) -> Result<(), crate::game_process_return::ConfigError> {
+        use crate::game_process_return::ConfigError;
+
+        if self.num_players < 2 || self.num_players as usize > MAX_PLAYERS {
+            return Err(ConfigError::CustomVariantMustHaveTwoToSixPlayers);
+        }
+
+        // Partnerships must be mutual, between two different players at the table and every player
+        // past the table must have no partner
+        for (index, partner_index) in self.partner_indices.iter().enumerate() {
+            let is_valid = match partner_index {
+                Some(partner_index) => {
+                    index < self.num_players as usize
+                        && *partner_index < self.num_players
+                        && *partner_index as usize != index
+                        && self.partner_indices[*partner_index as usize] == Some(index as u8)
+                }
+                None => true,
+            };
+            if !is_valid {
+                return Err(ConfigError::CustomVariantPartnershipsAreNotMutual);
+            }
+        }
+
+        let seats = 0..self.num_players as usize;
+        if seats
+            .clone()
+            .any(|seat| self.discards[seat] > self.cards_dealt[seat])
+            || seats
+                .clone()
+                .any(|seat| self.cards_dealt[seat] - self.discards[seat] != self.hand_size())
+            || self.hand_size() == 0
+        {
+            return Err(ConfigError::CustomVariantHandsAreNotTheSameSize);
+        }
+
+        // One card must be left in the deck for the starter
+        let cards_used = seats
+            .map(|seat| u32::from(self.cards_dealt[seat]))
+            .sum::<u32>()
+            + u32::from(self.crib_cards_from_deck);
+        if cards_used > 51 {
+            return Err(ConfigError::CustomVariantDealsMoreCardsThanTheDeck);
+        }
+
+        if self.crib_size() == 0 {
+            return Err(ConfigError::CustomVariantCribIsEmpty);
+        }
+
+        let is_target_valid =
+            |target_score: u8| target_score > 0 && target_score <= MAX_TARGET_SCORE;
+        if !is_target_valid(self.target_score)
+            || !self.captain_target_score.map_or(true, is_target_valid)
+        {
+            return Err(ConfigError::CustomVariantTargetScoreIsOutOfRange);
+        }
+
+        // A captain's target only makes sense with one player without a partner playing against
+        // partners
+        if self.captain_target_score.is_some()
+            && (!self.has_partners()
+                || (0..self.num_players)
+                    .filter(|index| self.partner_indices[*index as usize].is_none())
+                    .count()
+                    != 1)
+        {
+            return Err(ConfigError::CustomVariantCaptainNeedsOnePlayerWithoutAPartner);
+        }
+
+        Ok(())
+    }
+
+    fn seat_of(&self, player_index: u8, index_dealer: u8) -> usize {
+        ((player_index + self.num_players - index_dealer) % self.num_players) as usize
+    }
+}
+
+// A player who loses with a score below the skunk line is skunked and below the double skunk line
+// is double skunked, earning the winner extra game points in a match
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SkunkLines {
+    pub skunk: u8,
+    pub double_skunk: u8,
+}
+
+// The smallest opening bid, the smallest raise over the high bid, and the largest bid in auction
+// cribbage
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct AuctionRules {
+    pub minimum_bid: u8,
+    pub minimum_increment: u8,
+    // A bid is paid from the winner's pegs, which stop at the start, so it may be more than the
+    // bidder has pegged but never more than this
+    pub maximum_bid: u8,
+}
+
+// Jokers added to the deck as wild cards in the show, and the value a joker counts in the play
+// where it isn't wild
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct JokerRules {
+    // One or two jokers
+    pub num_jokers: u8,
+    // From one to ten
+    pub play_value: u8,
+}
+
+// House rules for scoring; the default is the standard game
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoringRules {
+    // A run in the play may continue with cards played before the count was reset after a go or a
+    // 31
+    pub is_run_across_reset: bool,
+    // The go is worth two rather than one; 31 is worth two either way. Under Back Up 10 the player
+    // who would score the go pegs one or moves an opponent back with a GoDecision instead
+    pub is_go_worth_two: bool,
+    // A flush of the cards of the crib counts without the starter as it does for a hand
+    pub is_crib_four_flush: bool,
+    // Whether the dealer scores his heels for a jack cut as the starter in a variant where seven
+    // cards are dealt to a player
+    pub is_seven_card_heels: bool,
+    // Points scored by a hand or crib that would otherwise score nothing, called a nineteen as no
+    // hand can total nineteen; zero for no bonus
+    pub nineteen_bonus: u8,
+    // The most holes a player, along with their partner, may be moved back by Back Up 10 over a
+    // game; None for no limit
+    pub back_up_ten_limit: Option<u8>,
+}
+
+impl Default for ScoringRules {
+    fn default() -> ScoringRules {
+        ScoringRules {
+            is_run_across_reset: false,
+            is_go_worth_two: false,
+            is_crib_four_flush: false,
+            is_seven_card_heels: true,
+            nineteen_bonus: 0,
+            back_up_ten_limit: None,
+        }
+    }
+}
+
+impl ScoringRules {
+    // The points of a go
+    pub fn go_points(&self) -> u8 {
+        if self.is_go_worth_two {
+            2
+        } else {
+            1
+        }
+    }
+}
+
+// Time limits on the decisions of a player, each in milliseconds of the game's clock::Clock; a
+// decision without a limit may take as long as the player likes
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeLimits {
+    pub discard: Option<TimeLimit>,
+    // Playing a card or saying go, and choosing after a go with Back Up 10
+    pub play: Option<TimeLimit>,
+    // Calling nibs and counting the play, a hand, or the crib with manual scoring
+    pub manual_scoring: Option<TimeLimit>,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TimeLimit {
+    pub millis: u64,
+    pub on_timeout: TimeoutPolicy,
+}
+
+// What happens to a player who runs out of time, once a GameEvent::Timeout is sent for them
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum TimeoutPolicy {
+    // A legal move is made for the player: a discard and a play as chosen by the
+    // strategy::GreedyStrategy, pegging one after a go, or the full count when scoring
+    AutoPlay,
+    // The full count is scored for the player; only for the manual scoring time limit
+    AutoScore,
+    // The player and their partner lose the game, which every other player wins
+    Forfeit,
 }
 
 // A setting for how the dealer for the next game in the match is chosen; with two players the first
@@ -33,7 +622,7 @@ pub enum RuleVariant {
 // have all of the losers cut for first dealer as if it were the initial game, or you can have the
 // last place loser (or losers with a pair) draw. With captain's cribbage, the captain always deals
 // first.
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum VictorDealerOption {
     TwoPlayers,
     LosersDrawForDealer,
@@ -41,7 +630,7 @@ pub enum VictorDealerOption {
     CaptainDeals,
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GameSettings {
     pub variant: RuleVariant,
     pub victor_dealer_option: VictorDealerOption,
@@ -52,4 +641,40 @@ pub struct GameSettings {
     // that only have two teams (such that there are not multiple winners) ie all the two player
     // variants, captain's cribbage, and four players in pairs
     pub is_lowball: bool,
+    // Back Up 10, where the player who would peg one for the go or last card instead chooses
+    // between pegging one and moving an opponent back ten holes
+    pub is_back_up_ten: bool,
+    // Auction cribbage, where the players bid points from their scores for the crib after looking
+    // at their hands
+    pub auction: Option<AuctionRules>,
+    // Skunk lines used for every player in place of the variant's standard lines
+    pub skunk_lines: Option<SkunkLines>,
+    // Jokers in the deck
+    pub jokers: Option<JokerRules>,
+    pub scoring_rules: ScoringRules,
+    // Limits on how long a player may take over a decision
+    pub time_limits: Option<TimeLimits>,
+}
+
+impl GameSettings {
+    // Returns the value of a card in the play, where a joker counts the play value of the
+    // JokerRules
+    pub fn play_value(&self, card: crate::deck::Card) -> u8 {
+        match self.jokers {
+            Some(jokers) if card.is_joker() => jokers.play_value,
+            _ => crate::deck::return_play_value(card),
+        }
+    }
+
+    // Returns whether the dealer scores his heels for the given starter
+    pub fn is_heels(&self, starter: crate::deck::Card) -> bool {
+        starter.value == crate::deck::CardValue::Jack
+            && (self.scoring_rules.is_seven_card_heels
+                || !self
+                    .variant
+                    .preset()
+                    .cards_dealt
+                    .iter()
+                    .any(|cards_dealt| *cards_dealt >= 7))
+    }
 }
@@ -0,0 +1,420 @@
+use crate::deck;
+use crate::score;
+use crate::score::ScoreEvent;
+
+// Solitaire cribbage, played alone on a four by four grid
+//
+// Cards are drawn from a shuffled deck one at a time and each is placed into an empty cell of the
+// grid or into the crib until the sixteen cells and the two cards of the crib are filled. The
+// starter is then cut and every row and every column of the grid is scored as a cribbage hand with
+// the starter, the crib is scored as a crib and a jack cut as the starter is worth two for his
+// heels as in the regular game. The aim is simply the highest total
+
+// The number of rows and of columns of the grid
+pub const GRID_SIZE: usize = 4;
+// The number of cards placed into the crib
+pub const CRIB_SIZE: usize = 2;
+
+#[cfg(test)]
+mod test {
+    use super::{
+        Line, Placement, Solitaire, SolitaireError, SolitaireEvent, SolitaireReturn, SolitaireState,
+    };
+    use crate::util::return_card;
+
+    // Returns a game whose cards are drawn in the order given with the starter last
+    fn set_up_solitaire(cards: &[&str]) -> Solitaire {
+        let mut cards: Vec<crate::deck::Card> = cards
+            .iter()
+            .map(|card| {
+                let mut chars = card.chars();
+                return_card(chars.next().unwrap(), chars.next().unwrap())
+            })
+            .collect();
+        cards.reverse();
+        Solitaire::from_deck(crate::deck::Deck::from_vec(cards))
+    }
+
+    // Places every card in turn filling each row from the top left and then the crib
+    fn place_in_order(game: &mut Solitaire) {
+        for row in 0..4 {
+            for column in 0..4 {
+                game.process_event(SolitaireEvent::Place(Placement::Grid { row, column }))
+                    .unwrap();
+            }
+        }
+        game.process_event(SolitaireEvent::Place(Placement::Crib))
+            .unwrap();
+        game.process_event(SolitaireEvent::Place(Placement::Crib))
+            .unwrap();
+    }
+
+    #[test]
+    fn placement() {
+        let mut game = Solitaire::from_seed(7);
+        assert_eq!(game.state(), SolitaireState::Place);
+        let first = game.current_card().unwrap();
+
+        assert_eq!(
+            game.process_event(SolitaireEvent::Place(Placement::Grid { row: 1, column: 2 })),
+            Ok(SolitaireReturn::Placed)
+        );
+        assert_eq!(game.grid()[1][2], Some(first));
+        assert_ne!(game.current_card(), Some(first));
+
+        assert_eq!(
+            game.process_event(SolitaireEvent::Place(Placement::Grid { row: 1, column: 2 })),
+            Err(SolitaireError::CellIsTaken)
+        );
+        assert_eq!(
+            game.process_event(SolitaireEvent::Place(Placement::Grid { row: 4, column: 0 })),
+            Err(SolitaireError::CellIsOutsideTheGrid)
+        );
+        assert_eq!(
+            game.process_event(SolitaireEvent::Confirmation),
+            Err(SolitaireError::ExpectedPlacement)
+        );
+
+        game.process_event(SolitaireEvent::Place(Placement::Crib))
+            .unwrap();
+        game.process_event(SolitaireEvent::Place(Placement::Crib))
+            .unwrap();
+        assert_eq!(game.crib().len(), 2);
+        assert_eq!(
+            game.process_event(SolitaireEvent::Place(Placement::Crib)),
+            Err(SolitaireError::CribIsFull)
+        );
+    }
+
+    #[test]
+    fn same_seed_same_cards() {
+        let mut first = Solitaire::from_seed(3);
+        let mut second = Solitaire::from_seed(3);
+        place_in_order(&mut first);
+        place_in_order(&mut second);
+        assert_eq!(first.grid(), second.grid());
+        assert_eq!(first.crib(), second.crib());
+    }
+
+    #[test]
+    fn final_score() {
+        let mut game = set_up_solitaire(&[
+            "5H", "5D", "5C", "JS", //
+            "AH", "2H", "3H", "4H", //
+            "KD", "QD", "9C", "8S", //
+            "6S", "7D", "TC", "2C", //
+            "4C", "4D", // crib
+            "JH", // starter
+        ]);
+        place_in_order(&mut game);
+        assert_eq!(game.state(), SolitaireState::CutStarter);
+        assert_eq!(game.current_card(), None);
+        assert_eq!(
+            game.process_event(SolitaireEvent::Place(Placement::Crib)),
+            Err(SolitaireError::ExpectedConfirmation)
+        );
+
+        let score = match game.process_event(SolitaireEvent::Confirmation) {
+            Ok(SolitaireReturn::StarterCut(score)) => score,
+            other => panic!("unexpected return {:?}", other),
+        };
+        assert_eq!(game.state(), SolitaireState::Complete);
+        assert_eq!(game.starter_card(), Some(return_card('J', 'H')));
+        assert_eq!(game.score(), Some(&score));
+
+        // Every row, then every column and the crib are each scored once
+        let lines: Vec<Line> = score.lines.iter().map(|line| line.line).collect();
+        assert_eq!(
+            lines,
+            vec![
+                Line::Row(0),
+                Line::Row(1),
+                Line::Row(2),
+                Line::Row(3),
+                Line::Column(0),
+                Line::Column(1),
+                Line::Column(2),
+                Line::Column(3),
+                Line::Crib,
+            ]
+        );
+
+        // 5H 5D 5C JS with the JH starter is seven fifteens, a triple and a pair
+        assert_eq!(score.lines[0].points(), 22);
+        // AH 2H 3H 4H with the JH starter is a five card flush, a run of four and two fifteens
+        assert_eq!(score.lines[1].points(), 13);
+        // 4C 4D in the crib with the JH starter is a pair
+        assert_eq!(score.lines[8].points(), 2);
+        for line in &score.lines {
+            assert_eq!(
+                line.score_events,
+                crate::score::show::score_hand(
+                    0,
+                    &line.cards,
+                    return_card('J', 'H'),
+                    line.line == Line::Crib
+                )
+            );
+        }
+
+        assert_eq!(score.heels.as_ref().unwrap().point_value, 2);
+        let total: u16 = score.lines.iter().map(|line| line.points()).sum::<u16>() + 2;
+        assert_eq!(score.total, total);
+
+        assert_eq!(
+            game.process_event(SolitaireEvent::Confirmation),
+            Err(SolitaireError::GameIsComplete)
+        );
+    }
+}
+
+// The cells of the grid are given by their row from the top and their column from the left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    Grid { row: u8, column: u8 },
+    Crib,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolitaireEvent {
+    // Places the current card
+    Place(Placement),
+    // Cuts the starter once every card has been placed
+    Confirmation,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolitaireState {
+    Place,
+    CutStarter,
+    Complete,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolitaireError {
+    ExpectedPlacement,
+    ExpectedConfirmation,
+    GameIsComplete,
+    CellIsOutsideTheGrid,
+    CellIsTaken,
+    CribIsFull,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolitaireReturn {
+    Placed,
+    StarterCut(SolitaireScore),
+}
+
+// A row, column, or the crib which is scored as a hand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Line {
+    Row(u8),
+    Column(u8),
+    Crib,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineScore {
+    pub line: Line,
+    // The cards of the line not including the starter
+    pub cards: Vec<deck::Card>,
+    pub score_events: Vec<ScoreEvent>,
+}
+
+impl LineScore {
+    pub fn points(&self) -> u16 {
+        self.score_events
+            .iter()
+            .map(|score_event| u16::from(score_event.point_value))
+            .sum()
+    }
+}
+
+// The final breakdown of a game; the ScoreEvents all have a player_index of 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolitaireScore {
+    // Each row from the top, each column from the left and then the crib
+    pub lines: Vec<LineScore>,
+    // Two for his heels when the starter is a jack
+    pub heels: Option<ScoreEvent>,
+    pub total: u16,
+}
+
+pub struct Solitaire {
+    deck: deck::Deck,
+    state: SolitaireState,
+    current_card: Option<deck::Card>,
+    grid: [[Option<deck::Card>; GRID_SIZE]; GRID_SIZE],
+    crib: Vec<deck::Card>,
+    starter_card: Option<deck::Card>,
+    score: Option<SolitaireScore>,
+}
+
+impl Default for Solitaire {
+    fn default() -> Solitaire {
+        Solitaire::new()
+    }
+}
+
+impl Solitaire {
+    pub fn new() -> Solitaire {
+        let mut deck = deck::Deck::new();
+        deck.reset_deck();
+        Solitaire::from_deck(deck)
+    }
+
+    // Returns a game whose shuffle is determined by the given seed
+    pub fn from_seed(seed: u64) -> Solitaire {
+        let mut deck = deck::Deck::from_seed(seed);
+        deck.reset_deck();
+        Solitaire::from_deck(deck)
+    }
+
+    // Starts a game with the deck as it is and draws the first card
+    fn from_deck(mut deck: deck::Deck) -> Solitaire {
+        let current_card = Some(deck.deal());
+        Solitaire {
+            deck,
+            state: SolitaireState::Place,
+            current_card,
+            grid: [[None; GRID_SIZE]; GRID_SIZE],
+            crib: Vec::new(),
+            starter_card: None,
+            score: None,
+        }
+    }
+
+    pub fn state(&self) -> SolitaireState {
+        self.state
+    }
+
+    // The card waiting to be placed
+    pub fn current_card(&self) -> Option<deck::Card> {
+        self.current_card
+    }
+
+    pub fn grid(&self) -> &[[Option<deck::Card>; GRID_SIZE]; GRID_SIZE] {
+        &self.grid
+    }
+
+    pub fn crib(&self) -> &[deck::Card] {
+        &self.crib
+    }
+
+    pub fn starter_card(&self) -> Option<deck::Card> {
+        self.starter_card
+    }
+
+    // The final breakdown once the starter has been cut
+    pub fn score(&self) -> Option<&SolitaireScore> {
+        self.score.as_ref()
+    }
+
+    pub fn process_event(
+        &mut self,
+        event: SolitaireEvent,
+    ) -> Result<SolitaireReturn, SolitaireError> {
+        match (self.state, event) {
+            (SolitaireState::Place, SolitaireEvent::Place(placement)) => {
+                self.process_placement(placement)
+            }
+            (SolitaireState::Place, _) => Err(SolitaireError::ExpectedPlacement),
+
+            (SolitaireState::CutStarter, SolitaireEvent::Confirmation) => self.process_cut(),
+            (SolitaireState::CutStarter, _) => Err(SolitaireError::ExpectedConfirmation),
+
+            (SolitaireState::Complete, _) => Err(SolitaireError::GameIsComplete),
+        }
+    }
+
+    // Places the current card and draws the next, moving on to the cut once every cell of the grid
+    // and the crib are filled
+    fn process_placement(
+        &mut self,
+        placement: Placement,
+    ) -> Result<SolitaireReturn, SolitaireError> {
+        let card = self.current_card.unwrap();
+        match placement {
+            Placement::Grid { row, column } => {
+                let cell = match self
+                    .grid
+                    .get_mut(row as usize)
+                    .and_then(|cells| cells.get_mut(column as usize))
+                {
+                    Some(cell) => cell,
+                    None => return Err(SolitaireError::CellIsOutsideTheGrid),
+                };
+                if cell.is_some() {
+                    return Err(SolitaireError::CellIsTaken);
+                }
+                *cell = Some(card);
+            }
+            Placement::Crib => {
+                if self.crib.len() == CRIB_SIZE {
+                    return Err(SolitaireError::CribIsFull);
+                }
+                self.crib.push(card);
+            }
+        }
+
+        let is_grid_full = self.grid.iter().flatten().all(|cell| cell.is_some());
+        if is_grid_full && self.crib.len() == CRIB_SIZE {
+            self.current_card = None;
+            self.state = SolitaireState::CutStarter;
+        } else {
+            self.current_card = Some(self.deck.deal());
+        }
+        Ok(SolitaireReturn::Placed)
+    }
+
+    // Cuts the starter and scores every line
+    fn process_cut(&mut self) -> Result<SolitaireReturn, SolitaireError> {
+        let starter = self.deck.deal();
+        self.starter_card = Some(starter);
+
+        let mut lines = Vec::with_capacity(2 * GRID_SIZE + 1);
+        for row in 0..GRID_SIZE {
+            let cards: Vec<deck::Card> = self.grid[row].iter().flatten().copied().collect();
+            lines.push(score_line(Line::Row(row as u8), cards, starter));
+        }
+        for column in 0..GRID_SIZE {
+            let cards: Vec<deck::Card> = self.grid.iter().filter_map(|row| row[column]).collect();
+            lines.push(score_line(Line::Column(column as u8), cards, starter));
+        }
+        lines.push(score_line(Line::Crib, self.crib.clone(), starter));
+
+        let heels = if starter.value == deck::CardValue::Jack {
+            Some(ScoreEvent {
+                score_type: score::ScoreType::Play(score::PlayScoreType::Nibs),
+                player_index: 0,
+                point_value: 2,
+            })
+        } else {
+            None
+        };
+
+        let total = lines.iter().map(LineScore::points).sum::<u16>()
+            + heels
+                .as_ref()
+                .map_or(0, |heels| u16::from(heels.point_value));
+        let score = SolitaireScore {
+            lines,
+            heels,
+            total,
+        };
+
+        self.score = Some(score.clone());
+        self.state = SolitaireState::Complete;
+        Ok(SolitaireReturn::StarterCut(score))
+    }
+}
+
+fn score_line(line: Line, cards: Vec<deck::Card>, starter: deck::Card) -> LineScore {
+    let score_events = score::show::score_hand(0, &cards, starter, line == Line::Crib);
+    LineScore {
+        line,
+        cards,
+        score_events,
+    }
+}
@@ -0,0 +1,307 @@
+use crate::player;
+use crate::settings;
+use serde::Serialize;
+
+// The result of each game of a match and the game points earned across the match. The winner of a
+// game earns one game point, and one more for each losing player or partnership below the skunk
+// line, or two more when below the double skunk line
+
+#[cfg(test)]
+mod test {
+    use super::game_result;
+    use super::GameResult;
+    use super::MatchStandings;
+    use crate::settings::{GameSettings, RuleVariant, SkunkLines, VictorDealerOption};
+
+    fn game_settings(
+        variant: RuleVariant,
+        victor_dealer_option: VictorDealerOption,
+    ) -> GameSettings {
+        GameSettings {
+            variant,
+            victor_dealer_option,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        }
+    }
+
+    fn players(scores: &[u8], partners: &[Option<u8>]) -> Vec<crate::player::Player> {
+        scores
+            .iter()
+            .zip(partners)
+            .map(|(score, partner_index)| crate::player::Player {
+                back_peg_pos: 0,
+                front_peg_pos: *score,
+                hand: vec![],
+                partner_index: *partner_index,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn two_players() {
+        let settings = game_settings(RuleVariant::TwoStandard, VictorDealerOption::TwoPlayers);
+        for (loser_score, game_points, skunked, double_skunked) in &[
+            (91, 1, vec![], vec![]),
+            (90, 2, vec![1], vec![]),
+            (61, 2, vec![1], vec![]),
+            (60, 3, vec![1], vec![1]),
+        ] {
+            assert_eq!(
+                game_result(&settings, &players(&[121, *loser_score], &[None, None]), 0),
+                GameResult {
+                    winners: vec![0],
+                    scores: vec![121, *loser_score],
+                    skunked: skunked.clone(),
+                    double_skunked: double_skunked.clone(),
+                    game_points: *game_points,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn scaled_and_custom_lines() {
+        let five_card = game_settings(RuleVariant::TwoFiveCard, VictorDealerOption::TwoPlayers);
+        let result = game_result(&five_card, &players(&[30, 61], &[None, None]), 1);
+        assert_eq!(result.skunked, vec![0]);
+        assert_eq!(result.game_points, 2);
+
+        let seven_card = game_settings(RuleVariant::TwoSevenCard, VictorDealerOption::TwoPlayers);
+        let result = game_result(&seven_card, &players(&[151, 90], &[None, None]), 0);
+        assert_eq!(result.double_skunked, vec![1]);
+        assert_eq!(result.game_points, 3);
+
+        let mut custom = game_settings(RuleVariant::TwoStandard, VictorDealerOption::TwoPlayers);
+        custom.skunk_lines = Some(SkunkLines {
+            skunk: 101,
+            double_skunk: 0,
+        });
+        let result = game_result(&custom, &players(&[121, 100], &[None, None]), 0);
+        assert_eq!(result.skunked, vec![1]);
+        assert_eq!(result.game_points, 2);
+    }
+
+    // Partners win together and a skunked partnership only counts once; the captain in captain's
+    // cribbage is measured against the lines of a game to 61
+    #[test]
+    fn partners() {
+        let settings = game_settings(
+            RuleVariant::FourPairs,
+            VictorDealerOption::LosersDrawForDealer,
+        );
+        let result = game_result(
+            &settings,
+            &players(&[121, 80, 121, 80], &[Some(2), Some(3), Some(0), Some(1)]),
+            2,
+        );
+        assert_eq!(result.winners, vec![0, 2]);
+        assert_eq!(result.skunked, vec![1, 3]);
+        assert_eq!(result.game_points, 2);
+
+        let settings = game_settings(RuleVariant::ThreeCaptain, VictorDealerOption::CaptainDeals);
+        let result = game_result(
+            &settings,
+            &players(&[40, 121, 121], &[None, Some(2), Some(1)]),
+            1,
+        );
+        assert_eq!(result.winners, vec![1, 2]);
+        assert!(result.skunked.is_empty());
+    }
+
+    #[test]
+    fn each_skunked_player_counts() {
+        let settings = game_settings(
+            RuleVariant::ThreeStandard,
+            VictorDealerOption::LosersDrawForDealer,
+        );
+        let result = game_result(&settings, &players(&[50, 121, 85], &[None; 3]), 1);
+        assert_eq!(result.skunked, vec![0, 2]);
+        assert_eq!(result.double_skunked, vec![0]);
+        assert_eq!(result.game_points, 1 + 2 + 1);
+    }
+
+    // In lowball the player reaching the target loses and there are no skunks
+    #[test]
+    fn lowball() {
+        let mut settings = game_settings(RuleVariant::TwoStandard, VictorDealerOption::TwoPlayers);
+        settings.is_lowball = true;
+        let result = game_result(&settings, &players(&[121, 20], &[None, None]), 0);
+        assert_eq!(result.winners, vec![1]);
+        assert!(result.skunked.is_empty());
+        assert_eq!(result.game_points, 1);
+    }
+
+    #[test]
+    fn standings() {
+        let settings = game_settings(RuleVariant::TwoStandard, VictorDealerOption::TwoPlayers);
+        let mut standings = MatchStandings::default();
+        assert!(standings.leaders().is_empty());
+
+        standings.record(game_result(
+            &settings,
+            &players(&[121, 50], &[None, None]),
+            0,
+        ));
+        standings.record(game_result(
+            &settings,
+            &players(&[100, 121], &[None, None]),
+            1,
+        ));
+        assert_eq!(standings.game_points, vec![3, 1]);
+        assert_eq!(standings.games_won, vec![1, 1]);
+        assert_eq!(standings.results.len(), 2);
+        assert_eq!(standings.leaders(), vec![0]);
+
+        standings.record(game_result(
+            &settings,
+            &players(&[80, 121], &[None, None]),
+            1,
+        ));
+        assert_eq!(standings.leaders(), vec![0, 1]);
+    }
+}
+
+// Game points for winning a game, and the extra game points for each loser who is skunked or
+// double skunked
+pub const WIN_GAME_POINTS: u8 = 1;
+pub const SKUNK_GAME_POINTS: u8 = 1;
+pub const DOUBLE_SKUNK_GAME_POINTS: u8 = 2;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct GameResult {
+    // The indices of the players who won; partners win together
+    pub winners: Vec<u8>,
+    // The final score of every player
+    pub scores: Vec<u8>,
+    // The losing players below the skunk line, which includes those below the double skunk line
+    pub skunked: Vec<u8>,
+    pub double_skunked: Vec<u8>,
+    // The game points earned by each winner
+    pub game_points: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct MatchStandings {
+    // The game points and games won by each player across the match
+    pub game_points: Vec<u32>,
+    pub games_won: Vec<u32>,
+    // The result of every game of the match in the order they were played
+    pub results: Vec<GameResult>,
+}
+
+impl MatchStandings {
+    // Adds the game points of a finished game to the standings
+    pub fn record(&mut self, result: GameResult) {
+        if self.game_points.len() < result.scores.len() {
+            self.game_points.resize(result.scores.len(), 0);
+            self.games_won.resize(result.scores.len(), 0);
+        }
+        for winner in &result.winners {
+            self.game_points[*winner as usize] += u32::from(result.game_points);
+            self.games_won[*winner as usize] += 1;
+        }
+        self.results.push(result);
+    }
+
+    // Returns the indices of the players with the most game points, or nothing before the first
+    // game has finished
+    pub fn leaders(&self) -> Vec<u8> {
+        let most = match self.game_points.iter().max() {
+            Some(most) => *most,
+            None => return Vec::new(),
+        };
+        (0..self.game_points.len() as u8)
+            .filter(|index| self.game_points[*index as usize] == most)
+            .collect()
+    }
+}
+
+// Returns the result of a game forfeited by the given player and their partner, which every other
+// player wins without any skunks
+pub(crate) fn forfeit_result(players: &[player::Player], forfeiter_index: usize) -> GameResult {
+    let partner_of_forfeiter = players[forfeiter_index].partner_index;
+    GameResult {
+        winners: (0..players.len() as u8)
+            .filter(|index| {
+                *index as usize != forfeiter_index && Some(*index) != partner_of_forfeiter
+            })
+            .collect(),
+        scores: players.iter().map(|player| player.front_peg_pos).collect(),
+        skunked: Vec::new(),
+        double_skunked: Vec::new(),
+        game_points: WIN_GAME_POINTS,
+    }
+}
+
+// Returns the result of a game which has just ended with the given player reaching their target
+pub(crate) fn game_result(
+    settings: &settings::GameSettings,
+    players: &[player::Player],
+    finisher_index: usize,
+) -> GameResult {
+    let partner_of_finisher = players[finisher_index].partner_index;
+    let is_finishing_side =
+        |index: usize| index == finisher_index || Some(index as u8) == partner_of_finisher;
+
+    let scores: Vec<u8> = players.iter().map(|player| player.front_peg_pos).collect();
+
+    // With lowball the side reaching the target loses and the game is simply won
+    if settings.is_lowball {
+        return GameResult {
+            winners: (0..players.len() as u8)
+                .filter(|index| !is_finishing_side(*index as usize))
+                .collect(),
+            scores,
+            skunked: Vec::new(),
+            double_skunked: Vec::new(),
+            game_points: WIN_GAME_POINTS,
+        };
+    }
+
+    let mut result = GameResult {
+        winners: Vec::new(),
+        scores,
+        skunked: Vec::new(),
+        double_skunked: Vec::new(),
+        game_points: WIN_GAME_POINTS,
+    };
+    for (index, player) in players.iter().enumerate() {
+        if is_finishing_side(index) {
+            result.winners.push(index as u8);
+            continue;
+        }
+
+        let lines = settings
+            .skunk_lines
+            .unwrap_or_else(|| settings.variant.skunk_lines(player.partner_index.is_some()));
+        // Partners share a score so a skunked partnership only earns the winner extra game
+        // points once
+        let is_counted = match player.partner_index {
+            Some(partner_index) => index < partner_index as usize,
+            None => true,
+        };
+
+        if player.front_peg_pos < lines.double_skunk {
+            result.skunked.push(index as u8);
+            result.double_skunked.push(index as u8);
+            if is_counted {
+                result.game_points += DOUBLE_SKUNK_GAME_POINTS;
+            }
+        } else if player.front_peg_pos < lines.skunk {
+            result.skunked.push(index as u8);
+            if is_counted {
+                result.game_points += SKUNK_GAME_POINTS;
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,283 @@
+use crate::game_process_return;
+use crate::util;
+
+// State for auction cribbage where, after looking at their hands, the players bid points for the
+// crib in turn from the left of the dealer. A bid must be at least the minimum bid and then raise
+// the high bid by at least the minimum increment, so two players never hold the same bid, and may
+// not be more than the maximum bid. A player who passes is out of the auction. Once every other
+// player has passed, the last player left takes the crib and pays their bid by moving their pegs
+// back, though never past the start; when everyone passes without a bid the dealer keeps their crib
+// for nothing
+
+#[cfg(test)]
+mod test {
+    use super::process_bid;
+    use super::process_pass;
+    use crate::game_process_return;
+    use crate::settings::{AuctionRules, RuleVariant, VictorDealerOption};
+
+    // Returns a game in the Auction state where every player has the given score
+    fn set_up_auction(variant: RuleVariant, minimum_bid: u8, score: u8) -> crate::GameImpl {
+        let mut game = crate::GameImpl::new();
+        game.is_debug = true;
+        let victor_dealer_option = if crate::util::return_num_players_for_variant(variant) == 2 {
+            VictorDealerOption::TwoPlayers
+        } else {
+            VictorDealerOption::LosersDrawForDealer
+        };
+        let settings = crate::settings::GameSettings {
+            variant,
+            victor_dealer_option,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: Some(AuctionRules {
+                minimum_bid,
+                minimum_increment: 2,
+                maximum_bid: 12,
+            }),
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        };
+        crate::state_logic::game_start::game_setup(&mut game, settings).unwrap();
+        game.deck = crate::deck::Deck::new();
+        crate::state_logic::cut_initial::process_cut(&mut game).unwrap();
+        game.deck = crate::deck::Deck::new();
+        crate::state_logic::deal::process_deal(&mut game).unwrap();
+        crate::state_logic::sort::process_sort(&mut game).unwrap();
+        for player in &mut game.players {
+            player.back_peg_pos = score;
+            player.front_peg_pos = score;
+        }
+        game
+    }
+
+    #[test]
+    fn two_players() {
+        let mut game = set_up_auction(RuleVariant::TwoStandard, 1, 10);
+        let dealer = game.index_dealer.unwrap();
+        let pone = (dealer + 1) % 2;
+        assert_eq!(game.state, crate::GameState::Auction);
+        assert_eq!(game.index_active, Some(pone));
+
+        assert_eq!(
+            process_bid(&mut game, 3),
+            Ok(game_process_return::Success::Auction(
+                game_process_return::AuctionReturn::NextBidder
+            ))
+        );
+        assert_eq!(game.index_active, Some(dealer));
+        assert_eq!(game.auction.as_ref().unwrap().high_bid(), Some((pone, 3)));
+
+        // The dealer must raise by at least two and can't bid more than the maximum bid
+        assert_eq!(
+            process_bid(&mut game, 4),
+            Err(game_process_return::Error::AuctionError(
+                game_process_return::AuctionError::BidDoesNotRaiseTheHighBidByTheMinimumIncrement
+            ))
+        );
+        assert_eq!(
+            process_bid(&mut game, 13),
+            Err(game_process_return::Error::AuctionError(
+                game_process_return::AuctionError::BidIsMoreThanTheMaximumBid
+            ))
+        );
+        process_bid(&mut game, 5).unwrap();
+        process_bid(&mut game, 8).unwrap();
+
+        assert_eq!(
+            process_pass(&mut game),
+            Ok(game_process_return::Success::Auction(
+                game_process_return::AuctionReturn::CribTaken(pone, 8)
+            ))
+        );
+        assert_eq!(game.players[pone as usize].front_peg_pos, 2);
+        assert_eq!(game.players[dealer as usize].front_peg_pos, 10);
+        assert_eq!(game.index_dealer, Some(pone));
+        assert_eq!(game.auction.as_ref().unwrap().index_dealer(), dealer);
+        assert_eq!(game.state, crate::GameState::Discard);
+    }
+
+    #[test]
+    fn everyone_passes() {
+        let mut game = set_up_auction(RuleVariant::TwoStandard, 1, 10);
+        let dealer = game.index_dealer.unwrap();
+        assert_eq!(
+            process_pass(&mut game),
+            Ok(game_process_return::Success::Auction(
+                game_process_return::AuctionReturn::CribTaken(dealer, 0)
+            ))
+        );
+        assert_eq!(game.index_dealer, Some(dealer));
+        assert_eq!(game.players[dealer as usize].front_peg_pos, 10);
+    }
+
+    #[test]
+    fn minimum_bid() {
+        let mut game = set_up_auction(RuleVariant::TwoStandard, 3, 10);
+        assert_eq!(
+            process_bid(&mut game, 2),
+            Err(game_process_return::Error::AuctionError(
+                game_process_return::AuctionError::BidIsBelowTheMinimumBid
+            ))
+        );
+    }
+
+    // The auction of the first hand opens at 0-0 and the winner's pegs stay at the start
+    #[test]
+    fn first_hand() {
+        let mut game = set_up_auction(RuleVariant::TwoStandard, 1, 0);
+        let dealer = game.index_dealer.unwrap();
+        let pone = (dealer + 1) % 2;
+        process_bid(&mut game, 4).unwrap();
+        process_bid(&mut game, 12).unwrap();
+        assert_eq!(
+            process_pass(&mut game),
+            Ok(game_process_return::Success::Auction(
+                game_process_return::AuctionReturn::CribTaken(dealer, 12)
+            ))
+        );
+        assert_eq!(game.players[dealer as usize].front_peg_pos, 0);
+        assert_eq!(game.players[pone as usize].front_peg_pos, 0);
+        assert_eq!(game.state, crate::GameState::Discard);
+    }
+
+    // Players who have passed are skipped and partners lose the bid from their shared score
+    #[test]
+    fn turn_order_and_partners() {
+        let mut game = set_up_auction(RuleVariant::FourPairs, 1, 20);
+        let dealer = game.index_dealer.unwrap();
+        let seat = |offset: u8| (dealer + offset) % 4;
+
+        process_pass(&mut game).unwrap();
+        process_bid(&mut game, 2).unwrap();
+        process_bid(&mut game, 4).unwrap();
+        assert_eq!(game.index_active, Some(seat(0)));
+        process_pass(&mut game).unwrap();
+        assert!(game.auction.as_ref().unwrap().has_passed(seat(1)));
+        // The first to pass is skipped
+        assert_eq!(game.index_active, Some(seat(2)));
+        process_bid(&mut game, 7).unwrap();
+        assert_eq!(game.index_active, Some(seat(3)));
+        assert_eq!(
+            process_pass(&mut game),
+            Ok(game_process_return::Success::Auction(
+                game_process_return::AuctionReturn::CribTaken(seat(2), 7)
+            ))
+        );
+        assert_eq!(game.players[seat(2) as usize].front_peg_pos, 13);
+        assert_eq!(game.players[seat(0) as usize].front_peg_pos, 13);
+        assert_eq!(game.players[seat(1) as usize].front_peg_pos, 20);
+    }
+
+    // After the crib is scored the deal passes to the left of the player who dealt
+    #[test]
+    fn deal_passes_from_dealer() {
+        let mut game = set_up_auction(RuleVariant::ThreeStandard, 1, 20);
+        let dealer = game.index_dealer.unwrap();
+        process_bid(&mut game, 5).unwrap();
+        process_pass(&mut game).unwrap();
+        process_pass(&mut game).unwrap();
+        assert_eq!(game.index_dealer, Some((dealer + 1) % 3));
+
+        game.crib = game.players[0].hand[..4].to_vec();
+        game.starter_card = Some(game.players[1].hand[0]);
+        game.state = crate::GameState::CribScore;
+        crate::state_logic::crib::process_crib_score(&mut game, None).unwrap();
+        assert_eq!(game.state, crate::GameState::Deal);
+        assert_eq!(game.index_dealer, Some((dealer + 1) % 3));
+        assert_eq!(game.auction, None);
+    }
+}
+
+pub(crate) fn process_bid(
+    game: &mut crate::GameImpl,
+    bid: u8,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let rules = match game.settings.and_then(|settings| settings.auction) {
+        Some(rules) => rules,
+        None => {
+            return Err(game_process_return::Error::ImplementationError(
+                game_process_return::ImplError::NoConfig,
+            ))
+        }
+    };
+    let index_active = game.index_active.unwrap();
+
+    match game.auction.as_ref().unwrap().high_bid {
+        None if bid < rules.minimum_bid => {
+            return Err(game_process_return::Error::AuctionError(
+                game_process_return::AuctionError::BidIsBelowTheMinimumBid,
+            ));
+        }
+        Some((_, high_bid))
+            if u16::from(bid) < u16::from(high_bid) + u16::from(rules.minimum_increment) =>
+        {
+            return Err(game_process_return::Error::AuctionError(
+                game_process_return::AuctionError::BidDoesNotRaiseTheHighBidByTheMinimumIncrement,
+            ));
+        }
+        _ => {}
+    }
+    if bid > rules.maximum_bid {
+        return Err(game_process_return::Error::AuctionError(
+            game_process_return::AuctionError::BidIsMoreThanTheMaximumBid,
+        ));
+    }
+
+    game.auction.as_mut().unwrap().high_bid = Some((index_active, bid));
+    move_to_next_bidder(game);
+
+    Ok(game_process_return::Success::Auction(
+        game_process_return::AuctionReturn::NextBidder,
+    ))
+}
+
+pub(crate) fn process_pass(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let auction = game.auction.as_mut().unwrap();
+    auction.has_passed[game.index_active.unwrap() as usize] = true;
+
+    let remaining: Vec<u8> = (0..auction.has_passed.len() as u8)
+        .filter(|index| !auction.has_passed[*index as usize])
+        .collect();
+    if remaining.len() > 1 {
+        move_to_next_bidder(game);
+        return Ok(game_process_return::Success::Auction(
+            game_process_return::AuctionReturn::NextBidder,
+        ));
+    }
+
+    // The high bidder is never active while they hold the high bid so they are the last player
+    // left, and without a bid the last player left is the dealer who bids last
+    let (index_winner, bid) = match auction.high_bid {
+        Some(high_bid) => high_bid,
+        None => (remaining[0], 0),
+    };
+    util::process_move_back(game, index_winner as usize, bid);
+    game.index_dealer = Some(index_winner);
+    util::set_state(game, crate::GameState::Discard);
+
+    Ok(game_process_return::Success::Auction(
+        game_process_return::AuctionReturn::CribTaken(index_winner, bid),
+    ))
+}
+
+// Moves the index_active to the next player to the left who has not passed
+fn move_to_next_bidder(game: &mut crate::GameImpl) {
+    let auction = game.auction.as_ref().unwrap();
+    let num_players = game.players.len() as u8;
+    let mut index_next = game.index_active.unwrap();
+    loop {
+        index_next = (index_next + 1) % num_players;
+        if !auction.has_passed[index_next as usize] {
+            break;
+        }
+    }
+    game.index_active = Some(index_next);
+}
@@ -0,0 +1,220 @@
+use crate::game_process_return;
+use crate::score;
+use crate::util;
+
+// States that sort the crib after a Confirmation event and then score it for the dealer
+// automatically with a Confirmation event or manually with a ManScoreSelection event; once the
+// crib is scored the deal passes to the left and the game returns to the Deal state, by way of
+// CribMuggins when the crib is counted manually with muggins
+
+#[cfg(test)]
+mod test {
+    use super::super::play::test_util::set_up_game;
+    use super::process_crib_muggins;
+    use super::process_crib_score;
+    use super::process_crib_sort;
+    use crate::game_process_return;
+    use crate::settings::RuleVariant;
+    use crate::util::return_card;
+
+    fn set_up_crib(variant: RuleVariant, is_manual: bool) -> crate::GameImpl {
+        let mut game = set_up_game(variant, is_manual, false, false);
+        game.crib = vec![
+            return_card('T', 'H'),
+            return_card('2', 'H'),
+            return_card('8', 'H'),
+            return_card('4', 'H'),
+        ];
+        game.starter_card = Some(return_card('6', 'S'));
+        game.index_dealer = Some(0);
+        game.index_active = Some(0);
+        game.state = crate::GameState::CribSort;
+        game
+    }
+
+    #[test]
+    fn sort() {
+        let mut game = set_up_crib(RuleVariant::TwoStandard, false);
+        assert_eq!(
+            process_crib_sort(&mut game),
+            Ok(game_process_return::Success::CribSort)
+        );
+        assert_eq!(
+            game.crib,
+            vec![
+                return_card('2', 'H'),
+                return_card('4', 'H'),
+                return_card('8', 'H'),
+                return_card('T', 'H'),
+            ]
+        );
+        assert_eq!(game.state, crate::GameState::CribScore);
+    }
+
+    // Every card is even so there are no fifteens and the four card flush does not count in the
+    // crib; then the deal passes to the next player
+    #[test]
+    fn automatic_score_and_pass_deal() {
+        for variant in crate::util::return_variants() {
+            let mut game = set_up_crib(variant, false);
+            process_crib_sort(&mut game).unwrap();
+            let score_before = game.players[0].front_peg_pos;
+
+            assert_eq!(
+                process_crib_score(&mut game, None),
+                Ok(game_process_return::Success::CribScore(vec![]))
+            );
+            assert_eq!(game.players[0].front_peg_pos, score_before);
+            assert_eq!(game.state, crate::GameState::Deal);
+            assert_eq!(game.index_dealer, Some(1));
+        }
+    }
+
+    #[test]
+    fn manual_underpegged() {
+        let mut game = set_up_crib(RuleVariant::TwoStandard, true);
+        game.starter_card = Some(return_card('5', 'S'));
+        process_crib_sort(&mut game).unwrap();
+        let optimal =
+            crate::score::show::score_hand(0, &game.crib.clone(), return_card('5', 'S'), true);
+        assert_eq!(
+            process_crib_score(&mut game, Some(vec![])),
+            Err(game_process_return::Error::CribScoreError(
+                game_process_return::ShowScoreError::UnderpeggingIsNotAllowed(
+                    crate::score::manual::ScoreDiff {
+                        overclaimed: vec![],
+                        missed: optimal,
+                        claimed_points: 0,
+                        optimal_points: 4,
+                    }
+                )
+            ))
+        );
+        assert_eq!(game.state, crate::GameState::CribScore);
+    }
+
+    #[test]
+    fn manual_underpegged_when_allowed() {
+        let mut game = set_up_crib(RuleVariant::TwoStandard, true);
+        game.settings.as_mut().unwrap().is_underpegging = true;
+        game.starter_card = Some(return_card('5', 'S'));
+        process_crib_sort(&mut game).unwrap();
+
+        assert_eq!(
+            process_crib_score(&mut game, Some(vec![])),
+            Ok(game_process_return::Success::CribScore(vec![]))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 0);
+        assert_eq!(game.state, crate::GameState::Deal);
+    }
+
+    // The pone calls the fifteens the dealer left in the crib before the deal passes
+    #[test]
+    fn manual_muggins_called_then_pass_deal() {
+        let mut game = set_up_crib(RuleVariant::TwoStandard, true);
+        game.settings.as_mut().unwrap().is_underpegging = true;
+        game.settings.as_mut().unwrap().is_muggins = true;
+        game.starter_card = Some(return_card('5', 'S'));
+        process_crib_sort(&mut game).unwrap();
+
+        process_crib_score(&mut game, Some(vec![])).unwrap();
+        assert_eq!(game.state, crate::GameState::CribMuggins);
+        let called: Vec<crate::score::ScoreEvent> = game
+            .missed_score_events
+            .iter()
+            .map(|score_event| crate::score::ScoreEvent {
+                player_index: 1,
+                ..score_event.clone()
+            })
+            .collect();
+
+        assert_eq!(
+            process_crib_muggins(&mut game, Some(called.clone())),
+            Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Called(called)
+            ))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 0);
+        assert_eq!(game.players[1].front_peg_pos, 4);
+        assert!(game.missed_score_events.is_empty());
+        assert_eq!(game.state, crate::GameState::CribMuggins);
+
+        process_crib_muggins(&mut game, None).unwrap();
+        assert_eq!(game.state, crate::GameState::Deal);
+        assert_eq!(game.index_dealer, Some(1));
+    }
+}
+
+pub(crate) fn process_crib_sort(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    game.crib.sort();
+    util::set_state(game, crate::GameState::CribScore);
+    Ok(game_process_return::Success::CribSort)
+}
+
+pub(crate) fn process_crib_score(
+    game: &mut crate::GameImpl,
+    selections: Option<Vec<score::ScoreEvent>>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let index_dealer = game.index_dealer.unwrap();
+
+    let (scoring, missed) = super::show::check_selections(
+        game,
+        selections,
+        optimal_scoring(game),
+        game_process_return::Error::CribScoreError,
+    )?;
+
+    if !util::process_score_events(game, index_dealer as usize, &scoring) {
+        if super::show::is_muggins_called(game) {
+            game.missed_score_events = missed;
+            util::set_state(game, crate::GameState::CribMuggins);
+        } else {
+            finish_crib_count(game);
+        }
+    }
+
+    Ok(game_process_return::Success::CribScore(scoring))
+}
+
+// Processes a call of muggins on the crib, or passes the deal with Muggins(None)
+pub(crate) fn process_crib_muggins(
+    game: &mut crate::GameImpl,
+    selections: Option<Vec<score::ScoreEvent>>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    match selections {
+        Some(selections) => super::show::call_muggins(game, game.index_dealer.unwrap(), selections),
+        None => {
+            game.missed_score_events.clear();
+            finish_crib_count(game);
+            Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Complete,
+            ))
+        }
+    }
+}
+
+// Passes the deal to the left once the crib has been counted
+fn finish_crib_count(game: &mut crate::GameImpl) {
+    // The winner of an auction takes the crib for the hand but the deal still passes to the left
+    // of the player who dealt
+    let index_dealt = match game.auction.take() {
+        Some(auction) => auction.index_dealer,
+        None => game.index_dealer.unwrap(),
+    };
+    game.last_hand = crate::view::hand_reveal(game);
+    game.index_dealer = Some((index_dealt + 1) % game.players.len() as u8);
+    util::set_state(game, crate::GameState::Deal);
+}
+
+// Returns the full count of the crib for the dealer
+pub(crate) fn optimal_scoring(game: &crate::GameImpl) -> Vec<score::ScoreEvent> {
+    score::show::score_hand_with_rules(
+        game.index_dealer.unwrap(),
+        &game.crib,
+        game.starter_card.unwrap(),
+        true,
+        &game.settings.unwrap().scoring_rules,
+    )
+}
@@ -29,6 +29,12 @@ mod test {
             is_underpegging: false,
             is_muggins: false,
             is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
         };
 
         crate::state_logic::game_start::game_setup(&mut game, settings).unwrap();
@@ -187,6 +193,38 @@ mod test {
             assert_eq!(game.players[index_loser].front_peg_pos, 3);
         }
     }
+
+    // A joker is cut high so it loses to any other card and two jokers tie
+    #[test]
+    fn process_cut_with_jokers() {
+        let mut game = set_up_game(2, crate::settings::RuleVariant::TwoStandard);
+        game.deck = crate::deck::Deck::from_vec(vec![
+            crate::deck::RED_JOKER,
+            crate::util::return_card('K', 'H'),
+        ]);
+        assert_eq!(
+            super::process_cut(&mut game),
+            Ok(super::game_process_return::Success::InitialCut(
+                super::game_process_return::InitialCutReturn::DealerChosen
+            ))
+        );
+        assert!(game.players[1].hand[0].is_joker());
+        assert_eq!(game.index_dealer, Some(0));
+
+        let mut game = set_up_game(2, crate::settings::RuleVariant::TwoStandard);
+        game.deck =
+            crate::deck::Deck::from_vec(vec![crate::deck::RED_JOKER, crate::deck::BLACK_JOKER]);
+        assert_eq!(
+            super::process_cut(&mut game),
+            Ok(super::game_process_return::Success::InitialCut(
+                super::game_process_return::InitialCutReturn::CutTie
+            ))
+        );
+        assert_eq!(
+            game.initial_cut_between_players_with_these_indices,
+            vec![0, 1]
+        );
+    }
 }
 
 pub(crate) fn process_cut(
@@ -231,14 +269,11 @@ fn set_dealer(index: u8, game: &mut crate::GameImpl) -> game_process_return::Suc
     // If the rules variant is TwoFiveCard, then award the loser of the cut three points
     if let Some(settings) = game.settings {
         if settings.variant == crate::settings::RuleVariant::TwoFiveCard {
-            if game.index_dealer == Some(0) {
-                game.players[1].change_score(3);
-            } else {
-                game.players[0].change_score(3);
-            }
+            let index_loser = if index == 0 { 1 } else { 0 };
+            crate::util::process_score(game, index_loser, 3);
         }
     }
-    game.state = crate::GameState::Deal;
+    crate::util::set_state(game, crate::GameState::Deal);
     game_process_return::Success::InitialCut(game_process_return::InitialCutReturn::DealerChosen)
 }
 
@@ -261,9 +296,10 @@ fn deal_card_to_valid_players(game: &mut crate::GameImpl) {
 
 // Takes an immutable reference to a  vector of players and a immutable reference to a Vec<u8> of
 // player indices who the cut is between and returns a new Vec<u8> with the reduced set of indices
-fn compare_cards(players: &Vec<player::Player>, indices: &Vec<u8>) -> Vec<u8> {
-    // The highest value is 13/King so this function will work with any valid cards
-    let mut lowest_value = 14;
+fn compare_cards(players: &[player::Player], indices: &[u8]) -> Vec<u8> {
+    // A joker ranks above a King when cutting so the highest value is 14 and this function will
+    // work with any valid cards
+    let mut lowest_value = 15;
     // At most four players will tie when the cut is between four or more players as there are four
     // cards of each suit, otherwise it'll be at most the number of players the cut is between
     let mut player_indices_with_cuts_of_lowest_values: Vec<u8> =
@@ -272,7 +308,7 @@ fn compare_cards(players: &Vec<player::Player>, indices: &Vec<u8>) -> Vec<u8> {
     for (index, player) in players.iter().enumerate() {
         if indices.contains(&(index as u8)) {
             // For every player who the cut is between
-            let value = deck::return_value(player.hand[0]);
+            let value = cut_value(player.hand[0]);
             // If their cut is the new lowest, make it known and set the vector tracking indices to
             // just the index of this player
             if value < lowest_value {
@@ -288,3 +324,13 @@ fn compare_cards(players: &Vec<player::Player>, indices: &Vec<u8>) -> Vec<u8> {
 
     player_indices_with_cuts_of_lowest_values
 }
+
+// Returns the rank of a card cut for first deal where the lowest card wins; a joker is wild in the
+// show but has no rank of its own, so it is cut high and never wins the deal
+fn cut_value(card: deck::Card) -> u8 {
+    if card.is_joker() {
+        14
+    } else {
+        deck::return_value(card)
+    }
+}
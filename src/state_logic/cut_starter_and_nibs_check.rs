@@ -19,6 +19,12 @@ mod test {
             is_underpegging: is_under,
             is_muggins: is_mug,
             is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
         };
 
         crate::state_logic::game_start::game_setup(&mut game, settings).unwrap();
@@ -94,7 +100,7 @@ mod test {
         fn process_cut_nibs_and_win_no_partner() {
             let mut game = super::set_up_game(false, false, false, false);
             game.deck = crate::deck::Deck::from_vec(vec![crate::util::return_card('J', 'H')]);
-            game.players[0].change_score(120);
+            game.players[0].change_score(120, 121);
 
             assert_eq!(
                 super::super::process_cut(&mut game),
@@ -103,7 +109,7 @@ mod test {
                 ))
             );
             assert_eq!(game.starter_card, Some(crate::util::return_card('J', 'H')));
-            assert_eq!(game.players[0].front_peg_pos, 122);
+            assert_eq!(game.players[0].front_peg_pos, 121);
             assert_eq!(game.index_active, Some(1));
             assert_eq!(game.play_groups.len(), 1);
             assert_eq!(game.play_groups[0].cards.len(), 0);
@@ -136,8 +142,8 @@ mod test {
         fn process_cut_nibs_and_win_with_partner() {
             let mut game = super::set_up_game(true, false, false, false);
             game.deck = crate::deck::Deck::from_vec(vec![crate::util::return_card('J', 'H')]);
-            game.players[0].change_score(120);
-            game.players[1].change_score(120);
+            game.players[0].change_score(120, 121);
+            game.players[1].change_score(120, 121);
             assert_eq!(
                 super::super::process_cut(&mut game),
                 Ok(crate::game_process_return::Success::StarterCut(
@@ -145,8 +151,8 @@ mod test {
                 ))
             );
             assert_eq!(game.starter_card, Some(crate::util::return_card('J', 'H')));
-            assert_eq!(game.players[0].front_peg_pos, 122);
-            assert_eq!(game.players[1].front_peg_pos, 122);
+            assert_eq!(game.players[0].front_peg_pos, 121);
+            assert_eq!(game.players[1].front_peg_pos, 121);
             assert_eq!(game.index_active, Some(1));
             assert_eq!(game.play_groups.len(), 1);
             assert_eq!(game.play_groups[0].cards.len(), 0);
@@ -245,7 +251,7 @@ mod test {
             let mut game = set_up_game(false, true, false, false);
             process_cut(&mut game).unwrap();
             game.starter_card = Some(crate::util::return_card('J', 'H'));
-            game.players[0].change_score(119);
+            game.players[0].change_score(119, 121);
 
             let score_event = crate::score::ScoreEvent {
                 score_type: crate::score::ScoreType::Play(crate::score::PlayScoreType::Nibs),
@@ -312,8 +318,8 @@ mod test {
             let mut game = set_up_game(true, true, false, false);
             process_cut(&mut game).unwrap();
             game.starter_card = Some(crate::util::return_card('J', 'H'));
-            game.players[0].change_score(119);
-            game.players[1].change_score(119);
+            game.players[0].change_score(119, 121);
+            game.players[1].change_score(119, 121);
 
             let score_event = crate::score::ScoreEvent {
                 score_type: crate::score::ScoreType::Play(crate::score::PlayScoreType::Nibs),
@@ -342,25 +348,31 @@ pub(crate) fn process_cut(
 
     game.starter_card = Some(game.deck.deal());
 
-    // If the cut card is a jack, the dealer scores two points
+    // If the cut card is a jack, the dealer scores two points for his heels unless the ScoringRules
+    // leave it out for the variant
     if !game.settings.unwrap().is_manual_scoring {
-        if crate::deck::return_value(game.starter_card.unwrap()) == 11 {
+        if game.settings.unwrap().is_heels(game.starter_card.unwrap()) {
             starter_cut_return = game_process_return::StarterCutReturn::AutoNibs;
 
             let dealer_index: usize = game.index_dealer.unwrap() as usize;
-            // process_score adds points to the dealer and their partner if they have one; if it
-            // returns true then it has set the state to Win and if it returns false continue the
+            let nibs = crate::score::ScoreEvent {
+                score_type: crate::score::ScoreType::Play(crate::score::PlayScoreType::Nibs),
+                player_index: dealer_index as u8,
+                point_value: 2,
+            };
+            // process_score_events adds points to the dealer and their partner if they have one; if
+            // it returns true then it has set the state to Win and if it returns false continue the
             // game by setting the state to PlayWaitForCard
-            if !crate::util::process_score(game, dealer_index, 2) {
-                game.state = crate::GameState::PlayWaitForCard;
+            if !crate::util::process_score_events(game, dealer_index, &[nibs]) {
+                crate::util::set_state(game, crate::GameState::PlayWaitForCard);
             }
         } else {
             starter_cut_return = game_process_return::StarterCutReturn::AutoNoNibs;
-            game.state = crate::GameState::PlayWaitForCard;
+            crate::util::set_state(game, crate::GameState::PlayWaitForCard);
         }
     } else {
         starter_cut_return = game_process_return::StarterCutReturn::ManualScoring;
-        game.state = crate::GameState::NibsCheck;
+        crate::util::set_state(game, crate::GameState::NibsCheck);
     }
 
     // Set the index_active to the pone to the left (I guess, the pone to the
@@ -385,7 +397,7 @@ pub(crate) fn process_nibs(
     call: Option<crate::score::ScoreEvent>,
 ) -> Result<game_process_return::Success, game_process_return::Error> {
     if let Some(starter) = game.starter_card {
-        if crate::deck::return_value(starter) == 11 {
+        if game.settings.unwrap().is_heels(starter) {
             // If the starter card is a jack and if there is no call, throw an error if
             // underscoring is disabled or allow the None call
             if call.is_none() {
@@ -394,7 +406,7 @@ pub(crate) fn process_nibs(
                         game_process_return::NibsError::NoNibsCallWhenUnderscoringIsDisabled,
                     ))
                 } else {
-                    game.state = crate::GameState::PlayWaitForCard;
+                    crate::util::set_state(game, crate::GameState::PlayWaitForCard);
                     Ok(game_process_return::Success::NibsCheck(
                         game_process_return::NibsCheckReturn::NoNibs,
                     ))
@@ -403,18 +415,15 @@ pub(crate) fn process_nibs(
             // If the starter card is a jack and there is a Nibs call, check that the score event
             // passed is valid and either throw an error or proceed to the next state
             else {
-                if call
-                    == Some(crate::score::ScoreEvent {
-                        score_type: crate::score::ScoreType::Play(
-                            crate::score::PlayScoreType::Nibs,
-                        ),
-                        player_index: game.index_dealer.unwrap(),
-                        point_value: 2,
-                    })
-                {
+                let nibs = crate::score::ScoreEvent {
+                    score_type: crate::score::ScoreType::Play(crate::score::PlayScoreType::Nibs),
+                    player_index: game.index_dealer.unwrap(),
+                    point_value: 2,
+                };
+                if call.as_ref() == Some(&nibs) {
                     let index_dealer = game.index_dealer.unwrap() as usize;
-                    if !crate::util::process_score(game, index_dealer, 2) {
-                        game.state = crate::GameState::PlayWaitForCard;
+                    if !crate::util::process_score_events(game, index_dealer, &[nibs]) {
+                        crate::util::set_state(game, crate::GameState::PlayWaitForCard);
                     }
                     Ok(game_process_return::Success::NibsCheck(
                         game_process_return::NibsCheckReturn::Nibs,
@@ -429,7 +438,7 @@ pub(crate) fn process_nibs(
             // If the card is not a jack and there is no call, do nothing but proceed to the next
             // state
             if call.is_none() {
-                game.state = crate::GameState::PlayWaitForCard;
+                crate::util::set_state(game, crate::GameState::PlayWaitForCard);
                 Ok(game_process_return::Success::NibsCheck(
                     game_process_return::NibsCheckReturn::NoNibs,
                 ))
@@ -18,12 +18,18 @@ mod test {
         };
 
         let settings = crate::settings::GameSettings {
-            variant: variant,
+            variant,
             victor_dealer_option: vdo,
             is_manual_scoring: false,
             is_underpegging: false,
             is_muggins: false,
             is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
         };
 
         crate::state_logic::game_start::game_setup(&mut game, settings).unwrap();
@@ -167,6 +173,29 @@ mod test {
         assert_eq!(game.players[4].hand.len(), 5);
         assert_eq!(game.players[5].hand.len(), 5);
     }
+
+    // The dealer of this custom variant receives an extra card and discards an extra card, and a
+    // card is dealt to the crib from the deck
+    #[test]
+    fn process_deal_custom() {
+        let variant = crate::settings::CustomVariant::new(3)
+            .seat(0, 7, 3)
+            .crib_cards_from_deck(1)
+            .build()
+            .unwrap();
+        let mut game = set_up_game(variant);
+        let index_dealer = game.index_dealer.unwrap() as usize;
+
+        assert_eq!(
+            super::process_deal(&mut game),
+            Ok(super::game_process_return::Success::Deal)
+        );
+
+        for (index, player) in game.players.iter().enumerate() {
+            assert_eq!(player.hand.len(), if index == index_dealer { 7 } else { 6 });
+        }
+        assert_eq!(game.crib.len(), 1);
+    }
 }
 
 pub(crate) fn process_deal(
@@ -184,21 +213,18 @@ pub(crate) fn process_deal(
 
     game.crib.clear();
 
-    if let Some(settings) = game.settings {
-        match settings.variant {
-            crate::settings::RuleVariant::TwoStandard => deal_two(6, game),
-            crate::settings::RuleVariant::TwoFiveCard => deal_two(5, game),
-            crate::settings::RuleVariant::TwoSevenCard => deal_two(7, game),
-            crate::settings::RuleVariant::ThreeStandard
-            | crate::settings::RuleVariant::ThreeCaptain => deal_three(game),
-            crate::settings::RuleVariant::FourIndividual
-            | crate::settings::RuleVariant::FourPairs => deal_four(game),
-            crate::settings::RuleVariant::FiveStandard => deal_five(game),
-            crate::settings::RuleVariant::SixPairs => deal_six(game),
-        };
+    if let (Some(settings), Some(index_dealer)) = (game.settings, game.index_dealer) {
+        deal_hands(settings.variant.preset(), index_dealer, game);
     }
+    let hands = game
+        .players
+        .iter()
+        .map(|player| player.hand.clone())
+        .collect::<Vec<Vec<crate::deck::Card>>>();
+    let crib = game.crib.clone();
+    crate::observer::notify(game, |observer| observer.on_cards_dealt(&hands, &crib));
 
-    game.state = crate::GameState::Sort;
+    crate::util::set_state(game, crate::GameState::Sort);
     Ok(game_process_return::Success::Deal)
 }
 
@@ -206,61 +232,24 @@ fn push_card_to_hand(index: u8, game: &mut crate::GameImpl) {
     game.players[index as usize].hand.push(game.deck.deal());
 }
 
-fn deal_two(cards: u8, game: &mut crate::GameImpl) {
-    for _card in 0..cards {
-        for player in 0..2 {
-            push_card_to_hand(player, game);
-        }
-    }
-
-    // In seven card cribbage, deal one card to the crib
-    if cards == 7 {
-        game.crib.push(game.deck.deal());
-    }
-}
-
-fn deal_three(game: &mut crate::GameImpl) {
-    for _card in 0..5 {
-        for player in 0..3 {
-            push_card_to_hand(player, game);
-        }
-    }
-
-    game.crib.push(game.deck.deal());
-}
-
-fn deal_four(game: &mut crate::GameImpl) {
-    for _card in 0..5 {
-        for player in 0..4 {
-            push_card_to_hand(player, game);
-        }
-    }
-}
-
-fn deal_five(game: &mut crate::GameImpl) {
-    // With five players the dealer receives only four cards
-    if let Some(index_dealer) = game.index_dealer {
-        for card in 0..5 {
-            for player in 0..5 {
-                if card != 4 || player != index_dealer {
-                    push_card_to_hand(player, game);
-                }
+// Deals one card at a time around the table, skipping any player who already has every card they
+// are dealt such as the dealer with five players, and then deals any cards to the crib from the
+// deck as in three handed and seven card cribbage
+fn deal_hands(
+    preset: crate::settings::CustomVariant,
+    index_dealer: u8,
+    game: &mut crate::GameImpl,
+) {
+    let most_cards_dealt = preset.cards_dealt.iter().max().copied().unwrap_or(0);
+    for card in 0..most_cards_dealt {
+        for player in 0..preset.num_players {
+            if card < preset.cards_dealt_to(player, index_dealer) {
+                push_card_to_hand(player, game);
             }
         }
     }
-}
 
-fn deal_six(game: &mut crate::GameImpl) {
-    // With six players the dealer and their partner receives only four cards
-    if let Some(index_dealer) = game.index_dealer {
-        if let Some(index_partner) = game.players[index_dealer as usize].partner_index {
-            for card in 0..5 {
-                for player in 0..6 {
-                    if card != 4 || (player != index_dealer && player != index_partner) {
-                        push_card_to_hand(player, game);
-                    }
-                }
-            }
-        }
+    for _card in 0..preset.crib_cards_from_deck {
+        game.crib.push(game.deck.deal());
     }
 }
@@ -18,12 +18,18 @@ mod test {
         };
 
         let settings = crate::settings::GameSettings {
-            variant: variant,
+            variant,
             victor_dealer_option: vdo,
             is_manual_scoring: false,
             is_underpegging: false,
             is_muggins: false,
             is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
         };
 
         crate::state_logic::game_start::game_setup(&mut game, settings).unwrap();
@@ -223,7 +229,7 @@ mod test {
 
     #[test]
     fn three_player_discard_validity() {
-        for variant in vec![crate::settings::RuleVariant::ThreeStandard, crate::settings::RuleVariant::ThreeCaptain] {
+        for variant in [crate::settings::RuleVariant::ThreeStandard, crate::settings::RuleVariant::ThreeCaptain] {
             let mut game = set_up_game(variant);
 
             // Test invalid number of inner vectors
@@ -271,7 +277,7 @@ mod test {
             let discard_indices_group: Vec<Vec<u8>> = vec![vec![0], vec![0], vec![0]];
             // The card dealt directly to the crib should be a three of diamonds with the sorted
             // debug deck
-            let mut expected_discards: Vec<crate::deck::Card>  = vec![
+            let expected_discards: Vec<crate::deck::Card>  = vec![
                 crate::util::return_card('3', 'D'),
                 game.players[0].hand[0],
                 game.players[1].hand[0], 
@@ -285,7 +291,7 @@ mod test {
 
     #[test]
     fn four_player_discard_validity() {
-        for variant in vec![crate::settings::RuleVariant::FourIndividual, crate::settings::RuleVariant::FourPairs] {
+        for variant in [crate::settings::RuleVariant::FourIndividual, crate::settings::RuleVariant::FourPairs] {
 
             let mut game = set_up_game(variant);
 
@@ -404,6 +410,46 @@ mod test {
         assert_eq!(super::process_discard(&mut game, discard_indices_group), Ok(super::game_process_return::Success::Discard));
         assert_eq!(game.crib, expected_discards);
     }
+
+    // With a custom variant the number of discards depends on the seat; here the dealer is dealt
+    // seven cards and discards three while the other players are dealt six and discard two
+    #[test]
+    fn custom_discard_validity() {
+        let variant = crate::settings::CustomVariant::new(3)
+            .seat(0, 7, 3)
+            .build()
+            .unwrap();
+        let mut game = set_up_game(variant);
+        let index_dealer = game.index_dealer.unwrap() as usize;
+        let others: Vec<usize> = (0..3).filter(|index| *index != index_dealer).collect();
+
+        // Test invalid number of indices, repeated indices and indices outside of the hand
+        let mut discard_indices_group: Vec<Vec<u8>> = vec![vec![]; 3];
+        discard_indices_group[index_dealer] = vec![0, 1];
+        discard_indices_group[others[0]] = vec![2, 2];
+        discard_indices_group[others[1]] = vec![0, 6];
+        let mut expected_output: Vec<(usize, super::game_process_return::DiscardError)> = vec![
+            (index_dealer, super::game_process_return::DiscardError::WrongNumberOfCardsAreDiscardedWithThisCustomVariant(index_dealer as u8)),
+            (others[0], super::game_process_return::DiscardError::CardIndicesMayNotBeRepeated(others[0] as u8)),
+            (others[1], super::game_process_return::DiscardError::IndicesAreWithinTheHandWithThisCustomVariant(others[1] as u8)),
+        ];
+        expected_output.sort_by_key(|(index, _)| *index);
+        assert_eq!(
+            super::process_discard(&mut game, discard_indices_group),
+            Err(super::game_process_return::Error::DiscardErrors(
+                expected_output.into_iter().map(|(_, error)| error).collect()
+            ))
+        );
+
+        // Test valid config
+        let mut discard_indices_group: Vec<Vec<u8>> = vec![vec![0, 5]; 3];
+        discard_indices_group[index_dealer] = vec![0, 1, 6];
+        assert_eq!(super::process_discard(&mut game, discard_indices_group), Ok(super::game_process_return::Success::Discard));
+        assert_eq!(game.crib.len(), 7);
+        for player in &game.players {
+            assert_eq!(player.hand.len(), 4);
+        }
+    }
 }
 
 pub(crate) fn process_discard(
@@ -412,7 +458,7 @@ pub(crate) fn process_discard(
 ) -> Result<game_process_return::Success, game_process_return::Error> {
     match check_discard_validity(game, &discard_indices_group) {
         Ok(discard_errors) => {
-            if discard_errors.len() > 0 {
+            if !discard_errors.is_empty() {
                 Err(game_process_return::Error::DiscardErrors(discard_errors))
             } else {
                 execute_discard(game, discard_indices_group);
@@ -424,9 +470,33 @@ pub(crate) fn process_discard(
     }
 }
 
+// Holds the discards of a single player until every player has chosen theirs, after which all of
+// them are placed in the crib at once; a player may change their discards until then
+pub(crate) fn process_player_discard(
+    game: &mut crate::GameImpl,
+    player_index: u8,
+    discards: Vec<u8>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    if let Some(error) = check_player_discard_validity(game, player_index, &discards)? {
+        return Err(game_process_return::Error::DiscardErrors(vec![error]));
+    }
+
+    if game.held_discards.len() != game.players.len() {
+        game.held_discards = vec![None; game.players.len()];
+    }
+    game.held_discards[player_index as usize] = Some(discards);
+    if game.held_discards.iter().any(Option::is_none) {
+        return Ok(game_process_return::Success::DiscardHeld);
+    }
+
+    let discard_indices_group = game.held_discards.drain(..).flatten().collect();
+    execute_discard(game, discard_indices_group);
+    Ok(game_process_return::Success::Discard)
+}
+
 fn check_discard_validity(
     game: &mut crate::GameImpl,
-    discard_indices_group: &Vec<Vec<u8>>,
+    discard_indices_group: &[Vec<u8>],
 ) -> Result<Vec<game_process_return::DiscardError>, game_process_return::Error> {
     // There should be one discard_indices vector for each player
     if discard_indices_group.len() != game.players.len() {
@@ -437,61 +507,73 @@ fn check_discard_validity(
 
     let mut errors: Vec<game_process_return::DiscardError> = Vec::new();
     for (player_index, discards) in discard_indices_group.iter().enumerate() {
-        if let Some(settings) = game.settings {
-            let error: Option<game_process_return::DiscardError> = match settings.variant {
-                crate::settings::RuleVariant::TwoStandard => {
-                    check_two_player_validity(player_index as u8, discards, 6)
-                }
-                crate::settings::RuleVariant::TwoFiveCard => {
-                    check_two_player_validity(player_index as u8, discards, 5)
-                }
-                crate::settings::RuleVariant::TwoSevenCard => {
-                    check_two_player_validity(player_index as u8, discards, 7)
-                }
-                crate::settings::RuleVariant::ThreeStandard => {
-                    check_three_or_four_player_validity(player_index as u8, discards, 3)
-                }
-                crate::settings::RuleVariant::ThreeCaptain => {
-                    check_three_or_four_player_validity(player_index as u8, discards, 3)
-                }
-                crate::settings::RuleVariant::FourIndividual => {
-                    check_three_or_four_player_validity(player_index as u8, discards, 4)
-                }
-                crate::settings::RuleVariant::FourPairs => {
-                    check_three_or_four_player_validity(player_index as u8, discards, 4)
-                }
-                crate::settings::RuleVariant::FiveStandard => check_five_player_validity(
-                    player_index as u8,
-                    discards,
-                    game.index_dealer.unwrap(),
-                ),
-                crate::settings::RuleVariant::SixPairs => check_six_player_validity(
-                    player_index as u8,
-                    discards,
-                    game.index_dealer.unwrap(),
-                    game.players[game.index_dealer.unwrap() as usize]
-                        .partner_index
-                        .unwrap(),
-                ),
-            };
+        if let Some(error) = check_player_discard_validity(game, player_index as u8, discards)? {
+            errors.push(error);
+        }
+    }
+    Ok(errors)
+}
 
-            if let Some(error) = error {
-                errors.push(error);
-            }
-        } else {
-            // Game should always have a valid settings config if the interface is used properly,
-            // but who knows?
+fn check_player_discard_validity(
+    game: &crate::GameImpl,
+    player_index: u8,
+    discards: &[u8],
+) -> Result<Option<game_process_return::DiscardError>, game_process_return::Error> {
+    let settings = match game.settings {
+        Some(settings) => settings,
+        // Game should always have a valid settings config if the interface is used properly, but
+        // who knows?
+        None => {
             return Err(game_process_return::Error::ImplementationError(
                 game_process_return::ImplError::NoConfig,
-            ));
+            ))
         }
-    }
-    Ok(errors)
+    };
+    Ok(match settings.variant {
+        crate::settings::RuleVariant::TwoStandard => {
+            check_two_player_validity(player_index, discards, 6)
+        }
+        crate::settings::RuleVariant::TwoFiveCard => {
+            check_two_player_validity(player_index, discards, 5)
+        }
+        crate::settings::RuleVariant::TwoSevenCard => {
+            check_two_player_validity(player_index, discards, 7)
+        }
+        crate::settings::RuleVariant::ThreeStandard => {
+            check_three_or_four_player_validity(player_index, discards, 3)
+        }
+        crate::settings::RuleVariant::ThreeCaptain => {
+            check_three_or_four_player_validity(player_index, discards, 3)
+        }
+        crate::settings::RuleVariant::FourIndividual => {
+            check_three_or_four_player_validity(player_index, discards, 4)
+        }
+        crate::settings::RuleVariant::FourPairs => {
+            check_three_or_four_player_validity(player_index, discards, 4)
+        }
+        crate::settings::RuleVariant::FiveStandard => {
+            check_five_player_validity(player_index, discards, game.index_dealer.unwrap())
+        }
+        crate::settings::RuleVariant::SixPairs => check_six_player_validity(
+            player_index,
+            discards,
+            game.index_dealer.unwrap(),
+            game.players[game.index_dealer.unwrap() as usize]
+                .partner_index
+                .unwrap(),
+        ),
+        crate::settings::RuleVariant::Custom(custom) => check_custom_validity(
+            player_index,
+            discards,
+            custom,
+            game.index_dealer.unwrap(),
+        ),
+    })
 }
 
 fn check_two_player_validity(
     player_index: u8,
-    discard_indices: &Vec<u8>,
+    discard_indices: &[u8],
     num_players: u8,
 ) -> Option<game_process_return::DiscardError> {
     if discard_indices.len() != 2 {
@@ -515,7 +597,7 @@ fn check_two_player_validity(
 
 fn check_three_or_four_player_validity(
     player_index: u8,
-    discard_indices: &Vec<u8>,
+    discard_indices: &[u8],
     num_players: u8,
 ) -> Option<game_process_return::DiscardError> {
     if discard_indices.len() != 1 {
@@ -537,11 +619,11 @@ fn check_three_or_four_player_validity(
 
 fn check_five_player_validity(
     player_index: u8,
-    discard_indices: &Vec<u8>,
+    discard_indices: &[u8],
     index_dealer: u8,
 ) -> Option<game_process_return::DiscardError> {
     if player_index == index_dealer {
-        if discard_indices.len() != 0 {
+        if !discard_indices.is_empty() {
             Some(
                 game_process_return::DiscardError::NoCardsAreDiscardedForDealerWithFivePlayers(
                     player_index,
@@ -567,12 +649,12 @@ fn check_five_player_validity(
 
 fn check_six_player_validity(
     player_index: u8,
-    discard_indices: &Vec<u8>,
+    discard_indices: &[u8],
     index_dealer: u8,
     index_dealer_partner: u8,
 ) -> Option<game_process_return::DiscardError> {
     if player_index == index_dealer || player_index == index_dealer_partner {
-        if discard_indices.len() != 0 {
+        if !discard_indices.is_empty() {
             Some(game_process_return::DiscardError::NoCardsAreDiscardedForDealerAndDealerPartnerWithSixPlayers(player_index))
         } else {
             None
@@ -588,26 +670,48 @@ fn check_six_player_validity(
     }
 }
 
+fn check_custom_validity(
+    player_index: u8,
+    discard_indices: &[u8],
+    custom: crate::settings::CustomVariant,
+    index_dealer: u8,
+) -> Option<game_process_return::DiscardError> {
+    let num_cards_dealt = custom.cards_dealt_to(player_index, index_dealer);
+    if discard_indices.len() != custom.discards_by(player_index, index_dealer) as usize {
+        Some(game_process_return::DiscardError::WrongNumberOfCardsAreDiscardedWithThisCustomVariant(player_index))
+    } else if discard_indices
+        .iter()
+        .enumerate()
+        .any(|(position, index)| discard_indices[..position].contains(index))
+    {
+        Some(game_process_return::DiscardError::CardIndicesMayNotBeRepeated(player_index))
+    } else if discard_indices
+        .iter()
+        .any(|index| *index >= num_cards_dealt)
+    {
+        Some(game_process_return::DiscardError::IndicesAreWithinTheHandWithThisCustomVariant(player_index))
+    } else {
+        None
+    }
+}
+
 fn execute_discard(game: &mut crate::GameImpl, discard_indices_group: Vec<Vec<u8>>) {
+    game.held_discards.clear();
+    game.discards.clear();
     for (player_index, discard_indices) in discard_indices_group.iter().enumerate() {
         let mut selected_cards: Vec<deck::Card> = Vec::new();
         for discard_index in discard_indices {
-            selected_cards.push(game.players[player_index as usize].hand[*discard_index as usize]);
+            selected_cards.push(game.players[player_index].hand[*discard_index as usize]);
         }
-        
+
         for card in &mut selected_cards {
             game.crib.push(*card);
-            game.players[player_index as usize].hand.retain({
-                |&hand_card| {
-                    if hand_card == *card {
-                        false
-                    } else {
-                        true
-                    }
-                }
-            });
+            game.players[player_index]
+                .hand
+                .retain(|&hand_card| hand_card != *card);
         }
+        game.discards.push(selected_cards);
 
-        game.state = crate::GameState::CutStarter;
+        crate::util::set_state(game, crate::GameState::CutStarter);
     }
 }
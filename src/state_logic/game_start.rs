@@ -20,6 +20,12 @@ mod test {
                     is_underpegging: false,
                     is_muggins: false,
                     is_lowball: false,
+                    is_back_up_ten: false,
+                    auction: None,
+                    skunk_lines: None,
+                    jokers: None,
+                    scoring_rules: crate::settings::ScoringRules::default(),
+                    time_limits: None,
                 }
             ),
             Ok(super::game_process_return::Success::GameStart)
@@ -38,6 +44,12 @@ mod test {
                     is_underpegging: false,
                     is_muggins: false,
                     is_lowball: false,
+                    is_back_up_ten: false,
+                    auction: None,
+                    skunk_lines: None,
+                    jokers: None,
+                    scoring_rules: crate::settings::ScoringRules::default(),
+                    time_limits: None,
                 }
             ),
             Err(super::game_process_return::Error::GameStartInvalidConfig(
@@ -71,6 +83,12 @@ mod test {
                     is_underpegging: false,
                     is_muggins: false,
                     is_lowball: false,
+                    is_back_up_ten: false,
+                    auction: None,
+                    skunk_lines: None,
+                    jokers: None,
+                    scoring_rules: crate::settings::ScoringRules::default(),
+                    time_limits: None,
                 };
 
                 // If the variant is a two player variant and the victor_dealer_option is not
@@ -203,12 +221,18 @@ mod test {
                 };
 
                 let settings = crate::settings::GameSettings {
-                    variant: variant,
+                    variant,
                     victor_dealer_option: vdo,
                     is_manual_scoring: false,
                     is_underpegging: false,
                     is_muggins: false,
                     is_lowball: *option,
+                    is_back_up_ten: false,
+                    auction: None,
+                    skunk_lines: None,
+                    jokers: None,
+                    scoring_rules: crate::settings::ScoringRules::default(),
+                    time_limits: None,
                 };
 
                 if settings.is_lowball
@@ -244,6 +268,12 @@ mod test {
                             is_underpegging: *under_option,
                             is_muggins: *muggins_option,
                             is_lowball: *low_option,
+                            is_back_up_ten: false,
+                            auction: None,
+                            skunk_lines: None,
+                            jokers: None,
+                            scoring_rules: crate::settings::ScoringRules::default(),
+                            time_limits: None,
                         };
 
                         match (*man_option, *under_option, *muggins_option, *low_option) {
@@ -285,17 +315,325 @@ mod test {
         }
     }
 
+    // A custom variant is checked first, and the other rules apply to it as they do to the presets
+    // with the same table
+    #[test]
+    fn settings_validity_custom_variant() {
+        let mut settings = crate::settings::GameSettings {
+            variant: crate::settings::RuleVariant::Custom(crate::settings::CustomVariant::new(1)),
+            victor_dealer_option: crate::settings::VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::CustomVariantMustHaveTwoToSixPlayers
+            ))
+        );
+
+        // A captain against two pairs must let the captain deal and can't be played as lowball
+        settings.variant = crate::settings::CustomVariant::new(5)
+            .partners(1, 3)
+            .partners(2, 4)
+            .cards_dealt(5)
+            .discards(1)
+            .captain_target_score(61)
+            .build()
+            .unwrap();
+        settings.victor_dealer_option = crate::settings::VictorDealerOption::LosersDrawForDealer;
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::VDOIsNotCaptainDealsWhenVariantIsThreeCaptain
+            ))
+        );
+        settings.victor_dealer_option = crate::settings::VictorDealerOption::CaptainDeals;
+        assert_eq!(super::check_settings_validity(settings), Ok(()));
+        settings.is_lowball = true;
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::LowballEnabledWhenVariantIsNotTwoPlayersOrPairs
+            ))
+        );
+
+        // The captain is the first to deal
+        let mut game = crate::GameImpl::new();
+        settings.is_lowball = false;
+        super::game_setup(&mut game, settings).unwrap();
+        assert_eq!(game.initial_cut_between_players_with_these_indices, vec![0]);
+        assert_eq!(game.players[3].partner_index, Some(1));
+    }
+
+    // An error should be returned when Back Up 10 is enabled with lowball
+    #[test]
+    fn settings_validity_back_up_ten() {
+        let mut settings = crate::settings::GameSettings {
+            variant: crate::settings::RuleVariant::FourPairs,
+            victor_dealer_option: crate::settings::VictorDealerOption::LosersDrawForDealer,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: true,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert_eq!(super::check_settings_validity(settings), Ok(()));
+
+        settings.is_lowball = true;
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::BackUpTenIsEnabledWhenLowballIsEnabled
+            ))
+        );
+    }
+
+    // An error should be returned when the auction is enabled with lowball, with a variant where
+    // the dealer holds a different hand than the other players, with a zero bid or increment, or
+    // with a maximum bid below the minimum bid
+    #[test]
+    fn settings_validity_auction() {
+        let mut settings = crate::settings::GameSettings {
+            variant: crate::settings::RuleVariant::TwoStandard,
+            victor_dealer_option: crate::settings::VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: Some(crate::settings::AuctionRules {
+                minimum_bid: 1,
+                minimum_increment: 1,
+                maximum_bid: 30,
+            }),
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert_eq!(super::check_settings_validity(settings), Ok(()));
+
+        settings.is_lowball = true;
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::AuctionIsEnabledWhenLowballIsEnabled
+            ))
+        );
+
+        settings.is_lowball = false;
+        settings.variant = crate::settings::RuleVariant::FiveStandard;
+        settings.victor_dealer_option = crate::settings::VictorDealerOption::LosersDrawForDealer;
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::AuctionIsEnabledWhenSeatsAreDealtDifferently
+            ))
+        );
+
+        settings.variant = crate::settings::RuleVariant::TwoStandard;
+        settings.victor_dealer_option = crate::settings::VictorDealerOption::TwoPlayers;
+        settings.auction = Some(crate::settings::AuctionRules {
+            minimum_bid: 1,
+            minimum_increment: 0,
+            maximum_bid: 30,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::AuctionBidsAndIncrementsMustBeAtLeastOne
+            ))
+        );
+
+        settings.auction = Some(crate::settings::AuctionRules {
+            minimum_bid: 5,
+            minimum_increment: 1,
+            maximum_bid: 4,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::AuctionMaximumBidIsBelowTheMinimumBid
+            ))
+        );
+    }
+
+    // An error should be returned when there are no jokers or more than two, or when the play value
+    // of a joker isn't that of a card
+    #[test]
+    fn settings_validity_jokers() {
+        let mut settings = crate::settings::GameSettings {
+            variant: crate::settings::RuleVariant::TwoStandard,
+            victor_dealer_option: crate::settings::VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: Some(crate::settings::JokerRules {
+                num_jokers: 2,
+                play_value: 10,
+            }),
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert_eq!(super::check_settings_validity(settings), Ok(()));
+
+        let mut game = crate::GameImpl::new();
+        super::game_setup(&mut game, settings).unwrap();
+        assert_eq!(game.deck.num_jokers(), 2);
+
+        settings.jokers = Some(crate::settings::JokerRules {
+            num_jokers: 3,
+            play_value: 10,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::JokersMustNumberOneOrTwo
+            ))
+        );
+
+        settings.jokers = Some(crate::settings::JokerRules {
+            num_jokers: 1,
+            play_value: 0,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::JokerPlayValueIsOutOfRange
+            ))
+        );
+    }
+
+    // An error should be returned when a discard or play time limit is scored automatically, or
+    // when there is a manual scoring time limit without manual scoring
+    #[test]
+    fn settings_validity_time_limits() {
+        let time_limit = |on_timeout| crate::settings::TimeLimit {
+            millis: 30_000,
+            on_timeout,
+        };
+        let mut settings = crate::settings::GameSettings {
+            variant: crate::settings::RuleVariant::TwoStandard,
+            victor_dealer_option: crate::settings::VictorDealerOption::TwoPlayers,
+            is_manual_scoring: true,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: Some(crate::settings::TimeLimits {
+                discard: Some(time_limit(crate::settings::TimeoutPolicy::AutoPlay)),
+                play: Some(time_limit(crate::settings::TimeoutPolicy::Forfeit)),
+                manual_scoring: Some(time_limit(crate::settings::TimeoutPolicy::AutoScore)),
+            }),
+        };
+        assert_eq!(super::check_settings_validity(settings), Ok(()));
+
+        settings.is_manual_scoring = false;
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::ManualScoringTimeLimitWhenManualScoringIsDisabled
+            ))
+        );
+
+        settings.time_limits = Some(crate::settings::TimeLimits {
+            discard: None,
+            play: Some(time_limit(crate::settings::TimeoutPolicy::AutoScore)),
+            manual_scoring: None,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::AutoScoreIsOnlyForTheManualScoringTimeLimit
+            ))
+        );
+    }
+
+    // An error should be returned when custom skunk lines have the double skunk line above the
+    // skunk line or the skunk line above the lowest target score of the variant
+    #[test]
+    fn settings_validity_skunk_lines() {
+        let mut settings = crate::settings::GameSettings {
+            variant: crate::settings::RuleVariant::ThreeCaptain,
+            victor_dealer_option: crate::settings::VictorDealerOption::CaptainDeals,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: Some(crate::settings::SkunkLines {
+                skunk: 61,
+                double_skunk: 31,
+            }),
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
+        };
+        assert_eq!(super::check_settings_validity(settings), Ok(()));
+
+        settings.skunk_lines = Some(crate::settings::SkunkLines {
+            skunk: 30,
+            double_skunk: 31,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::DoubleSkunkLineIsAboveSkunkLine
+            ))
+        );
+
+        settings.skunk_lines = Some(crate::settings::SkunkLines {
+            skunk: 91,
+            double_skunk: 61,
+        });
+        assert_eq!(
+            super::check_settings_validity(settings),
+            Err(crate::game_process_return::Error::GameStartInvalidConfig(
+                crate::game_process_return::ConfigError::SkunkLineIsAboveTargetScore
+            ))
+        );
+    }
+
     // Helper function to return a game with a given RuleVariant for testing the player_setup
     // function
     fn set_up_game(variant: crate::settings::RuleVariant) -> crate::GameImpl {
         let mut game = crate::GameImpl::new();
         game.settings = Some(crate::settings::GameSettings {
-            variant: variant,
+            variant,
             victor_dealer_option: crate::settings::VictorDealerOption::TwoPlayers,
             is_manual_scoring: false,
             is_underpegging: false,
             is_muggins: false,
             is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
         });
 
         game
@@ -405,20 +743,21 @@ pub(crate) fn game_setup(
 ) -> Result<game_process_return::Success, game_process_return::Error> {
     // If the settings are invalid, return the error given, otherwise set the game settings to the
     // given settings
-    if let Err(e) = check_settings_validity(settings) {
-        return Err(e);
-    }
+    check_settings_validity(settings)?;
     game.settings = Some(settings);
 
     set_up_players(game);
 
+    game.deck
+        .set_num_jokers(settings.jokers.map_or(0, |jokers| jokers.num_jokers));
     game.deck.reset_deck();
     game.crib = Vec::with_capacity(4);
 
     // When a game is being setup, the initial cut is between all players unless the variant is
     // captain's cribbage in which the first dealer is always the captain
-    if settings.variant == crate::settings::RuleVariant::ThreeCaptain {
-        game.initial_cut_between_players_with_these_indices.push(0);
+    if let Some(captain_index) = settings.variant.preset().captain_index() {
+        game.initial_cut_between_players_with_these_indices
+            .push(captain_index);
     } else {
         for index in 0..game.players.len() {
             game.initial_cut_between_players_with_these_indices
@@ -426,7 +765,7 @@ pub(crate) fn game_setup(
         }
     }
 
-    game.state = crate::GameState::CutInitial;
+    crate::util::set_state(game, crate::GameState::CutInitial);
 
     Ok(game_process_return::Success::GameStart)
 }
@@ -434,6 +773,15 @@ pub(crate) fn game_setup(
 fn check_settings_validity(
     settings: crate::settings::GameSettings,
 ) -> Result<(), game_process_return::Error> {
+    // The presets are always valid but a custom variant must be checked before anything else that
+    // depends on its description
+    if let crate::settings::RuleVariant::Custom(custom) = settings.variant {
+        custom
+            .validate()
+            .map_err(game_process_return::Error::GameStartInvalidConfig)?;
+    }
+    let preset = settings.variant.preset();
+
     // If the variant is one of the two player variants but the VictorDealerOption isn't TwoPlayers
     if crate::util::return_num_players_for_variant(settings.variant) == 2
         && settings.victor_dealer_option != crate::settings::VictorDealerOption::TwoPlayers
@@ -452,16 +800,17 @@ fn check_settings_validity(
         ));
     }
 
-    // If the variant is ThreeCaptain, but the VictorDealerOption isn't CaptainDeals
-    if settings.variant == crate::settings::RuleVariant::ThreeCaptain
+    // If the variant has a captain as in ThreeCaptain, but the VictorDealerOption isn't
+    // CaptainDeals
+    if preset.captain_index().is_some()
         && settings.victor_dealer_option != crate::settings::VictorDealerOption::CaptainDeals
     {
         return Err(game_process_return::Error::GameStartInvalidConfig(
             game_process_return::ConfigError::VDOIsNotCaptainDealsWhenVariantIsThreeCaptain,
         ));
     }
-    // If the variant isn't ThreeCaptain, but the VictorDealerOption is CaptainDeals
-    if settings.variant != crate::settings::RuleVariant::ThreeCaptain
+    // If the variant has no captain, but the VictorDealerOption is CaptainDeals
+    if preset.captain_index().is_none()
         && settings.victor_dealer_option == crate::settings::VictorDealerOption::CaptainDeals
     {
         return Err(game_process_return::Error::GameStartInvalidConfig(
@@ -469,11 +818,10 @@ fn check_settings_validity(
         ));
     }
 
-    // If the variant is FourPairs, or SixPairs then do not allow the
+    // If the variant has pairs such as FourPairs or SixPairs then do not allow the
     // victor_dealer_option to be LastPlaceIsDealer because there are partners so the losers
     // should always draw for first crib
-    if (settings.variant == crate::settings::RuleVariant::FourPairs
-        || settings.variant == crate::settings::RuleVariant::SixPairs)
+    if preset.has_partners()
         && settings.victor_dealer_option == crate::settings::VictorDealerOption::LastPlaceIsDealer
     {
         return Err(game_process_return::Error::GameStartInvalidConfig(
@@ -483,11 +831,7 @@ fn check_settings_validity(
 
     // If the variant has more than two players/pairs, then it doesn't work with lowball as the
     // game can't go on after one player/pair has won
-    if (settings.variant == crate::settings::RuleVariant::ThreeStandard
-        || settings.variant == crate::settings::RuleVariant::FourIndividual
-        || crate::util::return_num_players_for_variant(settings.variant) > 4)
-        && settings.is_lowball
-    {
+    if preset.num_sides() != 2 && settings.is_lowball {
         return Err(game_process_return::Error::GameStartInvalidConfig(
             game_process_return::ConfigError::LowballEnabledWhenVariantIsNotTwoPlayersOrPairs,
         ));
@@ -514,6 +858,92 @@ fn check_settings_validity(
         ));
     }
 
+    // Custom skunk lines must be in order and reachable by a losing player; the captain's target
+    // is the lowest target of any player
+    if let Some(lines) = settings.skunk_lines {
+        if lines.double_skunk > lines.skunk {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::DoubleSkunkLineIsAboveSkunkLine,
+            ));
+        }
+        if lines.skunk > settings.variant.target_score(false) {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::SkunkLineIsAboveTargetScore,
+            ));
+        }
+    }
+
+    // Moving an opponent back would only help them when the first to the target loses
+    if settings.is_back_up_ten && settings.is_lowball {
+        return Err(game_process_return::Error::GameStartInvalidConfig(
+            game_process_return::ConfigError::BackUpTenIsEnabledWhenLowballIsEnabled,
+        ));
+    }
+
+    // Bidding for the crib only works when the crib can change hands without changing the deal,
+    // and a bid deducted in lowball would be a benefit rather than a cost
+    if let Some(auction) = settings.auction {
+        if settings.is_lowball {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::AuctionIsEnabledWhenLowballIsEnabled,
+            ));
+        }
+        let preset = settings.variant.preset();
+        let seats = 0..preset.num_players as usize;
+        if seats
+            .clone()
+            .any(|seat| preset.cards_dealt[seat] != preset.cards_dealt[0])
+            || seats
+                .into_iter()
+                .any(|seat| preset.discards[seat] != preset.discards[0])
+        {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::AuctionIsEnabledWhenSeatsAreDealtDifferently,
+            ));
+        }
+        if auction.minimum_bid == 0 || auction.minimum_increment == 0 {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::AuctionBidsAndIncrementsMustBeAtLeastOne,
+            ));
+        }
+        if auction.maximum_bid < auction.minimum_bid {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::AuctionMaximumBidIsBelowTheMinimumBid,
+            ));
+        }
+    }
+
+    if let Some(jokers) = settings.jokers {
+        if jokers.num_jokers == 0 || jokers.num_jokers > 2 {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::JokersMustNumberOneOrTwo,
+            ));
+        }
+        if jokers.play_value == 0 || jokers.play_value > 10 {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::JokerPlayValueIsOutOfRange,
+            ));
+        }
+    }
+
+    if let Some(time_limits) = settings.time_limits {
+        let is_auto_score = |time_limit: Option<crate::settings::TimeLimit>| {
+            time_limit.map_or(false, |time_limit| {
+                time_limit.on_timeout == crate::settings::TimeoutPolicy::AutoScore
+            })
+        };
+        if is_auto_score(time_limits.discard) || is_auto_score(time_limits.play) {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::AutoScoreIsOnlyForTheManualScoringTimeLimit,
+            ));
+        }
+        if time_limits.manual_scoring.is_some() && !settings.is_manual_scoring {
+            return Err(game_process_return::Error::GameStartInvalidConfig(
+                game_process_return::ConfigError::ManualScoringTimeLimitWhenManualScoringIsDisabled,
+            ));
+        }
+    }
+
     // Muggins just doesn't really work with underpegging underpegging
     if settings.is_underpegging && settings.is_lowball {
         return Err(game_process_return::Error::GameStartInvalidConfig(
@@ -526,17 +956,12 @@ fn check_settings_validity(
 
 fn set_up_players(game: &mut crate::GameImpl) {
     if let Some(settings) = game.settings {
-        game.players = match settings.variant {
-            crate::settings::RuleVariant::TwoStandard => set_up_no_partners(2, 6),
-            crate::settings::RuleVariant::TwoFiveCard => set_up_no_partners(2, 5),
-            crate::settings::RuleVariant::TwoSevenCard => set_up_no_partners(2, 7),
-            crate::settings::RuleVariant::ThreeStandard => set_up_no_partners(3, 5),
-            crate::settings::RuleVariant::ThreeCaptain => set_up_three_captain(),
-            crate::settings::RuleVariant::FourIndividual => set_up_no_partners(4, 5),
-            crate::settings::RuleVariant::FourPairs => set_up_four_pairs(),
-            crate::settings::RuleVariant::FiveStandard => set_up_no_partners(5, 5),
-            crate::settings::RuleVariant::SixPairs => set_up_six_pairs(),
-        };
+        let preset = settings.variant.preset();
+        let num_cards = preset.cards_dealt.iter().max().copied().unwrap_or(0);
+        game.players = (0..preset.num_players as usize)
+            .map(|index| set_up_player(preset.partner_indices[index], num_cards))
+            .collect();
+        game.holes_moved_back = vec![0; preset.num_players as usize];
     }
 }
 
@@ -546,59 +971,6 @@ fn set_up_player(partner_index: Option<u8>, num_cards: u8) -> player::Player {
         back_peg_pos: 0,
         front_peg_pos: 0,
         hand: Vec::with_capacity(num_cards as usize),
-        partner_index: partner_index,
-    }
-}
-
-// The following four functions return a vector of Players to be given to the GameImpl object
-
-fn set_up_no_partners(num_players: u8, num_cards: u8) -> Vec<player::Player> {
-    let mut players = Vec::new();
-    for _ in 0..num_players {
-        players.push(set_up_player(None, num_cards));
+        partner_index,
     }
-    players
-}
-
-fn set_up_three_captain() -> Vec<player::Player> {
-    let mut players = Vec::new();
-    for index in 0..3 {
-        players.push(match index {
-            0 => set_up_player(None, 5),
-            1 => set_up_player(Some(2), 5),
-            2 => set_up_player(Some(1), 5),
-            _ => panic!("Invalid index set_up_three_captain"),
-        })
-    }
-    players
-}
-
-fn set_up_four_pairs() -> Vec<player::Player> {
-    let mut players = Vec::new();
-    for index in 0..4 {
-        players.push(match index {
-            0 => set_up_player(Some(2), 5),
-            1 => set_up_player(Some(3), 5),
-            2 => set_up_player(Some(0), 5),
-            3 => set_up_player(Some(1), 5),
-            _ => panic!("Invalid index set_up_four_pairs"),
-        })
-    }
-    players
-}
-
-fn set_up_six_pairs() -> Vec<player::Player> {
-    let mut players = Vec::new();
-    for index in 0..6 {
-        players.push(match index {
-            0 => set_up_player(Some(3), 5),
-            1 => set_up_player(Some(4), 5),
-            2 => set_up_player(Some(5), 5),
-            3 => set_up_player(Some(0), 5),
-            4 => set_up_player(Some(1), 5),
-            5 => set_up_player(Some(2), 5),
-            _ => panic!("Invalid index set_up_six_pairs"),
-        })
-    }
-    players
 }
@@ -1,7 +1,13 @@
+pub mod auction;
+pub mod crib;
 pub mod cut_initial;
 pub mod cut_starter_and_nibs_check;
 pub mod deal;
 pub mod discard;
 pub mod game_start;
 pub mod play;
+pub mod seats;
+pub mod show;
 pub mod sort;
+pub mod timeout;
+pub mod win;
@@ -0,0 +1,274 @@
+use crate::game_process_return;
+use crate::score;
+use crate::util;
+
+// State for Back Up 10 where the player who played the last card before everyone said go chooses
+// between pegging one for the go and moving an opponent, along with their partner, back ten holes.
+// An opponent is never moved back past the start so they lose at most the holes they have pegged,
+// nor past the back_up_ten_limit of the ScoringRules over a game. Either way the play then
+// continues to ResetPlay
+
+#[cfg(test)]
+mod test {
+    use super::super::test_util::set_up_game;
+    use super::process_go_decision;
+    use crate::game_process_return;
+    use crate::settings::RuleVariant;
+    use crate::util::return_card;
+    use crate::GoDecision;
+
+    // Sets up a game waiting for the decision of player 1 with the given scores
+    fn set_up_decision(variant: RuleVariant, scores: &[u8]) -> crate::GameImpl {
+        let mut game = set_up_game(variant, false, false, false);
+        game.settings.as_mut().unwrap().is_back_up_ten = true;
+        for (player, score) in game.players.iter_mut().zip(scores) {
+            player.back_peg_pos = score.saturating_sub(2);
+            player.front_peg_pos = *score;
+        }
+        game.last_player_index = Some(1);
+        game.index_active = Some(1);
+        game.last_play_was_go = true;
+        game.state = crate::GameState::PlayGoDecision;
+        game
+    }
+
+    #[test]
+    fn peg_one() {
+        let mut game = set_up_decision(RuleVariant::TwoStandard, &[30, 40]);
+        assert_eq!(
+            process_go_decision(&mut game, GoDecision::PegOne),
+            Ok(game_process_return::Success::GoDecision(
+                game_process_return::GoDecisionReturn::PeggedOne(crate::score::ScoreEvent {
+                    player_index: 1,
+                    point_value: 1,
+                    score_type: crate::score::ScoreType::Play(
+                        crate::score::PlayScoreType::LastCard
+                    ),
+                })
+            ))
+        );
+        assert_eq!(game.players[1].front_peg_pos, 41);
+        assert_eq!(game.state, crate::GameState::ResetPlay);
+        // The player after the last to play leads the next PlayGroup
+        assert_eq!(game.index_active, Some(0));
+
+        let mut game = set_up_decision(RuleVariant::TwoStandard, &[30, 120]);
+        process_go_decision(&mut game, GoDecision::PegOne).unwrap();
+        assert_eq!(game.state, crate::GameState::Win);
+    }
+
+    #[test]
+    fn back_up_ten() {
+        let mut game = set_up_decision(RuleVariant::TwoStandard, &[30, 40]);
+        assert_eq!(
+            process_go_decision(&mut game, GoDecision::BackUpTen(0)),
+            Ok(game_process_return::Success::GoDecision(
+                game_process_return::GoDecisionReturn::MovedBack(0, 10)
+            ))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 20);
+        assert_eq!(game.players[0].back_peg_pos, 20);
+        assert_eq!(game.players[1].front_peg_pos, 40);
+        assert_eq!(game.state, crate::GameState::ResetPlay);
+        assert_eq!(game.index_active, Some(0));
+
+        // An opponent near the start is only moved back to it
+        let mut game = set_up_decision(RuleVariant::TwoStandard, &[4, 40]);
+        assert_eq!(
+            process_go_decision(&mut game, GoDecision::BackUpTen(0)),
+            Ok(game_process_return::Success::GoDecision(
+                game_process_return::GoDecisionReturn::MovedBack(0, 4)
+            ))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 0);
+    }
+
+    // Once an opponent has been moved back the limit over the game they can't be moved back again
+    #[test]
+    fn back_up_ten_limit() {
+        let mut game = set_up_decision(RuleVariant::TwoStandard, &[60, 40]);
+        game.settings
+            .as_mut()
+            .unwrap()
+            .scoring_rules
+            .back_up_ten_limit = Some(15);
+        process_go_decision(&mut game, GoDecision::BackUpTen(0)).unwrap();
+        assert_eq!(game.players[0].front_peg_pos, 50);
+
+        game.state = crate::GameState::PlayGoDecision;
+        assert_eq!(
+            process_go_decision(&mut game, GoDecision::BackUpTen(0)),
+            Ok(game_process_return::Success::GoDecision(
+                game_process_return::GoDecisionReturn::MovedBack(0, 5)
+            ))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 45);
+
+        game.state = crate::GameState::PlayGoDecision;
+        assert_eq!(
+            process_go_decision(&mut game, GoDecision::BackUpTen(0)),
+            Err(game_process_return::Error::GoDecisionError(
+                game_process_return::GoDecisionError::OpponentIsAtTheBackUpTenLimit
+            ))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 45);
+        assert_eq!(game.state, crate::GameState::PlayGoDecision);
+    }
+
+    // Partners are moved back together and only an opponent may be chosen
+    #[test]
+    fn partners_and_invalid_choices() {
+        let mut game = set_up_decision(RuleVariant::FourPairs, &[50, 60, 50, 60]);
+        for (player_index, error) in &[
+            (
+                1,
+                game_process_return::GoDecisionError::OnlyAnOpponentMayBeMovedBack,
+            ),
+            (
+                3,
+                game_process_return::GoDecisionError::OnlyAnOpponentMayBeMovedBack,
+            ),
+            (
+                4,
+                game_process_return::GoDecisionError::PlayerIndexIsNotAtTheTable,
+            ),
+        ] {
+            assert_eq!(
+                process_go_decision(&mut game, GoDecision::BackUpTen(*player_index)),
+                Err(game_process_return::Error::GoDecisionError(error.clone()))
+            );
+        }
+        assert_eq!(game.state, crate::GameState::PlayGoDecision);
+
+        process_go_decision(&mut game, GoDecision::BackUpTen(2)).unwrap();
+        assert_eq!(game.players[0].front_peg_pos, 40);
+        assert_eq!(game.players[2].front_peg_pos, 40);
+        assert_eq!(game.index_active, Some(2));
+    }
+
+    // Gives each player two cards that can't be played on the current PlayGroup, last played into
+    // by player 0
+    fn set_up_go(is_manual: bool) -> crate::GameImpl {
+        let mut game = set_up_game(RuleVariant::TwoStandard, is_manual, is_manual, false);
+        game.settings.as_mut().unwrap().is_back_up_ten = true;
+        game.players[0].hand = vec![return_card('K', 'C'), return_card('K', 'S')];
+        game.players[1].hand = vec![return_card('Q', 'C'), return_card('Q', 'S')];
+        let play_group = game.play_groups.last_mut().unwrap();
+        play_group.cards = vec![
+            return_card('K', 'H'),
+            return_card('K', 'D'),
+            return_card('5', 'C'),
+        ];
+        play_group.total = 25;
+        game.last_player_index = Some(0);
+        game.index_active = Some(1);
+        game
+    }
+
+    // With Back Up 10 the go that comes back around to the last player to play asks for their
+    // decision instead of pegging the point
+    #[test]
+    fn automatic_go_leads_to_decision() {
+        let mut game = set_up_go(false);
+        super::super::play_card(&mut game, crate::PlayTurn::Go).unwrap();
+        assert_eq!(
+            super::super::play_card(&mut game, crate::PlayTurn::Go),
+            Ok(game_process_return::Success::PlayWaitForCard(
+                game_process_return::PlayWaitForCardReturn::GoDecision
+            ))
+        );
+        assert_eq!(game.state, crate::GameState::PlayGoDecision);
+        assert_eq!(game.index_active, Some(0));
+        assert_eq!(game.players[0].front_peg_pos, 0);
+    }
+
+    // With manual scoring the last card point is not claimed and the decision follows the scoring
+    #[test]
+    fn manual_go_leads_to_decision() {
+        let mut game = set_up_go(true);
+        super::super::play_card(&mut game, crate::PlayTurn::Go).unwrap();
+        super::super::play_score(&mut game, vec![]).unwrap();
+        super::super::play_card(&mut game, crate::PlayTurn::Go).unwrap();
+        assert_eq!(game.state, crate::GameState::PlayScore);
+
+        let last_card = vec![crate::score::ScoreEvent {
+            player_index: 0,
+            point_value: 1,
+            score_type: crate::score::ScoreType::Play(crate::score::PlayScoreType::LastCard),
+        }];
+        assert!(super::super::play_score(&mut game, last_card).is_err());
+        assert_eq!(
+            super::super::play_score(&mut game, vec![]),
+            Ok(game_process_return::Success::PlayScore(vec![]))
+        );
+        assert_eq!(game.state, crate::GameState::PlayGoDecision);
+        assert_eq!(game.index_active, Some(0));
+    }
+}
+
+pub(crate) fn process_go_decision(
+    game: &mut crate::GameImpl,
+    decision: crate::GoDecision,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let index_last_player = game.last_player_index.unwrap();
+
+    let decision_return = match decision {
+        crate::GoDecision::PegOne => {
+            let score_event = score::ScoreEvent {
+                player_index: index_last_player,
+                point_value: 1,
+                score_type: score::ScoreType::Play(score::PlayScoreType::LastCard),
+            };
+            if util::process_score_events(
+                game,
+                index_last_player as usize,
+                std::slice::from_ref(&score_event),
+            ) {
+                return Ok(game_process_return::Success::GoDecision(
+                    game_process_return::GoDecisionReturn::PeggedOne(score_event),
+                ));
+            }
+            game_process_return::GoDecisionReturn::PeggedOne(score_event)
+        }
+        crate::GoDecision::BackUpTen(player_index) => {
+            if player_index as usize >= game.players.len() {
+                return Err(game_process_return::Error::GoDecisionError(
+                    game_process_return::GoDecisionError::PlayerIndexIsNotAtTheTable,
+                ));
+            }
+            if player_index == index_last_player
+                || game.players[index_last_player as usize].partner_index == Some(player_index)
+            {
+                return Err(game_process_return::Error::GoDecisionError(
+                    game_process_return::GoDecisionError::OnlyAnOpponentMayBeMovedBack,
+                ));
+            }
+
+            let limit = game.settings.unwrap().scoring_rules.back_up_ten_limit;
+            let moved_back = game.holes_moved_back[player_index as usize];
+            let holes = match limit {
+                Some(limit) if moved_back >= limit => {
+                    return Err(game_process_return::Error::GoDecisionError(
+                        game_process_return::GoDecisionError::OpponentIsAtTheBackUpTenLimit,
+                    ));
+                }
+                Some(limit) => (limit - moved_back).min(10),
+                None => 10,
+            };
+
+            let holes = util::process_move_back(game, player_index as usize, holes);
+            let partner_index = game.players[player_index as usize].partner_index;
+            for index in std::iter::once(player_index).chain(partner_index) {
+                let moved_back = &mut game.holes_moved_back[index as usize];
+                *moved_back = moved_back.saturating_add(holes);
+            }
+            game_process_return::GoDecisionReturn::MovedBack(player_index, holes)
+        }
+    };
+
+    // The player after the last to play leads the next PlayGroup
+    game.index_active = Some((index_last_player + 1) % game.players.len() as u8);
+    util::set_state(game, crate::GameState::ResetPlay);
+
+    Ok(game_process_return::Success::GoDecision(decision_return))
+}
@@ -3,10 +3,14 @@ pub(crate) use wait_for_card::play_card;
 pub mod score;
 pub(crate) use score::play_score;
 pub mod muggins;
+pub(crate) use muggins::play_muggins;
+pub mod go_decision;
+pub(crate) use go_decision::process_go_decision;
 pub mod reset_play;
+pub(crate) use reset_play::reset_play;
 
 #[cfg(test)]
-mod test_util {
+pub(crate) mod test_util {
     // Helper function to create a GameImpl in the PlayWaitForCard stage of the game
     pub(crate) fn set_up_game(
         variant: crate::settings::RuleVariant,
@@ -25,12 +29,18 @@ mod test_util {
         };
         let mut game = crate::GameImpl::new();
         let settings = crate::settings::GameSettings {
-            variant: variant,
+            variant,
             victor_dealer_option: vdo,
             is_manual_scoring: is_man,
             is_underpegging: is_under,
             is_muggins: is_mug,
             is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: crate::settings::ScoringRules::default(),
+            time_limits: None,
         };
 
         game.is_debug = true;
@@ -1,44 +1,330 @@
+use crate::game_process_return;
+use crate::score;
+
+// State that follows every manual count of the play when muggins is enabled such that the
+// opponents of the player who counted may call any points that were missed with Muggins events;
+// a Muggins(None) event moves on as the count would have without muggins
+
 #[cfg(test)]
 mod test {
     // State that deals with any missed ScoreEvents when muggings is enabled
     mod play_muggins {
+        use super::super::super::play_card;
+        use super::super::super::play_score;
+        use super::super::super::test_util::set_up_game;
+        use super::super::play_muggins;
+        use crate::game_process_return::{Error, MugginsError, MugginsReturn, Success};
+        use crate::score::{PlayScoreType, ScoreEvent, ScoreType};
+        use crate::settings::RuleVariant;
+        use crate::util::return_card;
+
+        fn score_event(
+            player_index: u8,
+            point_value: u8,
+            play_score_type: PlayScoreType,
+        ) -> ScoreEvent {
+            ScoreEvent {
+                score_type: ScoreType::Play(play_score_type),
+                player_index,
+                point_value,
+            }
+        }
+
+        // Player 1 leads a ten and counts nothing as there is nothing to count
+        fn set_up_no_remaining() -> crate::GameImpl {
+            let mut game = set_up_game(RuleVariant::TwoStandard, true, true, true);
+            game.players[1].hand = vec![return_card('T', 'H')];
+            game.players[0].hand = vec![return_card('5', 'H')];
+            play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            game
+        }
+
+        // Player 0 answers with a five and misses the fifteen
+        fn set_up_one_remaining() -> crate::GameImpl {
+            let mut game = set_up_no_remaining();
+            play_muggins(&mut game, None).unwrap();
+            play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            game
+        }
+
+        // Player 1 plays 4, 5, 6 with a fifteen and a run of three to count, claiming the given
+        // ScoreEvents
+        fn set_up_multiple_remaining(claims: Vec<ScoreEvent>) -> crate::GameImpl {
+            let mut game = set_up_game(RuleVariant::TwoStandard, true, true, true);
+            game.players[1].hand = vec![return_card('4', 'H'), return_card('6', 'H')];
+            game.players[0].hand = vec![return_card('5', 'H')];
+            play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            play_muggins(&mut game, None).unwrap();
+            play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            play_muggins(&mut game, None).unwrap();
+            play_card(&mut game, crate::PlayTurn::CardSelected(1)).unwrap();
+            play_score(&mut game, claims).unwrap();
+            game
+        }
+
         #[test]
-        fn no_remaining_score_events_overpegged() {}
+        fn no_remaining_score_events_overpegged() {
+            let mut game = set_up_no_remaining();
+            assert_eq!(game.state, crate::GameState::PlayMuggins);
+            assert_eq!(game.index_active, Some(1));
+
+            let call = score_event(0, 2, PlayScoreType::Fifteen);
+            assert_eq!(
+                play_muggins(&mut game, Some(vec![call])),
+                Err(Error::MugginsError(
+                    MugginsError::CalledPointsWereNotMissed(crate::score::manual::ScoreDiff {
+                        overclaimed: vec![score_event(1, 2, PlayScoreType::Fifteen)],
+                        missed: vec![],
+                        claimed_points: 2,
+                        optimal_points: 0,
+                    })
+                ))
+            );
+            assert_eq!(game.state, crate::GameState::PlayMuggins);
+            assert_eq!(game.players[0].front_peg_pos, 0);
+        }
 
         #[test]
-        fn no_remaining_score_events_proceed() {}
+        fn no_remaining_score_events_proceed() {
+            let mut game = set_up_no_remaining();
+            assert_eq!(
+                play_muggins(&mut game, None),
+                Ok(Success::Muggins(MugginsReturn::Complete))
+            );
+            assert_eq!(game.state, crate::GameState::PlayWaitForCard);
+            assert_eq!(game.index_active, Some(0));
+        }
 
+        // Only an opponent of the player who counted may call muggins on the count
         #[test]
-        fn one_remaining_score_event_overpegged() {}
+        fn only_opponents_call() {
+            let mut game = set_up_one_remaining();
+            assert_eq!(
+                play_muggins(
+                    &mut game,
+                    Some(vec![score_event(0, 2, PlayScoreType::Fifteen)])
+                ),
+                Err(Error::MugginsError(
+                    MugginsError::OnlyAnOpponentMayCallMuggins
+                ))
+            );
+            assert_eq!(game.players[0].front_peg_pos, 0);
+        }
 
         #[test]
-        fn one_remaining_score_event_underpegged() {}
+        fn one_remaining_score_event_overpegged() {
+            let mut game = set_up_one_remaining();
+            assert_eq!(game.state, crate::GameState::PlayMuggins);
+            assert_eq!(game.index_active, Some(0));
 
+            assert_eq!(
+                play_muggins(
+                    &mut game,
+                    Some(vec![score_event(1, 2, PlayScoreType::Pair)])
+                ),
+                Err(Error::MugginsError(
+                    MugginsError::CalledPointsWereNotMissed(crate::score::manual::ScoreDiff {
+                        overclaimed: vec![score_event(0, 2, PlayScoreType::Pair)],
+                        missed: vec![score_event(0, 2, PlayScoreType::Fifteen)],
+                        claimed_points: 2,
+                        optimal_points: 2,
+                    })
+                ))
+            );
+            assert_eq!(game.players[1].front_peg_pos, 0);
+        }
+
+        // Calling nothing leaves the missed points to be called
         #[test]
-        fn one_remaining_score_event_correctly_pegged() {}
+        fn one_remaining_score_event_underpegged() {
+            let mut game = set_up_one_remaining();
+            assert_eq!(
+                play_muggins(&mut game, Some(vec![])),
+                Ok(Success::Muggins(MugginsReturn::Called(vec![])))
+            );
+            assert_eq!(game.state, crate::GameState::PlayMuggins);
+            assert_eq!(
+                game.missed_score_events,
+                vec![score_event(0, 2, PlayScoreType::Fifteen)]
+            );
+        }
 
         #[test]
-        fn one_remaining_score_event_proceed() {}
+        fn one_remaining_score_event_correctly_pegged() {
+            let mut game = set_up_one_remaining();
+            let call = vec![score_event(1, 2, PlayScoreType::Fifteen)];
+            assert_eq!(
+                play_muggins(&mut game, Some(call.clone())),
+                Ok(Success::Muggins(MugginsReturn::Called(call.clone())))
+            );
+            assert_eq!(game.players[1].front_peg_pos, 2);
+            assert_eq!(game.players[0].front_peg_pos, 0);
+            assert_eq!(game.state, crate::GameState::PlayMuggins);
+
+            // The points can't be called twice
+            assert!(play_muggins(&mut game, Some(call)).is_err());
+            assert_eq!(game.players[1].front_peg_pos, 2);
+        }
 
         #[test]
-        fn multiple_remaining_score_events_overpegged() {}
+        fn one_remaining_score_event_proceed() {
+            let mut game = set_up_one_remaining();
+            play_muggins(&mut game, None).unwrap();
+            assert_eq!(game.state, crate::GameState::PlayWaitForCard);
+            assert_eq!(game.index_active, Some(1));
+            assert!(game.missed_score_events.is_empty());
+            assert_eq!(game.players[0].front_peg_pos, 0);
+            assert_eq!(game.players[1].front_peg_pos, 0);
+        }
 
         #[test]
-        fn multiple_remaining_score_events_completely_underpegged() {}
+        fn multiple_remaining_score_events_overpegged() {
+            let mut game = set_up_multiple_remaining(vec![]);
+            assert!(matches!(
+                play_muggins(
+                    &mut game,
+                    Some(vec![
+                        score_event(0, 2, PlayScoreType::Fifteen),
+                        score_event(0, 4, PlayScoreType::Straight(4)),
+                    ])
+                ),
+                Err(Error::MugginsError(
+                    MugginsError::CalledPointsWereNotMissed(_)
+                ))
+            ));
+            assert_eq!(game.players[0].front_peg_pos, 0);
+            assert_eq!(game.missed_score_events.len(), 2);
+        }
 
+        // Nothing was counted so every point may be called
         #[test]
-        fn multiple_remaining_score_events_partially_underpegged() {}
+        fn multiple_remaining_score_events_completely_underpegged() {
+            let mut game = set_up_multiple_remaining(vec![]);
+            play_muggins(
+                &mut game,
+                Some(vec![
+                    score_event(0, 3, PlayScoreType::Straight(3)),
+                    score_event(0, 2, PlayScoreType::Fifteen),
+                ]),
+            )
+            .unwrap();
+            assert_eq!(game.players[0].front_peg_pos, 5);
+            assert_eq!(game.players[1].front_peg_pos, 0);
+            assert!(game.missed_score_events.is_empty());
+        }
 
+        // Only the run was missed so only the run may be called
         #[test]
-        fn multiple_remaining_score_events_corectly_pegged() {}
+        fn multiple_remaining_score_events_partially_underpegged() {
+            let mut game =
+                set_up_multiple_remaining(vec![score_event(1, 2, PlayScoreType::Fifteen)]);
+            assert_eq!(game.players[1].front_peg_pos, 2);
+            assert!(play_muggins(
+                &mut game,
+                Some(vec![score_event(0, 2, PlayScoreType::Fifteen)])
+            )
+            .is_err());
+            play_muggins(
+                &mut game,
+                Some(vec![score_event(0, 3, PlayScoreType::Straight(3))]),
+            )
+            .unwrap();
+            assert_eq!(game.players[0].front_peg_pos, 3);
+        }
 
+        // Points may be called one at a time
         #[test]
-        fn multiple_remaining_score_events_proceed() {}
+        fn multiple_remaining_score_events_corectly_pegged() {
+            let mut game = set_up_multiple_remaining(vec![]);
+            play_muggins(
+                &mut game,
+                Some(vec![score_event(0, 2, PlayScoreType::Fifteen)]),
+            )
+            .unwrap();
+            play_muggins(
+                &mut game,
+                Some(vec![score_event(0, 3, PlayScoreType::Straight(3))]),
+            )
+            .unwrap();
+            assert_eq!(game.players[0].front_peg_pos, 5);
+            assert!(game.missed_score_events.is_empty());
+        }
 
         #[test]
-        fn last_card_remaining_underpegged_proceed() {}
+        fn multiple_remaining_score_events_proceed() {
+            let mut game = set_up_multiple_remaining(vec![]);
+            play_muggins(&mut game, None).unwrap();
+            assert_eq!(game.state, crate::GameState::PlayWaitForCard);
+            assert_eq!(game.index_active, Some(0));
+            assert_eq!(game.players[0].front_peg_pos, 0);
+            assert_eq!(game.players[1].front_peg_pos, 0);
+        }
 
+        // A go doesn't count anything for the player who said it unless it's the last card
         #[test]
-        fn thirty_one_remaining_underpegged_proceed() {}
+        fn last_card_remaining_underpegged_proceed() {
+            let mut game = set_up_game(RuleVariant::TwoStandard, true, true, true);
+            game.play_groups[0].total = 30;
+            game.players[0].hand = vec![return_card('2', 'C')];
+            game.last_player_index = Some(0);
+
+            play_card(&mut game, crate::PlayTurn::Go).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            assert_eq!(game.state, crate::GameState::PlayWaitForCard);
+            play_card(&mut game, crate::PlayTurn::Go).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            assert_eq!(game.state, crate::GameState::PlayMuggins);
+            assert_eq!(
+                game.missed_score_events,
+                vec![score_event(0, 1, PlayScoreType::LastCard)]
+            );
+
+            play_muggins(&mut game, None).unwrap();
+            assert_eq!(game.state, crate::GameState::ResetPlay);
+            assert_eq!(game.index_active, Some(1));
+            assert_eq!(game.players[0].front_peg_pos, 0);
+        }
+
+        #[test]
+        fn thirty_one_remaining_underpegged_proceed() {
+            let mut game = set_up_game(RuleVariant::TwoStandard, true, true, true);
+            game.play_groups[0].total = 30;
+            game.players[1].hand = vec![return_card('A', 'H')];
+
+            play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+            play_score(&mut game, vec![]).unwrap();
+            assert_eq!(
+                game.missed_score_events,
+                vec![score_event(1, 2, PlayScoreType::ThirtyOne)]
+            );
+
+            play_muggins(&mut game, None).unwrap();
+            assert_eq!(game.state, crate::GameState::ResetPlay);
+            assert_eq!(game.index_active, Some(0));
+            assert_eq!(game.players[1].front_peg_pos, 0);
+        }
+    }
+}
+
+// Processes a call of muggins on the count of the last card, or moves on with Muggins(None)
+pub(crate) fn play_muggins(
+    game: &mut crate::GameImpl,
+    selections: Option<Vec<score::ScoreEvent>>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    match selections {
+        Some(selections) => {
+            crate::state_logic::show::call_muggins(game, game.index_active.unwrap(), selections)
+        }
+        None => {
+            game.missed_score_events.clear();
+            super::score::finish_play_count(game);
+            Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Complete,
+            ))
+        }
     }
 }
@@ -1,14 +1,124 @@
+use crate::game_process_return;
+
 // State that will either add a new, empty member to play_groups and go back to PlayWaitForCard
 // or transition the game to the ShowScore state
 
 #[cfg(test)]
 mod test {
+    use super::super::test_util::set_up_game;
+    use super::reset_play;
+    use crate::game_process_return;
+    use crate::settings::RuleVariant;
+
+    // Plays every card of the given player's hand into the last PlayGroup without regard for the
+    // total
+    fn play_whole_hand(game: &mut crate::GameImpl, player_index: usize) {
+        let hand = game.players[player_index].hand.clone();
+        game.play_groups.last_mut().unwrap().cards.extend(hand);
+    }
+
     #[test]
-    fn cards_remaining_back_to_play_wait_for_card() {}
+    fn cards_remaining_back_to_play_wait_for_card() {
+        let mut game = set_up_game(RuleVariant::TwoStandard, false, false, false);
+        play_whole_hand(&mut game, 0);
+        game.state = crate::GameState::ResetPlay;
+        game.index_active = Some(1);
+        game.last_play_was_go = true;
+
+        assert_eq!(
+            reset_play(&mut game),
+            Ok(game_process_return::Success::ResetPlay(
+                game_process_return::ResetPlayReturn::NewPlayGroup
+            ))
+        );
+        assert_eq!(game.state, crate::GameState::PlayWaitForCard);
+        assert_eq!(game.play_groups.len(), 2);
+        assert_eq!(game.play_groups[1].cards, vec![]);
+        assert_eq!(game.play_groups[1].total, 0);
+        assert!(!game.last_play_was_go);
+        // The player following the last to play leads the next PlayGroup
+        assert_eq!(game.index_active, Some(1));
+    }
 
     #[test]
-    fn no_cards_remaining_to_show_score() {}
+    fn no_cards_remaining_to_show_score() {
+        for variant in crate::util::return_variants() {
+            let mut game = set_up_game(variant, false, false, false);
+            for player_index in 0..game.players.len() {
+                play_whole_hand(&mut game, player_index);
+            }
+            game.state = crate::GameState::ResetPlay;
+            game.index_active = Some(0);
 
+            assert_eq!(
+                reset_play(&mut game),
+                Ok(game_process_return::Success::ResetPlay(
+                    game_process_return::ResetPlayReturn::PlayComplete
+                ))
+            );
+            assert_eq!(game.state, crate::GameState::ShowScore);
+            // The player to the left of the dealer shows first
+            assert_eq!(
+                game.index_active,
+                Some((game.index_dealer.unwrap() + 1) % game.players.len() as u8)
+            );
+        }
+    }
+
+    // Five card cribbage only plays one PlayGroup to 31 or a go
     #[test]
-    fn cards_remaining_two_five_cargd_to_show_score() {}
+    fn cards_remaining_two_five_card_to_show_score() {
+        let mut game = set_up_game(RuleVariant::TwoFiveCard, false, false, false);
+        game.state = crate::GameState::ResetPlay;
+
+        assert_eq!(
+            reset_play(&mut game),
+            Ok(game_process_return::Success::ResetPlay(
+                game_process_return::ResetPlayReturn::PlayComplete
+            ))
+        );
+        assert_eq!(game.state, crate::GameState::ShowScore);
+        assert_eq!(game.play_groups.len(), 1);
+    }
+}
+
+pub(crate) fn reset_play(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let is_five_card = game.settings.unwrap().variant == crate::settings::RuleVariant::TwoFiveCard;
+
+    if !is_five_card && is_card_remaining(game) {
+        // The index_active has already been moved past the player who played the last card of the
+        // previous PlayGroup so they lead the new one
+        game.play_groups.push(crate::PlayGroup {
+            total: 0,
+            cards: Vec::new(),
+        });
+        game.last_play_was_go = false;
+        crate::util::set_state(game, crate::GameState::PlayWaitForCard);
+
+        Ok(game_process_return::Success::ResetPlay(
+            game_process_return::ResetPlayReturn::NewPlayGroup,
+        ))
+    } else {
+        // The show starts with the player to the left of the dealer
+        game.index_active = Some((game.index_dealer.unwrap() + 1) % game.players.len() as u8);
+        crate::util::set_state(game, crate::GameState::ShowScore);
+
+        Ok(game_process_return::Success::ResetPlay(
+            game_process_return::ResetPlayReturn::PlayComplete,
+        ))
+    }
+}
+
+// Returns whether any player has a card in their hand that has not yet been played
+fn is_card_remaining(game: &crate::GameImpl) -> bool {
+    game.players.iter().any(|player| {
+        player.hand.iter().any(|card| {
+            !game
+                .play_groups
+                .iter()
+                .any(|play_group| play_group.cards.contains(card))
+        })
+    })
 }
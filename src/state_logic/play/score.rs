@@ -138,7 +138,14 @@ mod test {
             assert_eq!(
                 play_score(&mut game, vec![score.clone()]),
                 Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::OverpeggingIsNotAllowed(score)
+                    game_process_return::PlayScoreError::OverpeggingIsNotAllowed(
+                        crate::score::manual::ScoreDiff {
+                            overclaimed: vec![score],
+                            missed: vec![],
+                            claimed_points: 2,
+                            optimal_points: 0,
+                        }
+                    )
                 ))
             );
             assert_eq!(game.state, crate::GameState::PlayScore);
@@ -164,7 +171,20 @@ mod test {
             assert_eq!(
                 play_score(&mut game, vec![]),
                 Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed
+                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed(
+                        crate::score::manual::ScoreDiff {
+                            overclaimed: vec![],
+                            missed: vec![crate::score::ScoreEvent {
+                                player_index: 0,
+                                point_value: 2,
+                                score_type: crate::score::ScoreType::Play(
+                                    crate::score::PlayScoreType::Fifteen,
+                                ),
+                            }],
+                            claimed_points: 0,
+                            optimal_points: 2,
+                        }
+                    )
                 ))
             );
 
@@ -212,7 +232,14 @@ mod test {
             assert_eq!(
                 play_score(&mut game, vec![score_event.clone()]),
                 Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::OverpeggingIsNotAllowed(score_event)
+                    game_process_return::PlayScoreError::OverpeggingIsNotAllowed(
+                        crate::score::manual::ScoreDiff {
+                            overclaimed: vec![score_event],
+                            missed: vec![],
+                            claimed_points: 2,
+                            optimal_points: 0,
+                        }
+                    )
                 ))
             );
             assert_eq!(game.players[1].front_peg_pos, 0);
@@ -237,7 +264,20 @@ mod test {
             assert_eq!(
                 play_score(&mut game, vec![]),
                 Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed
+                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed(
+                        crate::score::manual::ScoreDiff {
+                            overclaimed: vec![],
+                            missed: vec![crate::score::ScoreEvent {
+                                player_index: 0,
+                                point_value: 1,
+                                score_type: crate::score::ScoreType::Play(
+                                    crate::score::PlayScoreType::LastCard,
+                                ),
+                            }],
+                            claimed_points: 0,
+                            optimal_points: 1,
+                        }
+                    )
                 ))
             );
             assert_eq!(game.state, crate::GameState::PlayScore);
@@ -291,7 +331,20 @@ mod test {
             assert_eq!(
                 play_score(&mut game, vec![]),
                 Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed
+                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed(
+                        crate::score::manual::ScoreDiff {
+                            overclaimed: vec![],
+                            missed: vec![crate::score::ScoreEvent {
+                                player_index: 1,
+                                point_value: 2,
+                                score_type: crate::score::ScoreType::Play(
+                                    crate::score::PlayScoreType::ThirtyOne,
+                                ),
+                            }],
+                            claimed_points: 0,
+                            optimal_points: 2,
+                        }
+                    )
                 ))
             );
             assert_eq!(game.state, crate::GameState::PlayScore);
@@ -327,7 +380,12 @@ mod test {
                 play_score(&mut game, score_events.clone()),
                 Err(game_process_return::Error::PlayScoreError(
                     game_process_return::PlayScoreError::OverpeggingIsNotAllowed(
-                        score_events[1].clone()
+                        crate::score::manual::ScoreDiff {
+                            overclaimed: vec![score_events[1].clone()],
+                            missed: vec![],
+                            claimed_points: 4,
+                            optimal_points: 2,
+                        }
                     )
                 ))
             );
@@ -364,15 +422,35 @@ mod test {
     mod underpegging {
         mod no_muggins {
             use super::super::play_card;
+            use super::super::play_score;
             use super::super::set_up_game;
             use crate::game_process_return;
+            use crate::settings::RuleVariant;
             use crate::util;
 
             #[test]
             fn no_valid_correctly_pegged() {}
 
+            // The fifteen is left unclaimed, so it isn't pegged, and the play carries on
             #[test]
-            fn one_valid_underpegged() {}
+            fn one_valid_underpegged() {
+                let mut game = set_up_game(RuleVariant::TwoStandard, true, true, false);
+
+                game.players[1].hand = vec![util::return_card('5', 'H')];
+                game.players[0].hand = vec![util::return_card('T', 'H')];
+
+                play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+                play_score(&mut game, vec![]).unwrap();
+                play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+
+                assert_eq!(
+                    play_score(&mut game, vec![]),
+                    Ok(game_process_return::Success::PlayScore(vec![]))
+                );
+                assert_eq!(game.state, crate::GameState::PlayWaitForCard);
+                assert_eq!(game.index_active, Some(1));
+                assert_eq!(game.players[0].front_peg_pos, 0);
+            }
 
             #[test]
             fn multiple_valid_completely_underpegged() {}
@@ -387,11 +465,6 @@ mod test {
             fn thirty_one_underpegged() {}
         }
         mod muggins {
-            use super::super::play_card;
-            use super::super::set_up_game;
-            use crate::game_process_return;
-            use crate::util;
-
             #[test]
             fn no_valid_correctly_pegged() {}
 
@@ -433,80 +506,83 @@ pub(crate) fn play_score(
     // in the previous PlayWaitForCard state or that the index_active has looped around to the
     // last_player_index
     if game.index_active == game.last_player_index {
-        let mut optimal_scoring = score::play::play_score(
-            game.index_active.unwrap(),
-            &game.play_groups.last().unwrap(),
-        );
-
-        // If the last play was a go, add the LastCard ScoreEvent to the optimal scoring
-        if game.last_play_was_go {
-            optimal_scoring.push(score::ScoreEvent {
-                point_value: 1,
-                player_index: game.index_active.unwrap(),
-                score_type: score::ScoreType::Play(score::PlayScoreType::LastCard),
-            });
+        // Claims grouped differently from the optimal scoring are brought to the same form first
+        let selections = score::manual::normalize(&selections);
+        let score_diff = score::manual::diff(&selections, &optimal_scoring(game));
+        if !score_diff.overclaimed.is_empty() {
+            return Err(game_process_return::Error::PlayScoreError(
+                game_process_return::PlayScoreError::OverpeggingIsNotAllowed(score_diff),
+            ));
         }
-
-        let mut score_total = 0;
-
-        for selection in &selections {
-            let mut is_in_optimal = false;
-
-            for score in &optimal_scoring {
-                if *selection == *score {
-                    is_in_optimal = true;
-                }
-            }
-
-            //TODO Remove that clone somehow
-            if !is_in_optimal {
-                return Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::OverpeggingIsNotAllowed(selection.clone()),
-                ));
-            }
-
-            score_total += selection.point_value;
+        if !score_diff.missed.is_empty() && !game.settings.unwrap().is_underpegging {
+            return Err(game_process_return::Error::PlayScoreError(
+                game_process_return::PlayScoreError::UnderpeggingIsNotAllowed(score_diff),
+            ));
         }
-
-        for score in &optimal_scoring {
-            let mut is_in_selections = false;
-
-            for selection in &selections {
-                if *selection == *score {
-                    is_in_selections = true;
-                }
-            }
-
-            if !is_in_selections {
-                return Err(game_process_return::Error::PlayScoreError(
-                    game_process_return::PlayScoreError::UnderpeggingIsNotAllowed,
-                ));
-            }
-        }
-
-        util::process_score(game, game.index_active.unwrap() as usize, score_total);
-
-        if !game.last_play_was_go && game.play_groups.last().unwrap().total != 31 {
-            game.state = crate::GameState::PlayWaitForCard;
+        if util::process_score_events(game, game.index_active.unwrap() as usize, &selections) {
+            game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
+        } else if crate::state_logic::show::is_muggins_called(game) {
+            // The player who counted stays active while their count is in question
+            game.missed_score_events = score_diff.missed;
+            util::set_state(game, crate::GameState::PlayMuggins);
         } else {
-            game.state = crate::GameState::ResetPlay;
+            finish_play_count(game);
         }
 
-        game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
-
         Ok(game_process_return::Success::PlayScore(selections))
     }
     // If the player scoring sent a Go in the last PlayWaitForCard and it is not the last card
-    else {
-        if selections.len() > 0 {
-            Err(game_process_return::Error::PlayScoreError(
-                game_process_return::PlayScoreError::OverpeggingIsNotAllowed(selections[0].clone()),
-            ))
-        } else {
-            game.state = crate::GameState::PlayWaitForCard;
-            game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
+    else if !selections.is_empty() {
+        Err(game_process_return::Error::PlayScoreError(
+            game_process_return::PlayScoreError::OverpeggingIsNotAllowed(score::manual::diff(
+                &selections,
+                &[],
+            )),
+        ))
+    } else {
+        util::set_state(game, crate::GameState::PlayWaitForCard);
+        game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
 
-            Ok(game_process_return::Success::PlayScore(vec![]))
-        }
+        Ok(game_process_return::Success::PlayScore(vec![]))
+    }
+}
+
+// Moves on from the count of the last card to the next card, the reset of the play, or the
+// GoDecision of the player who played last after a go under Back Up 10
+pub(crate) fn finish_play_count(game: &mut crate::GameImpl) {
+    // The player who played last stays active to make their GoDecision
+    if game.last_play_was_go && game.settings.unwrap().is_back_up_ten {
+        util::set_state(game, crate::GameState::PlayGoDecision);
+        return;
+    } else if !game.last_play_was_go && game.play_groups.last().unwrap().total != 31 {
+        util::set_state(game, crate::GameState::PlayWaitForCard);
+    } else {
+        util::set_state(game, crate::GameState::ResetPlay);
+    }
+
+    game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
+}
+
+// Returns the points earned by the last card played for the active player; nothing when the active
+// player sent a Go and someone else played the last card
+pub(crate) fn optimal_scoring(game: &crate::GameImpl) -> Vec<score::ScoreEvent> {
+    if game.index_active != game.last_player_index {
+        return Vec::new();
+    }
+    let mut optimal_scoring = score::play::play_score_with_rules(
+        game.index_active.unwrap(),
+        &game.play_groups,
+        &game.settings.unwrap().scoring_rules,
+    );
+
+    // If the last play was a go, add the LastCard ScoreEvent to the optimal scoring unless the
+    // point is decided with a GoDecision under Back Up 10
+    if game.last_play_was_go && !game.settings.unwrap().is_back_up_ten {
+        optimal_scoring.push(score::ScoreEvent {
+            point_value: game.settings.unwrap().scoring_rules.go_points(),
+            player_index: game.index_active.unwrap(),
+            score_type: score::ScoreType::Play(score::PlayScoreType::LastCard),
+        });
     }
+    optimal_scoring
 }
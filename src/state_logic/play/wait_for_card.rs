@@ -1,6 +1,5 @@
 use crate::game_process_return;
 use crate::score;
-use crate::util;
 
 // State that handles a PlayTurn object (enum with either the card played or a go); must check
 // that the play is valid, adds the card to the last member of the play_groups variable (what
@@ -401,6 +400,65 @@ mod test {
         assert_eq!(game.players[1].front_peg_pos, 1);
     }
 
+    // With the go worth two the last card pegs two and with runs across a reset the first card of
+    // the next PlayGroup may continue a run of the cards before it
+    #[test]
+    fn test_scoring_rules() {
+        let mut game = set_up_game(
+            crate::settings::RuleVariant::TwoStandard,
+            false,
+            false,
+            false,
+        );
+        let rules = &mut game.settings.as_mut().unwrap().scoring_rules;
+        rules.is_go_worth_two = true;
+        rules.is_run_across_reset = true;
+
+        game.players[1].hand = vec![
+            return_card('9', 'S'),
+            return_card('J', 'S'),
+            return_card('K', 'S'),
+        ];
+        game.players[0].hand = vec![return_card('T', 'S'), return_card('Q', 'S')];
+
+        play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+        play_card(&mut game, crate::PlayTurn::CardSelected(0)).unwrap();
+        play_card(&mut game, crate::PlayTurn::CardSelected(1)).unwrap();
+        play_card(&mut game, crate::PlayTurn::Go).unwrap();
+        assert_eq!(
+            play_card(&mut game, crate::PlayTurn::Go),
+            Ok(game_process_return::Success::PlayWaitForCard(
+                game_process_return::PlayWaitForCardReturn::AutomaticScoring(vec![
+                    crate::score::ScoreEvent {
+                        player_index: 1,
+                        point_value: 2,
+                        score_type: crate::score::ScoreType::Play(
+                            crate::score::PlayScoreType::LastCard
+                        )
+                    }
+                ])
+            ))
+        );
+        // A run of three and the go
+        assert_eq!(game.players[1].front_peg_pos, 5);
+
+        crate::state_logic::play::reset_play(&mut game).unwrap();
+        assert_eq!(
+            play_card(&mut game, crate::PlayTurn::CardSelected(1)),
+            Ok(game_process_return::Success::PlayWaitForCard(
+                game_process_return::PlayWaitForCardReturn::AutomaticScoring(vec![
+                    crate::score::ScoreEvent {
+                        player_index: 0,
+                        point_value: 4,
+                        score_type: crate::score::ScoreType::Play(
+                            crate::score::PlayScoreType::Straight(4)
+                        )
+                    }
+                ])
+            ))
+        );
+    }
+
     // Test that it awards no extra points (you get two from the scoring function already) for
     // a value of 31 and that the state is set to ResetPlay with automatic scoring
     #[test]
@@ -501,6 +559,13 @@ pub(crate) fn play_card(
                     ));
                 }
             }
+            crate::settings::RuleVariant::Custom(custom) => {
+                if index >= custom.hand_size() {
+                    return Err(game_process_return::Error::PlayWaitForCardError(
+                        game_process_return::PlayTurnError::IndexIsWithinTheHandWithThisCustomVariant
+                    ));
+                }
+            }
             _ => {
                 if index > 3 {
                     return Err(game_process_return::Error::PlayWaitForCardError(
@@ -519,7 +584,7 @@ pub(crate) fn play_card(
         let card = game.players[game.index_active.unwrap() as usize].hand[index as usize];
 
         // Return an error if the card would bring the play total over 31
-        if game.play_groups.last().unwrap().total + crate::deck::return_play_value(card) > 31 {
+        if game.play_groups.last().unwrap().total + game.settings.unwrap().play_value(card) > 31 {
             return Err(game_process_return::Error::PlayWaitForCardError(
                 game_process_return::PlayTurnError::PlayGroupTotalMayNotExceed31,
             ));
@@ -528,13 +593,20 @@ pub(crate) fn play_card(
         // Update the last PlayGroup to include the given card index and add the value to the
         // PlayGroup total
         game.play_groups.last_mut().unwrap().cards.push(card);
-        game.play_groups.last_mut().unwrap().total += crate::deck::return_play_value(card);
+        game.play_groups.last_mut().unwrap().total += game.settings.unwrap().play_value(card);
         game.last_player_index = game.index_active;
+        let (player_index, total) = (
+            game.index_active.unwrap(),
+            game.play_groups.last().unwrap().total,
+        );
+        crate::observer::notify(game, |observer| {
+            observer.on_card_played(player_index, card, total)
+        });
 
         game.last_play_was_go = false;
 
         if game.settings.unwrap().is_manual_scoring {
-            game.state = crate::GameState::PlayScore;
+            crate::util::set_state(game, crate::GameState::PlayScore);
 
             Ok(game_process_return::Success::PlayWaitForCard(
                 game_process_return::PlayWaitForCardReturn::ManualScoring,
@@ -543,24 +615,22 @@ pub(crate) fn play_card(
             let index_active = game.index_active.unwrap();
 
             // Caluclate ScoreEvents
-            let scoring_vec =
-                score::play::play_score(index_active, game.play_groups.last().unwrap());
-
-            // Calculate total score change
-            let mut score_change = 0;
-            for score_event in &scoring_vec {
-                score_change += score_event.point_value;
-            }
+            let scoring_vec = score::play::play_score_with_rules(
+                index_active,
+                &game.play_groups,
+                &game.settings.unwrap().scoring_rules,
+            );
 
             // Process score change
-            crate::util::process_score(game, index_active as usize, score_change);
+            let is_win =
+                crate::util::process_score_events(game, index_active as usize, &scoring_vec);
 
             // Change index_active to next player
             game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
 
-            // Change state to ResetPlay when the PlayGroup total is 31
-            if game.play_groups.last().unwrap().total == 31 {
-                game.state = crate::GameState::ResetPlay;
+            // Change state to ResetPlay when the PlayGroup total is 31 unless the game has been won
+            if !is_win && game.play_groups.last().unwrap().total == 31 {
+                crate::util::set_state(game, crate::GameState::ResetPlay);
             }
 
             // Return
@@ -578,14 +648,13 @@ pub(crate) fn play_card(
             .enumerate()
         {
             // Return an error if the player can play a card, but has sent a Go
-            if !has_card_been_played(game, index as u8) {
-                if game.play_groups.last().unwrap().total + crate::deck::return_play_value(*card)
+            if !has_card_been_played(game, index as u8)
+                && game.play_groups.last().unwrap().total + game.settings.unwrap().play_value(*card)
                     <= 31
-                {
-                    return Err(game_process_return::Error::PlayWaitForCardError(
-                        game_process_return::PlayTurnError::MustPlayCardIfAble,
-                    ));
-                }
+            {
+                return Err(game_process_return::Error::PlayWaitForCardError(
+                    game_process_return::PlayTurnError::MustPlayCardIfAble,
+                ));
             }
         }
 
@@ -595,7 +664,7 @@ pub(crate) fn play_card(
 
         // Send game to PlayScore with manual scoring
         if game.settings.unwrap().is_manual_scoring {
-            game.state = crate::GameState::PlayScore;
+            crate::util::set_state(game, crate::GameState::PlayScore);
             Ok(game_process_return::Success::PlayWaitForCard(
                 game_process_return::PlayWaitForCardReturn::ManualScoring,
             ))
@@ -603,17 +672,27 @@ pub(crate) fn play_card(
             // With automatic scoring, check if everyone has gone and if the player represented by
             // the last_player_index should receive a point
             game.index_active = Some((game.index_active.unwrap() + 1) % game.players.len() as u8);
-            if last_card {
-                game.state = crate::GameState::ResetPlay;
-                crate::util::process_score(game, game.last_player_index.unwrap() as usize, 1);
+            // With Back Up 10 the last player to play chooses what to do with the point instead
+            if last_card && game.settings.unwrap().is_back_up_ten {
+                game.index_active = game.last_player_index;
+                crate::util::set_state(game, crate::GameState::PlayGoDecision);
+                Ok(game_process_return::Success::PlayWaitForCard(
+                    game_process_return::PlayWaitForCardReturn::GoDecision,
+                ))
+            } else if last_card {
+                crate::util::set_state(game, crate::GameState::ResetPlay);
+                let go_point = score::ScoreEvent {
+                    player_index: game.last_player_index.unwrap(),
+                    point_value: game.settings.unwrap().scoring_rules.go_points(),
+                    score_type: score::ScoreType::Play(score::PlayScoreType::LastCard),
+                };
+                crate::util::process_score_events(
+                    game,
+                    game.last_player_index.unwrap() as usize,
+                    std::slice::from_ref(&go_point),
+                );
                 Ok(game_process_return::Success::PlayWaitForCard(
-                    game_process_return::PlayWaitForCardReturn::AutomaticScoring(vec![
-                        score::ScoreEvent {
-                            player_index: game.last_player_index.unwrap(),
-                            point_value: 1,
-                            score_type: score::ScoreType::Play(score::PlayScoreType::LastCard),
-                        },
-                    ]),
+                    game_process_return::PlayWaitForCardReturn::AutomaticScoring(vec![go_point]),
                 ))
             } else {
                 Ok(game_process_return::Success::PlayWaitForCard(
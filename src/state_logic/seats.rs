@@ -0,0 +1,226 @@
+use crate::game_process_return;
+
+// Decides which seat may send an event to Game::process_event_from in each state
+//
+// The dealer deals, calls nibs, and counts the crib; the player to the dealer's left cuts the
+// starter; the active player bids, plays, chooses after a go, and counts their own hand or play;
+// and only an opponent of the player whose count is in question may call muggins. Cuts for the
+// first deal, including the cuts to break a tie, may be confirmed from any seat as may the
+// confirmations which only set the pace of the game and the claims that a player ran out of time
+
+#[cfg(test)]
+mod test {
+    use crate::game_process_return::{Error, Success};
+    use crate::settings::{GameSettings, RuleVariant, ScoringRules, VictorDealerOption};
+    use crate::{Game, GameEvent, GameState, PlayTurn};
+
+    fn settings(variant: RuleVariant, victor_dealer_option: VictorDealerOption) -> GameSettings {
+        GameSettings {
+            variant,
+            victor_dealer_option,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }
+    }
+
+    #[test]
+    fn two_player_hand() {
+        let mut game = Game::from_seed(8);
+        let event = GameEvent::GameSetup(settings(
+            RuleVariant::TwoStandard,
+            VictorDealerOption::TwoPlayers,
+        ));
+        assert!(game.process_event_from(0, event).is_ok());
+        assert_eq!(
+            game.process_event_from(2, GameEvent::Confirmation),
+            Err(Error::SeatNotAllowed)
+        );
+
+        // Either player may cut for the deal, however many times it takes
+        let mut seat = 0;
+        while game.state() == GameState::CutInitial {
+            game.process_event_from(seat, GameEvent::Confirmation)
+                .unwrap();
+            seat = 1 - seat;
+        }
+        let dealer = game.index_dealer().unwrap();
+        let pone = 1 - dealer;
+        assert_eq!(
+            game.process_event_from(pone, GameEvent::Confirmation),
+            Err(Error::NotYourTurn)
+        );
+        game.process_event_from(dealer, GameEvent::Confirmation)
+            .unwrap();
+        game.process_event_from(pone, GameEvent::Confirmation)
+            .unwrap();
+        assert_eq!(game.state(), GameState::Discard);
+
+        // Each player discards on their own and may not discard for their opponent
+        let mut discards = vec![vec![], vec![]];
+        discards[dealer as usize] = vec![0, 1];
+        assert_eq!(
+            game.process_event_from(pone, GameEvent::DiscardSelection(discards.clone())),
+            Err(Error::SeatNotAllowed)
+        );
+        assert_eq!(
+            game.process_event_from(dealer, GameEvent::DiscardSelection(discards)),
+            Ok(Success::DiscardHeld)
+        );
+        assert_eq!(game.crib(), &[]);
+        let mut discards = vec![vec![], vec![]];
+        discards[pone as usize] = vec![4, 4];
+        assert!(matches!(
+            game.process_event_from(pone, GameEvent::DiscardSelection(discards)),
+            Err(Error::DiscardErrors(_))
+        ));
+        let mut discards = vec![vec![], vec![]];
+        discards[pone as usize] = vec![4, 5];
+        let hand = game.players()[pone as usize].hand.clone();
+        assert_eq!(
+            game.process_event_from(pone, GameEvent::DiscardSelection(discards)),
+            Ok(Success::Discard)
+        );
+        assert_eq!(&game.crib()[2..], &hand[4..]);
+
+        // The pone cuts the starter and leads
+        assert_eq!(
+            game.process_event_from(dealer, GameEvent::Confirmation),
+            Err(Error::NotYourTurn)
+        );
+        game.process_event_from(pone, GameEvent::Confirmation)
+            .unwrap();
+        assert_eq!(game.state(), GameState::PlayWaitForCard);
+        assert_eq!(
+            game.process_event_from(dealer, GameEvent::Play(PlayTurn::CardSelected(0))),
+            Err(Error::NotYourTurn)
+        );
+        assert!(game
+            .process_event_from(pone, GameEvent::Play(PlayTurn::CardSelected(0)))
+            .is_ok());
+    }
+
+    #[test]
+    fn muggins_is_called_by_opponents() {
+        let mut game = crate::GameImpl::new();
+        let settings = settings(
+            RuleVariant::FourPairs,
+            VictorDealerOption::LosersDrawForDealer,
+        );
+        crate::state_logic::game_start::game_setup(&mut game, settings).unwrap();
+        game.index_dealer = Some(3);
+        game.index_active = Some(0);
+        let partner = game.players[0].partner_index.unwrap();
+        let muggins = GameEvent::Muggins(None);
+
+        game.state = GameState::ShowMuggins;
+        assert_eq!(
+            super::check_seat(&game, 0, &muggins),
+            Err(Error::SeatNotAllowed)
+        );
+        assert_eq!(
+            super::check_seat(&game, partner, &muggins),
+            Err(Error::SeatNotAllowed)
+        );
+        assert_eq!(super::check_seat(&game, 1, &muggins), Ok(()));
+
+        // The crib belongs to the dealer whoever was last active
+        game.state = GameState::CribMuggins;
+        assert_eq!(super::check_seat(&game, 0, &muggins), Ok(()));
+        assert_eq!(
+            super::check_seat(&game, 3, &muggins),
+            Err(Error::SeatNotAllowed)
+        );
+        game.state = GameState::CribScore;
+        assert_eq!(
+            super::check_seat(&game, 0, &GameEvent::Confirmation),
+            Err(Error::NotYourTurn)
+        );
+    }
+}
+
+// Returns an error unless the seat may send the event in the game's current state
+pub(crate) fn check_seat(
+    game: &crate::GameImpl,
+    seat: u8,
+    event: &crate::GameEvent,
+) -> Result<(), game_process_return::Error> {
+    // Nobody has a seat before the game is set up
+    if game.state == crate::GameState::GameStart {
+        return Ok(());
+    }
+    let num_players = game.players.len() as u8;
+    if seat >= num_players {
+        return Err(game_process_return::Error::SeatNotAllowed);
+    }
+
+    let index_turn = match (game.state, event) {
+        // Anyone may claim that the player being waited on has run out of time
+        (_, crate::GameEvent::Timeout) => None,
+        // A seat discards for itself alone with an empty selection for every other seat
+        (crate::GameState::Discard, crate::GameEvent::DiscardSelection(discard_indices_group)) => {
+            if discard_indices_group.len() != game.players.len() {
+                return Err(game_process_return::Error::ImplementationError(
+                    game_process_return::ImplError::ThereShouldBeOneDiscardIndicesVectorPerPlayer,
+                ));
+            }
+            if discard_indices_group
+                .iter()
+                .enumerate()
+                .any(|(player_index, discards)| player_index as u8 != seat && !discards.is_empty())
+            {
+                return Err(game_process_return::Error::SeatNotAllowed);
+            }
+            None
+        }
+        (crate::GameState::Deal, _)
+        | (crate::GameState::NibsCheck, _)
+        | (crate::GameState::CribScore, _) => game.index_dealer,
+        (crate::GameState::CutStarter, _) => game
+            .index_dealer
+            .map(|index_dealer| (index_dealer + 1) % num_players),
+        (crate::GameState::Auction, _)
+        | (crate::GameState::PlayWaitForCard, _)
+        | (crate::GameState::PlayScore, _)
+        | (crate::GameState::PlayGoDecision, _)
+        | (crate::GameState::ShowScore, _) => game.index_active,
+        (crate::GameState::PlayMuggins, _) | (crate::GameState::ShowMuggins, _) => {
+            check_opponent(game, seat, game.index_active)?;
+            None
+        }
+        (crate::GameState::CribMuggins, _) => {
+            check_opponent(game, seat, game.index_dealer)?;
+            None
+        }
+        _ => None,
+    };
+
+    match index_turn {
+        Some(index_turn) if index_turn != seat => Err(game_process_return::Error::NotYourTurn),
+        _ => Ok(()),
+    }
+}
+
+// Only an opponent of the player whose count is in question may call muggins on it
+fn check_opponent(
+    game: &crate::GameImpl,
+    seat: u8,
+    player_index: Option<u8>,
+) -> Result<(), game_process_return::Error> {
+    match player_index {
+        Some(player_index)
+            if player_index == seat
+                || game.players[player_index as usize].partner_index == Some(seat) =>
+        {
+            Err(game_process_return::Error::SeatNotAllowed)
+        }
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,485 @@
+use crate::game_process_return;
+use crate::score;
+use crate::util;
+
+// State that scores each player's hand with the starter card in turn starting with the player to
+// the left of the dealer; automatically with a Confirmation event or manually with a
+// ManScoreSelection event. After the dealer has scored their hand the game moves on to CribSort
+//
+// With muggins every manual count is followed by ShowMuggins, where the opponents of the player
+// who counted may call any points they missed with Muggins events until a Muggins(None) event
+
+#[cfg(test)]
+mod test {
+    use super::super::play::test_util::set_up_game;
+    use super::{process_show_muggins, process_show_score};
+    use crate::game_process_return;
+    use crate::settings::RuleVariant;
+    use crate::util::return_card;
+
+    // Sets up a two player game at the start of the show with known hands; player 1 is the pone
+    // and player 0 is the dealer
+    fn set_up_show(is_manual: bool) -> crate::GameImpl {
+        let mut game = set_up_game(RuleVariant::TwoStandard, is_manual, false, false);
+        game.players[0].hand = vec![
+            return_card('2', 'H'),
+            return_card('4', 'D'),
+            return_card('6', 'C'),
+            return_card('8', 'S'),
+        ];
+        game.players[1].hand = vec![
+            return_card('5', 'H'),
+            return_card('T', 'D'),
+            return_card('J', 'C'),
+            return_card('Q', 'S'),
+        ];
+        game.starter_card = Some(return_card('K', 'H'));
+        game.index_dealer = Some(0);
+        game.index_active = Some(1);
+        game.state = crate::GameState::ShowScore;
+        game
+    }
+
+    #[test]
+    fn automatic_pone_then_dealer() {
+        let mut game = set_up_show(false);
+
+        let expected = crate::score::show::score_hand(
+            1,
+            &game.players[1].hand.clone(),
+            game.starter_card.unwrap(),
+            false,
+        );
+        assert_eq!(
+            process_show_score(&mut game, None),
+            Ok(game_process_return::Success::ShowScore(expected))
+        );
+        // 5 with each of the four ten cards and a run of four
+        assert_eq!(game.players[1].front_peg_pos, 12);
+        assert_eq!(game.state, crate::GameState::ShowScore);
+        assert_eq!(game.index_active, Some(0));
+
+        assert_eq!(
+            process_show_score(&mut game, None),
+            Ok(game_process_return::Success::ShowScore(vec![]))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 0);
+        assert_eq!(game.state, crate::GameState::CribSort);
+        assert_eq!(game.index_active, Some(0));
+    }
+
+    #[test]
+    fn automatic_win() {
+        let mut game = set_up_show(false);
+        game.players[1].front_peg_pos = 115;
+
+        // The front peg stops in the game hole
+        process_show_score(&mut game, None).unwrap();
+        assert_eq!(game.players[1].front_peg_pos, 121);
+        assert_eq!(game.state, crate::GameState::Win);
+    }
+
+    #[test]
+    fn automatic_rejects_selection() {
+        let mut game = set_up_show(false);
+        assert_eq!(
+            process_show_score(&mut game, Some(vec![])),
+            Err(game_process_return::Error::ExpectedEvent(vec![
+                game_process_return::Event::Confirmation
+            ]))
+        );
+    }
+
+    #[test]
+    fn manual_correctly_pegged() {
+        let mut game = set_up_show(true);
+        let optimal = crate::score::show::score_hand(
+            1,
+            &game.players[1].hand.clone(),
+            game.starter_card.unwrap(),
+            false,
+        );
+
+        assert_eq!(
+            process_show_score(&mut game, None),
+            Err(game_process_return::Error::ExpectedEvent(vec![
+                game_process_return::Event::ManScoreSelection
+            ]))
+        );
+        assert_eq!(
+            process_show_score(&mut game, Some(optimal.clone())),
+            Ok(game_process_return::Success::ShowScore(optimal))
+        );
+        assert_eq!(game.players[1].front_peg_pos, 12);
+        assert_eq!(game.index_active, Some(0));
+    }
+
+    // A double run claimed as a single straight is the same as its two straights and pair
+    #[test]
+    fn manual_equivalent_claims() {
+        let mut game = set_up_show(true);
+        game.players[1].hand = vec![
+            return_card('3', 'H'),
+            return_card('4', 'D'),
+            return_card('4', 'C'),
+            return_card('5', 'S'),
+        ];
+        let claims = vec![
+            crate::score::ScoreEvent {
+                score_type: crate::score::ScoreType::Show(crate::score::ShowScoreType::Straight(
+                    game.players[1].hand.clone(),
+                )),
+                player_index: 1,
+                point_value: 8,
+            },
+            crate::score::ScoreEvent {
+                score_type: crate::score::ScoreType::Show(crate::score::ShowScoreType::Fifteen(
+                    vec![return_card('K', 'H'), return_card('5', 'S')],
+                )),
+                player_index: 1,
+                point_value: 2,
+            },
+        ];
+
+        assert_eq!(
+            process_show_score(&mut game, Some(claims.clone())),
+            Ok(game_process_return::Success::ShowScore(
+                crate::score::manual::normalize(&claims)
+            ))
+        );
+        assert_eq!(game.players[1].front_peg_pos, 10);
+    }
+
+    #[test]
+    fn manual_overpegged() {
+        let mut game = set_up_show(true);
+        let optimal = crate::score::show::score_hand(
+            1,
+            &game.players[1].hand.clone(),
+            game.starter_card.unwrap(),
+            false,
+        );
+        let claim = crate::score::ScoreEvent {
+            score_type: crate::score::ScoreType::Show(crate::score::ShowScoreType::Pair(vec![
+                return_card('T', 'D'),
+                return_card('J', 'C'),
+            ])),
+            player_index: 1,
+            point_value: 2,
+        };
+
+        assert_eq!(
+            process_show_score(&mut game, Some(vec![claim.clone()])),
+            Err(game_process_return::Error::ShowScoreError(
+                game_process_return::ShowScoreError::OverpeggingIsNotAllowed(
+                    crate::score::manual::ScoreDiff {
+                        overclaimed: vec![claim],
+                        missed: optimal,
+                        claimed_points: 2,
+                        optimal_points: 12,
+                    }
+                )
+            ))
+        );
+        assert_eq!(game.players[1].front_peg_pos, 0);
+        assert_eq!(game.state, crate::GameState::ShowScore);
+    }
+
+    #[test]
+    fn manual_underpegged() {
+        let mut game = set_up_show(true);
+        let optimal = crate::score::show::score_hand(
+            1,
+            &game.players[1].hand.clone(),
+            game.starter_card.unwrap(),
+            false,
+        );
+        assert_eq!(
+            process_show_score(&mut game, Some(vec![])),
+            Err(game_process_return::Error::ShowScoreError(
+                game_process_return::ShowScoreError::UnderpeggingIsNotAllowed(
+                    crate::score::manual::ScoreDiff {
+                        overclaimed: vec![],
+                        missed: optimal,
+                        claimed_points: 0,
+                        optimal_points: 12,
+                    }
+                )
+            ))
+        );
+        assert_eq!(game.players[1].front_peg_pos, 0);
+        assert_eq!(game.state, crate::GameState::ShowScore);
+    }
+
+    // With underpegging enabled a player may claim less than their hand is worth and the points
+    // left unclaimed are not pegged
+    #[test]
+    fn manual_underpegged_when_allowed() {
+        let mut game = set_up_show(true);
+        game.settings.as_mut().unwrap().is_underpegging = true;
+        let claim = crate::score::ScoreEvent {
+            score_type: crate::score::ScoreType::Show(crate::score::ShowScoreType::Fifteen(vec![
+                return_card('5', 'H'),
+                return_card('T', 'D'),
+            ])),
+            player_index: 1,
+            point_value: 2,
+        };
+
+        assert_eq!(
+            process_show_score(&mut game, Some(vec![claim.clone()])),
+            Ok(game_process_return::Success::ShowScore(vec![claim]))
+        );
+        assert_eq!(game.players[1].front_peg_pos, 2);
+        assert_eq!(game.index_active, Some(0));
+    }
+
+    // Sets up the show with muggins where player 1 has counted only one fifteen of their hand
+    fn set_up_show_muggins() -> crate::GameImpl {
+        let mut game = set_up_show(true);
+        game.settings.as_mut().unwrap().is_underpegging = true;
+        game.settings.as_mut().unwrap().is_muggins = true;
+        let claim = fifteen(1, 'D');
+        process_show_score(&mut game, Some(vec![claim])).unwrap();
+        assert_eq!(game.state, crate::GameState::ShowMuggins);
+        assert_eq!(game.index_active, Some(1));
+        game
+    }
+
+    // The fifteen of the five of hearts with the ten card of the given suit
+    fn fifteen(player_index: u8, ten_suit: char) -> crate::score::ScoreEvent {
+        let ten_value = match ten_suit {
+            'D' => 'T',
+            'C' => 'J',
+            _ => 'Q',
+        };
+        crate::score::ScoreEvent {
+            score_type: crate::score::ScoreType::Show(crate::score::ShowScoreType::Fifteen(vec![
+                return_card('5', 'H'),
+                return_card(ten_value, ten_suit),
+            ])),
+            player_index,
+            point_value: 2,
+        }
+    }
+
+    #[test]
+    fn muggins_called_then_proceed() {
+        let mut game = set_up_show_muggins();
+
+        assert_eq!(
+            process_show_muggins(&mut game, Some(vec![fifteen(0, 'C')])),
+            Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Called(vec![fifteen(0, 'C')])
+            ))
+        );
+        assert_eq!(game.players[0].front_peg_pos, 2);
+        assert_eq!(game.players[1].front_peg_pos, 2);
+        assert_eq!(game.state, crate::GameState::ShowMuggins);
+
+        // The same points can't be called twice and the player who counted can't call muggins
+        assert!(matches!(
+            process_show_muggins(&mut game, Some(vec![fifteen(0, 'C')])),
+            Err(game_process_return::Error::MugginsError(
+                game_process_return::MugginsError::CalledPointsWereNotMissed(_)
+            ))
+        ));
+        assert_eq!(
+            process_show_muggins(&mut game, Some(vec![fifteen(1, 'S')])),
+            Err(game_process_return::Error::MugginsError(
+                game_process_return::MugginsError::OnlyAnOpponentMayCallMuggins
+            ))
+        );
+
+        assert_eq!(
+            process_show_muggins(&mut game, None),
+            Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Complete
+            ))
+        );
+        assert!(game.missed_score_events.is_empty());
+        assert_eq!(game.state, crate::GameState::ShowScore);
+        assert_eq!(game.index_active, Some(0));
+    }
+
+    #[test]
+    fn muggins_call_wins() {
+        let mut game = set_up_show_muggins();
+        game.players[0].front_peg_pos = 120;
+
+        process_show_muggins(&mut game, Some(vec![fifteen(0, 'S')])).unwrap();
+        assert_eq!(game.players[0].front_peg_pos, 121);
+        assert_eq!(game.state, crate::GameState::Win);
+    }
+}
+
+pub(crate) fn process_show_score(
+    game: &mut crate::GameImpl,
+    selections: Option<Vec<score::ScoreEvent>>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let index_active = game.index_active.unwrap();
+
+    let (scoring, missed) = check_selections(
+        game,
+        selections,
+        optimal_scoring(game),
+        game_process_return::Error::ShowScoreError,
+    )?;
+
+    if !util::process_score_events(game, index_active as usize, &scoring) {
+        if is_muggins_called(game) {
+            game.missed_score_events = missed;
+            util::set_state(game, crate::GameState::ShowMuggins);
+        } else {
+            finish_show_count(game);
+        }
+    }
+
+    Ok(game_process_return::Success::ShowScore(scoring))
+}
+
+// Processes a call of muggins on the hand just counted, or moves on with Muggins(None)
+pub(crate) fn process_show_muggins(
+    game: &mut crate::GameImpl,
+    selections: Option<Vec<score::ScoreEvent>>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    match selections {
+        Some(selections) => call_muggins(game, game.index_active.unwrap(), selections),
+        None => {
+            game.missed_score_events.clear();
+            util::set_state(game, crate::GameState::ShowScore);
+            finish_show_count(game);
+            Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Complete,
+            ))
+        }
+    }
+}
+
+// Moves on to the next hand to be counted or to the crib once the dealer's hand has been counted
+fn finish_show_count(game: &mut crate::GameImpl) {
+    let index_active = game.index_active.unwrap();
+    if Some(index_active) == game.index_dealer {
+        util::set_state(game, crate::GameState::CribSort);
+    } else {
+        game.index_active = Some((index_active + 1) % game.players.len() as u8);
+    }
+}
+
+// Returns the full count of the hand of the active player
+pub(crate) fn optimal_scoring(game: &crate::GameImpl) -> Vec<score::ScoreEvent> {
+    let index_active = game.index_active.unwrap();
+    score::show::score_hand_with_rules(
+        index_active,
+        &game.players[index_active as usize].hand,
+        game.starter_card.unwrap(),
+        false,
+        &game.settings.unwrap().scoring_rules,
+    )
+}
+
+// Returns the ScoreEvents to be applied for a hand or crib along with the ScoreEvents left
+// unclaimed: the optimal scoring when automatic scoring is enabled or the manual selections after
+// checking them against the optimal scoring, where points may be left unclaimed only when
+// underpegging is enabled.
+// to_error wraps any problem with the selections in the Error for the state doing the scoring
+pub(crate) fn check_selections(
+    game: &crate::GameImpl,
+    selections: Option<Vec<score::ScoreEvent>>,
+    optimal_scoring: Vec<score::ScoreEvent>,
+    to_error: fn(game_process_return::ShowScoreError) -> game_process_return::Error,
+) -> Result<(Vec<score::ScoreEvent>, Vec<score::ScoreEvent>), game_process_return::Error> {
+    match (game.settings.unwrap().is_manual_scoring, selections) {
+        (false, None) => Ok((optimal_scoring, Vec::new())),
+        (false, Some(_)) => Err(game_process_return::Error::ExpectedEvent(vec![
+            game_process_return::Event::Confirmation,
+        ])),
+        (true, None) => Err(game_process_return::Error::ExpectedEvent(vec![
+            game_process_return::Event::ManScoreSelection,
+        ])),
+        (true, Some(selections)) => {
+            // Each selection must match a ScoreEvent of the optimal scoring that hasn't already
+            // been matched such that the same ScoreEvent can't be claimed twice, once the
+            // selections have been brought to the same form as the optimal scoring
+            let selections = score::manual::normalize(&selections);
+            let score_diff = score::manual::diff(&selections, &optimal_scoring);
+            if !score_diff.overclaimed.is_empty() {
+                return Err(to_error(
+                    game_process_return::ShowScoreError::OverpeggingIsNotAllowed(score_diff),
+                ));
+            }
+            if !score_diff.missed.is_empty() && !game.settings.unwrap().is_underpegging {
+                return Err(to_error(
+                    game_process_return::ShowScoreError::UnderpeggingIsNotAllowed(score_diff),
+                ));
+            }
+
+            Ok((selections, score_diff.missed))
+        }
+    }
+}
+
+// Returns whether the opponents of a player who has just counted manually may call muggins
+pub(crate) fn is_muggins_called(game: &crate::GameImpl) -> bool {
+    let settings = game.settings.unwrap();
+    settings.is_manual_scoring && settings.is_muggins
+}
+
+// Pegs the points called in a muggins state for the opponent of the player whose count is in
+// question, staying in the state for any further calls unless the points win the game. Each called
+// ScoreEvent is given for the caller and must match a ScoreEvent missed by the count that hasn't
+// been called already
+pub(crate) fn call_muggins(
+    game: &mut crate::GameImpl,
+    index_counted: u8,
+    selections: Vec<score::ScoreEvent>,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let index_caller = match selections.first() {
+        Some(score_event) => score_event.player_index,
+        None => {
+            return Ok(game_process_return::Success::Muggins(
+                game_process_return::MugginsReturn::Called(selections),
+            ))
+        }
+    };
+    if index_caller as usize >= game.players.len()
+        || index_caller == index_counted
+        || game.players[index_counted as usize].partner_index == Some(index_caller)
+        || selections
+            .iter()
+            .any(|score_event| score_event.player_index != index_caller)
+    {
+        return Err(game_process_return::Error::MugginsError(
+            game_process_return::MugginsError::OnlyAnOpponentMayCallMuggins,
+        ));
+    }
+
+    // The missed ScoreEvents are those of the player who counted
+    let as_counted: Vec<score::ScoreEvent> = score::manual::normalize(&selections)
+        .into_iter()
+        .map(|score_event| score::ScoreEvent {
+            player_index: index_counted,
+            ..score_event
+        })
+        .collect();
+    let score_diff = score::manual::diff(&as_counted, &game.missed_score_events);
+    if !score_diff.overclaimed.is_empty() {
+        return Err(game_process_return::Error::MugginsError(
+            game_process_return::MugginsError::CalledPointsWereNotMissed(score_diff),
+        ));
+    }
+    game.missed_score_events = score_diff.missed;
+
+    let called: Vec<score::ScoreEvent> = as_counted
+        .into_iter()
+        .map(|score_event| score::ScoreEvent {
+            player_index: index_caller,
+            ..score_event
+        })
+        .collect();
+    util::process_score_events(game, index_caller as usize, &called);
+
+    Ok(game_process_return::Success::Muggins(
+        game_process_return::MugginsReturn::Called(called),
+    ))
+}
@@ -7,6 +7,23 @@ pub(crate) fn process_sort(
     for player in &mut game.players {
         player.hand.sort();
     }
-    game.state = crate::GameState::Discard;
+
+    // With auction cribbage the players bid for the crib, starting left of the dealer, before
+    // discarding
+    match (game.settings, game.index_dealer) {
+        (Some(settings), Some(index_dealer)) if settings.auction.is_some() => {
+            game.auction = Some(crate::Auction {
+                index_dealer,
+                high_bid: None,
+                has_passed: vec![false; game.players.len()],
+            });
+            game.index_active = Some((index_dealer + 1) % game.players.len() as u8);
+            crate::util::set_state(game, crate::GameState::Auction);
+            return Ok(game_process_return::Success::Sort);
+        }
+        _ => {}
+    }
+
+    crate::util::set_state(game, crate::GameState::Discard);
     Ok(game_process_return::Success::Sort)
 }
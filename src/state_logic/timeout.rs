@@ -0,0 +1,366 @@
+use crate::game_process_return;
+use crate::score;
+use crate::settings;
+use crate::strategy::{self, Strategy};
+
+// Keeps the time limit of the decision being waited on and makes the move decided by the
+// TimeoutPolicy once a Timeout event is sent after the time has run out
+//
+// The clock starts again after every accepted event, other than the discards of a single player
+// which are made while the rest of the table is still deciding
+
+#[cfg(test)]
+mod test {
+    use crate::clock::ManualClock;
+    use crate::game_process_return::{Error, Success};
+    use crate::settings::{
+        GameSettings, RuleVariant, ScoringRules, TimeLimit, TimeLimits, TimeoutPolicy,
+        VictorDealerOption,
+    };
+    use crate::{Game, GameEvent, GameState};
+
+    fn set_up_game(
+        is_manual_scoring: bool,
+        time_limits: Option<TimeLimits>,
+    ) -> (Game, ManualClock) {
+        let clock = ManualClock::new();
+        let mut game = Game::from_seed(6);
+        game.set_clock(Box::new(clock.clone()));
+        game.process_event(GameEvent::GameSetup(GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: VictorDealerOption::TwoPlayers,
+            is_manual_scoring,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits,
+        }))
+        .unwrap();
+        while game.state() != GameState::Discard {
+            game.process_event(GameEvent::Confirmation).unwrap();
+        }
+        (game, clock)
+    }
+
+    fn time_limit(millis: u64, on_timeout: TimeoutPolicy) -> Option<TimeLimit> {
+        Some(TimeLimit { millis, on_timeout })
+    }
+
+    #[test]
+    fn no_time_limits() {
+        let (mut game, clock) = set_up_game(false, None);
+        clock.advance(1_000_000);
+        assert_eq!(game.time_remaining(), None);
+        assert_eq!(
+            game.process_event(GameEvent::Timeout),
+            Err(Error::TimeHasNotRunOut)
+        );
+    }
+
+    #[test]
+    fn discards_are_made_for_late_players() {
+        let (mut game, clock) = set_up_game(
+            false,
+            Some(TimeLimits {
+                discard: time_limit(1000, TimeoutPolicy::AutoPlay),
+                play: None,
+                manual_scoring: None,
+            }),
+        );
+        assert_eq!(game.time_remaining(), Some(1000));
+        clock.advance(400);
+        assert_eq!(game.view(0).time_remaining, Some(600));
+        assert_eq!(
+            game.process_event_from(1, GameEvent::Timeout),
+            Err(Error::TimeHasNotRunOut)
+        );
+
+        // The clock keeps running for the player who hasn't discarded yet
+        let hand = game.players()[0].hand.clone();
+        game.process_event_from(0, GameEvent::DiscardSelection(vec![vec![4, 5], vec![]]))
+            .unwrap();
+        assert_eq!(game.time_remaining(), Some(600));
+        clock.advance(600);
+        assert_eq!(game.time_remaining(), Some(0));
+        assert_eq!(
+            game.process_event_from(0, GameEvent::Timeout),
+            Ok(Success::Discard)
+        );
+        assert_eq!(game.state(), GameState::CutStarter);
+        assert_eq!(game.crib().len(), 4);
+        assert!(game.crib().contains(&hand[4]) && game.crib().contains(&hand[5]));
+        assert_eq!(game.time_remaining(), None);
+    }
+
+    #[test]
+    fn late_player_forfeits() {
+        let (mut game, clock) = set_up_game(
+            false,
+            Some(TimeLimits {
+                discard: None,
+                play: time_limit(500, TimeoutPolicy::Forfeit),
+                manual_scoring: None,
+            }),
+        );
+        game.process_event(GameEvent::DiscardSelection(vec![vec![0, 1], vec![0, 1]]))
+            .unwrap();
+        game.process_event(GameEvent::Confirmation).unwrap();
+        assert_eq!(game.state(), GameState::PlayWaitForCard);
+        let index_active = game.index_active().unwrap();
+
+        clock.advance(499);
+        assert_eq!(
+            game.process_event(GameEvent::Timeout),
+            Err(Error::TimeHasNotRunOut)
+        );
+        clock.advance(1);
+        assert_eq!(
+            game.process_event(GameEvent::Timeout),
+            Ok(Success::Forfeit(index_active))
+        );
+        assert_eq!(game.state(), GameState::Win);
+        let result = &game.match_standings().results[0];
+        assert_eq!(result.winners, vec![1 - index_active]);
+        assert_eq!(result.game_points, 1);
+    }
+
+    // His heels is called for a dealer out of time when the starter is a jack
+    #[test]
+    fn heels_are_called() {
+        let (mut game, clock) = set_up_game(
+            true,
+            Some(TimeLimits {
+                discard: None,
+                play: None,
+                manual_scoring: time_limit(1000, TimeoutPolicy::AutoScore),
+            }),
+        );
+        game.process_event(GameEvent::DiscardSelection(vec![vec![0, 1], vec![0, 1]]))
+            .unwrap();
+        game.process_event(GameEvent::Confirmation).unwrap();
+        assert_eq!(game.state(), GameState::NibsCheck);
+        game.game.starter_card = Some(crate::util::return_card('J', 'S'));
+
+        clock.advance(1000);
+        let index_dealer = game.index_dealer().unwrap() as usize;
+        game.process_event(GameEvent::Timeout).unwrap();
+        assert_eq!(game.players()[index_dealer].front_peg_pos, 2);
+        assert_eq!(game.state(), GameState::PlayWaitForCard);
+    }
+
+    // A hand can be played and counted to the end by the moves made for players out of time
+    #[test]
+    fn hand_is_played_and_scored_automatically() {
+        let (mut game, clock) = set_up_game(
+            true,
+            Some(TimeLimits {
+                discard: time_limit(1000, TimeoutPolicy::AutoPlay),
+                play: time_limit(1000, TimeoutPolicy::AutoPlay),
+                manual_scoring: time_limit(1000, TimeoutPolicy::AutoScore),
+            }),
+        );
+        while game.hand_reveal().is_none() {
+            let event = match game.time_remaining() {
+                Some(millis) => {
+                    clock.advance(millis);
+                    GameEvent::Timeout
+                }
+                None => GameEvent::Confirmation,
+            };
+            game.process_event(event).unwrap();
+        }
+        let score = |player_index: usize| game.players()[player_index].front_peg_pos;
+        assert!(score(0) + score(1) > 0);
+    }
+}
+
+// When the decision being waited on runs out of time
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Deadline {
+    pub state: crate::GameState,
+    pub millis: u64,
+}
+
+// Returns the time limit of the decision the game is waiting on, if it has one
+fn time_limit(game: &crate::GameImpl) -> Option<settings::TimeLimit> {
+    let settings = game.settings?;
+    let time_limits = settings.time_limits?;
+    match game.state {
+        crate::GameState::Discard => time_limits.discard,
+        crate::GameState::PlayWaitForCard | crate::GameState::PlayGoDecision => time_limits.play,
+        crate::GameState::NibsCheck
+        | crate::GameState::PlayScore
+        | crate::GameState::ShowScore
+        | crate::GameState::CribScore
+            if settings.is_manual_scoring =>
+        {
+            time_limits.manual_scoring
+        }
+        _ => None,
+    }
+}
+
+// Starts the clock for the decision the game is now waiting on; called after every accepted event
+pub(crate) fn update_deadline(game: &mut crate::GameImpl) {
+    let time_limit = match time_limit(game) {
+        Some(time_limit) => time_limit,
+        None => {
+            game.deadline = None;
+            return;
+        }
+    };
+    // Every player discards at once so one player's discards don't give the others more time
+    let is_discarding = game.state == crate::GameState::Discard
+        && game.deadline.map_or(false, |deadline| {
+            deadline.state == crate::GameState::Discard
+        });
+    if !is_discarding {
+        game.deadline = Some(Deadline {
+            state: game.state,
+            millis: game.clock.now_millis() + time_limit.millis,
+        });
+    }
+}
+
+pub(crate) fn time_remaining(game: &crate::GameImpl) -> Option<u64> {
+    game.deadline
+        .map(|deadline| deadline.millis.saturating_sub(game.clock.now_millis()))
+}
+
+pub(crate) fn process_timeout(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let time_limit = match (game.deadline, time_limit(game)) {
+        (Some(deadline), Some(time_limit)) if game.clock.now_millis() >= deadline.millis => {
+            time_limit
+        }
+        _ => return Err(game_process_return::Error::TimeHasNotRunOut),
+    };
+
+    match time_limit.on_timeout {
+        settings::TimeoutPolicy::Forfeit => {
+            let index_late = late_player(game);
+            let result = crate::standings::forfeit_result(&game.players, index_late as usize);
+            crate::observer::notify(game, |observer| observer.on_game_won(&result));
+            game.match_standings.record(result);
+            game.held_discards.clear();
+            game.last_hand = crate::view::hand_reveal(game);
+            crate::util::set_state(game, crate::GameState::Win);
+            Ok(game_process_return::Success::Forfeit(index_late))
+        }
+        settings::TimeoutPolicy::AutoPlay | settings::TimeoutPolicy::AutoScore => auto_move(game),
+    }
+}
+
+// Returns the player who ran out of time; when discarding it's the first player from the dealer's
+// left who hasn't discarded
+fn late_player(game: &crate::GameImpl) -> u8 {
+    let index_dealer = game.index_dealer.unwrap_or(0);
+    match game.state {
+        crate::GameState::Discard => {
+            let num_players = game.players.len() as u8;
+            (1..=num_players)
+                .map(|offset| (index_dealer + offset) % num_players)
+                .find(|player_index| !has_discarded(game, *player_index))
+                .unwrap_or(index_dealer)
+        }
+        crate::GameState::NibsCheck | crate::GameState::CribScore => index_dealer,
+        _ => game.index_active.unwrap_or(index_dealer),
+    }
+}
+
+fn has_discarded(game: &crate::GameImpl, player_index: u8) -> bool {
+    game.held_discards
+        .get(player_index as usize)
+        .map_or(false, Option::is_some)
+}
+
+fn auto_move(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    match game.state {
+        crate::GameState::Discard => {
+            let preset = game.settings.unwrap().variant.preset();
+            let index_dealer = game.index_dealer.unwrap();
+            let mut result = Err(game_process_return::Error::TimeHasNotRunOut);
+            for player_index in 0..game.players.len() as u8 {
+                if has_discarded(game, player_index) {
+                    continue;
+                }
+                let num_discards = preset.discards_by(player_index, index_dealer) as usize;
+                let discards = if num_discards == 0 {
+                    Vec::new()
+                } else {
+                    strategy::GreedyStrategy::new().choose_discard(
+                        &game.players[player_index as usize].hand,
+                        num_discards,
+                        player_index == index_dealer,
+                    )
+                };
+                result = Ok(super::discard::process_player_discard(
+                    game,
+                    player_index,
+                    discards,
+                )?);
+            }
+            result
+        }
+        crate::GameState::NibsCheck => {
+            let settings = game.settings.unwrap();
+            let call = if settings.is_heels(game.starter_card.unwrap()) {
+                Some(score::ScoreEvent {
+                    score_type: score::ScoreType::Play(score::PlayScoreType::Nibs),
+                    player_index: game.index_dealer.unwrap(),
+                    point_value: 2,
+                })
+            } else {
+                None
+            };
+            super::cut_starter_and_nibs_check::process_nibs(game, call)
+        }
+        // The strategy's pick of the cards that may be played, or a Go when none may be
+        crate::GameState::PlayWaitForCard => {
+            let settings = game.settings.unwrap();
+            let hand = &game.players[game.index_active.unwrap() as usize].hand;
+            let is_played: Vec<bool> = hand
+                .iter()
+                .map(|card| {
+                    game.play_groups
+                        .iter()
+                        .any(|play_group| play_group.cards.contains(card))
+                })
+                .collect();
+            let play_group = game.play_groups.last().unwrap();
+            let play = if strategy::legal_plays(hand, &is_played, play_group.total, &settings)
+                .is_empty()
+            {
+                crate::PlayTurn::Go
+            } else {
+                strategy::GreedyStrategy::new().choose_play(hand, &is_played, play_group, &settings)
+            };
+            super::play::play_card(game, play)
+        }
+        crate::GameState::PlayGoDecision => {
+            super::play::process_go_decision(game, crate::GoDecision::PegOne)
+        }
+        // The full count is claimed for the player, which is always accepted
+        crate::GameState::PlayScore => {
+            let claims = super::play::score::optimal_scoring(game);
+            super::play::play_score(game, claims)
+        }
+        crate::GameState::ShowScore => {
+            let claims = super::show::optimal_scoring(game);
+            super::show::process_show_score(game, Some(claims))
+        }
+        crate::GameState::CribScore => {
+            let claims = super::crib::optimal_scoring(game);
+            super::crib::process_crib_score(game, Some(claims))
+        }
+        _ => Err(game_process_return::Error::TimeHasNotRunOut),
+    }
+}
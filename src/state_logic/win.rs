@@ -0,0 +1,204 @@
+use crate::game_process_return;
+use crate::settings;
+
+// State reached once a player reaches their target score; the result of the game has already been
+// added to the match standings. A Confirmation event resets the board for the next game of the
+// match, which starts by cutting for the dealer between the players given by the
+// VictorDealerOption, while a Denial event ends the match
+
+#[cfg(test)]
+mod test {
+    use super::super::play::test_util::set_up_game;
+    use super::process_end_match;
+    use super::process_next_game;
+    use crate::game_process_return;
+    use crate::settings::RuleVariant;
+
+    // Sets up a game which has just been won by the given player
+    fn set_up_win(variant: RuleVariant, winner_index: usize, scores: &[u8]) -> crate::GameImpl {
+        let mut game = set_up_game(variant, false, false, false);
+        for (player, score) in game.players.iter_mut().zip(scores) {
+            player.front_peg_pos = *score;
+        }
+        let points_to_win = 121 - game.players[winner_index].front_peg_pos;
+        assert!(crate::util::process_score(
+            &mut game,
+            winner_index,
+            points_to_win
+        ));
+        game
+    }
+
+    #[test]
+    fn next_game_resets_board() {
+        let mut game = set_up_win(RuleVariant::TwoStandard, 1, &[80, 110]);
+        assert_eq!(game.state, crate::GameState::Win);
+        assert_eq!(game.match_standings.game_points, vec![0, 2]);
+
+        assert_eq!(
+            process_next_game(&mut game),
+            Ok(game_process_return::Success::Win(
+                game_process_return::WinReturn::NextGame
+            ))
+        );
+        assert_eq!(game.state, crate::GameState::CutInitial);
+        for player in &game.players {
+            assert_eq!(player.front_peg_pos, 0);
+            assert_eq!(player.back_peg_pos, 0);
+            assert!(player.hand.is_empty());
+        }
+        assert!(game.crib.is_empty());
+        assert!(game.play_groups.is_empty());
+        assert_eq!(game.starter_card, None);
+        assert_eq!(game.index_dealer, None);
+        assert_eq!(game.index_active, None);
+
+        // The loser deals the next game without a cut and the standings are kept
+        assert_eq!(game.initial_cut_between_players_with_these_indices, vec![0]);
+        crate::state_logic::cut_initial::process_cut(&mut game).unwrap();
+        assert_eq!(game.index_dealer, Some(0));
+        assert_eq!(game.match_standings.results.len(), 1);
+    }
+
+    #[test]
+    fn next_game_dealer_options() {
+        let mut game = set_up_win(RuleVariant::FourIndividual, 2, &[30, 100, 110, 30]);
+        process_next_game(&mut game).unwrap();
+        assert_eq!(
+            game.initial_cut_between_players_with_these_indices,
+            vec![0, 1, 3]
+        );
+
+        let mut game = set_up_win(RuleVariant::FourIndividual, 2, &[30, 100, 110, 30]);
+        game.settings.as_mut().unwrap().victor_dealer_option =
+            crate::settings::VictorDealerOption::LastPlaceIsDealer;
+        process_next_game(&mut game).unwrap();
+        assert_eq!(
+            game.initial_cut_between_players_with_these_indices,
+            vec![0, 3]
+        );
+
+        let mut game = set_up_win(RuleVariant::SixPairs, 4, &[30, 100, 110, 30, 100, 110]);
+        process_next_game(&mut game).unwrap();
+        assert_eq!(
+            game.initial_cut_between_players_with_these_indices,
+            vec![0, 2, 3, 5]
+        );
+
+        let mut game = set_up_win(RuleVariant::ThreeCaptain, 1, &[50, 100, 100]);
+        process_next_game(&mut game).unwrap();
+        assert_eq!(game.initial_cut_between_players_with_these_indices, vec![0]);
+    }
+
+    #[test]
+    fn end_match() {
+        let mut game = set_up_win(RuleVariant::TwoStandard, 0, &[110, 50]);
+        assert_eq!(
+            process_end_match(&mut game),
+            Ok(game_process_return::Success::Win(
+                game_process_return::WinReturn::MatchComplete
+            ))
+        );
+        assert_eq!(game.state, crate::GameState::End);
+        assert_eq!(game.match_standings.game_points, vec![3, 0]);
+        assert_eq!(game.match_standings.leaders(), vec![0]);
+    }
+
+    // The public interface routes the Win state and refuses every event once the match is over
+    #[test]
+    fn win_and_end_routing() {
+        let mut game = crate::Game {
+            game: set_up_win(RuleVariant::TwoStandard, 0, &[110, 50]),
+        };
+        assert_eq!(
+            game.process_event(crate::GameEvent::Nibs(None)),
+            Err(game_process_return::Error::ExpectedEvent(vec![
+                game_process_return::Event::Confirmation,
+                game_process_return::Event::Denial,
+            ]))
+        );
+        game.process_event(crate::GameEvent::Denial).unwrap();
+        assert_eq!(
+            game.process_event(crate::GameEvent::Confirmation),
+            Err(game_process_return::Error::MatchIsOver)
+        );
+        assert_eq!(game.match_standings().games_won, vec![1, 0]);
+    }
+}
+
+pub(crate) fn process_next_game(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    let settings = match game.settings {
+        Some(settings) => settings,
+        None => {
+            return Err(game_process_return::Error::ImplementationError(
+                game_process_return::ImplError::NoConfig,
+            ))
+        }
+    };
+
+    game.initial_cut_between_players_with_these_indices = next_cut_indices(game, settings);
+
+    for player in &mut game.players {
+        player.back_peg_pos = 0;
+        player.front_peg_pos = 0;
+        player.hand.clear();
+    }
+    for holes in &mut game.holes_moved_back {
+        *holes = 0;
+    }
+    game.crib.clear();
+    game.play_groups.clear();
+    game.starter_card = None;
+    game.index_dealer = None;
+    game.index_active = None;
+    game.last_player_index = None;
+    game.last_play_was_go = false;
+    game.auction = None;
+
+    crate::util::set_state(game, crate::GameState::CutInitial);
+    Ok(game_process_return::Success::Win(
+        game_process_return::WinReturn::NextGame,
+    ))
+}
+
+pub(crate) fn process_end_match(
+    game: &mut crate::GameImpl,
+) -> Result<game_process_return::Success, game_process_return::Error> {
+    crate::util::set_state(game, crate::GameState::End);
+    Ok(game_process_return::Success::Win(
+        game_process_return::WinReturn::MatchComplete,
+    ))
+}
+
+// Returns the indices of the players who cut for the first deal of the next game; a single index
+// means that player deals without a cut
+fn next_cut_indices(game: &crate::GameImpl, settings: settings::GameSettings) -> Vec<u8> {
+    let winners = match game.match_standings.results.last() {
+        Some(result) => result.winners.clone(),
+        None => Vec::new(),
+    };
+    let losers: Vec<u8> = (0..game.players.len() as u8)
+        .filter(|index| !winners.contains(index))
+        .collect();
+
+    match settings.victor_dealer_option {
+        settings::VictorDealerOption::TwoPlayers
+        | settings::VictorDealerOption::LosersDrawForDealer => losers,
+        settings::VictorDealerOption::LastPlaceIsDealer => {
+            let lowest = game
+                .players
+                .iter()
+                .map(|player| player.front_peg_pos)
+                .min()
+                .unwrap_or(0);
+            (0..game.players.len() as u8)
+                .filter(|index| game.players[*index as usize].front_peg_pos == lowest)
+                .collect()
+        }
+        settings::VictorDealerOption::CaptainDeals => {
+            vec![settings.variant.preset().captain_index().unwrap_or(0)]
+        }
+    }
+}
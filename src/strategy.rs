@@ -0,0 +1,467 @@
+extern crate rand;
+use crate::deck;
+use crate::score;
+use crate::settings;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+// Strategies choose the discards and plays for a seat such that games can be played by the
+// computer, whether to fill empty seats or to evaluate one strategy against another
+
+#[cfg(test)]
+mod test {
+    use super::Strategy;
+    use crate::settings::{
+        GameSettings, JokerRules, RuleVariant, ScoringRules, VictorDealerOption,
+    };
+    use crate::util::return_card;
+
+    fn settings(jokers: Option<JokerRules>) -> GameSettings {
+        GameSettings {
+            variant: RuleVariant::TwoStandard,
+            victor_dealer_option: VictorDealerOption::TwoPlayers,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }
+    }
+
+    fn play_group(cards: Vec<crate::deck::Card>) -> crate::PlayGroup {
+        let total = cards
+            .iter()
+            .map(|card| crate::deck::return_play_value(*card))
+            .sum();
+        crate::PlayGroup { total, cards }
+    }
+
+    #[test]
+    fn legal_plays() {
+        let hand = vec![
+            return_card('2', 'H'),
+            return_card('9', 'D'),
+            return_card('T', 'C'),
+            return_card('K', 'S'),
+        ];
+        let settings = settings(None);
+        assert_eq!(
+            super::legal_plays(&hand, &[false, false, false, false], 22, &settings),
+            vec![0, 1]
+        );
+        assert_eq!(
+            super::legal_plays(&hand, &[true, false, false, false], 22, &settings),
+            vec![1]
+        );
+        assert!(super::legal_plays(&hand, &[true, false, false, false], 23, &settings).is_empty());
+    }
+
+    // A joker is only legal when the play value of the game's JokerRules fits under 31
+    #[test]
+    fn legal_plays_with_jokers() {
+        let hand = vec![return_card('5', 'C'), crate::deck::RED_JOKER];
+        let settings = settings(Some(JokerRules {
+            num_jokers: 1,
+            play_value: 10,
+        }));
+        assert_eq!(
+            super::legal_plays(&hand, &[false, false], 25, &settings),
+            vec![0]
+        );
+        for name in super::STRATEGY_NAMES {
+            let mut strategy = super::from_name(name, 3).unwrap();
+            let group = play_group(vec![
+                return_card('T', 'S'),
+                return_card('K', 'C'),
+                return_card('5', 'D'),
+            ]);
+            assert_eq!(
+                strategy.choose_play(&hand, &[false, false], &group, &settings),
+                crate::PlayTurn::CardSelected(0)
+            );
+            assert_eq!(
+                strategy.choose_play(&hand, &[true, false], &group, &settings),
+                crate::PlayTurn::Go
+            );
+        }
+    }
+
+    // Both strategies must always choose the right number of distinct discards and a legal play
+    #[test]
+    fn strategies_make_legal_choices() {
+        let hand = vec![
+            return_card('A', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('J', 'S'),
+            return_card('Q', 'H'),
+            return_card('K', 'D'),
+        ];
+        let settings = settings(None);
+        for name in super::STRATEGY_NAMES {
+            let mut strategy = super::from_name(name, 3).unwrap();
+            for num_discards in 0..3 {
+                let mut discards = strategy.choose_discard(&hand, num_discards, true);
+                assert_eq!(discards.len(), num_discards);
+                discards.dedup();
+                assert_eq!(discards.len(), num_discards);
+                assert!(discards.iter().all(|index| (*index as usize) < hand.len()));
+            }
+
+            let group = play_group(vec![return_card('T', 'S'), return_card('K', 'C')]);
+            match strategy.choose_play(&hand[..4], &[false, false, true, false], &group, &settings)
+            {
+                crate::PlayTurn::CardSelected(index) => assert!([0, 1, 3].contains(&index)),
+                crate::PlayTurn::Go => panic!("{} sent a Go with a legal play", name),
+            }
+
+            let group = play_group(vec![
+                return_card('T', 'S'),
+                return_card('K', 'C'),
+                return_card('9', 'C'),
+                return_card('2', 'C'),
+            ]);
+            match strategy.choose_play(
+                &hand[1..5],
+                &[false, false, false, false],
+                &group,
+                &settings,
+            ) {
+                crate::PlayTurn::Go => {}
+                crate::PlayTurn::CardSelected(_) => panic!("{} played past 31", name),
+            }
+        }
+    }
+
+    // The greedy strategy keeps the hand with the best expected score and takes the points
+    // available in the play
+    #[test]
+    fn greedy_choices() {
+        let mut strategy = super::GreedyStrategy::new();
+
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('J', 'S'),
+            return_card('2', 'H'),
+            return_card('8', 'D'),
+        ];
+        let mut discards = strategy.choose_discard(&hand, 2, false);
+        discards.sort();
+        assert_eq!(discards, vec![4, 5]);
+
+        let group = play_group(vec![return_card('T', 'S')]);
+        assert_eq!(
+            strategy.choose_play(&hand[..4], &[false; 4], &group, &settings(None)),
+            crate::PlayTurn::CardSelected(0)
+        );
+    }
+
+    // The expected crib from a table outweighs the points of the hand kept
+    #[test]
+    fn greedy_discards_with_crib_table() {
+        let hand = vec![
+            return_card('5', 'H'),
+            return_card('5', 'D'),
+            return_card('5', 'C'),
+            return_card('J', 'S'),
+            return_card('2', 'H'),
+            return_card('8', 'D'),
+        ];
+        let mut own = vec![vec![0.0; 13]; 13];
+        own[4][10] = 30.0;
+        own[10][4] = 30.0;
+        let crib_table = crate::analysis::crib_table::CribTable {
+            own,
+            opponent: vec![vec![0.0; 13]; 13],
+            samples_per_pair: 1,
+        };
+        let mut strategy = super::GreedyStrategy::with_crib_table(crib_table);
+
+        let mut discards = strategy.choose_discard(&hand, 2, true);
+        discards.sort();
+        assert_eq!(
+            hand[discards[0] as usize].value,
+            crate::deck::CardValue::Five
+        );
+        assert_eq!(discards[1], 3);
+
+        // Without a table entry for a joker the points the discards make are used instead
+        let thrown = [return_card('5', 'S'), crate::deck::RED_JOKER];
+        assert_eq!(strategy.crib_value(&thrown, true), 0.0);
+        let thrown = [return_card('5', 'S'), return_card('J', 'D')];
+        assert_eq!(strategy.crib_value(&thrown, true), 30.0);
+        assert_eq!(strategy.crib_value(&thrown[..1], true), 0.0);
+    }
+}
+
+// Names accepted by from_name
+pub const STRATEGY_NAMES: [&str; 2] = ["random", "greedy"];
+
+pub trait Strategy {
+    // Returns the name used to identify the strategy in reports
+    fn name(&self) -> String;
+
+    // Returns the indices of the cards of the hand to be sent to the crib; num_discards is the
+    // number of cards the seat must discard with the rule variant being played
+    fn choose_discard(
+        &mut self,
+        hand: &[deck::Card],
+        num_discards: usize,
+        is_dealer: bool,
+    ) -> Vec<u8>;
+
+    // Returns the play to make given the seat's hand, whether each card of that hand has already
+    // been played, the PlayGroup currently being played, and the settings of the game which give
+    // the play value of a joker; must be a Go only when there is no legal play
+    fn choose_play(
+        &mut self,
+        hand: &[deck::Card],
+        is_played: &[bool],
+        play_group: &crate::PlayGroup,
+        settings: &settings::GameSettings,
+    ) -> crate::PlayTurn;
+}
+
+// Returns the strategy with the given name, or None if there is no such strategy; the seed is used
+// by any strategy which makes random choices
+pub fn from_name(name: &str, seed: u64) -> Option<Box<dyn Strategy>> {
+    match name {
+        "random" => Some(Box::new(RandomStrategy::new(seed))),
+        "greedy" => Some(Box::new(GreedyStrategy::new())),
+        _ => None,
+    }
+}
+
+// Returns the indices of every card in the hand that has not been played and would not bring the
+// PlayGroup total over 31 with the play values of the game's settings
+pub fn legal_plays(
+    hand: &[deck::Card],
+    is_played: &[bool],
+    total: u8,
+    settings: &settings::GameSettings,
+) -> Vec<u8> {
+    hand.iter()
+        .enumerate()
+        .filter(|(index, card)| !is_played[*index] && total + settings.play_value(**card) <= 31)
+        .map(|(index, _)| index as u8)
+        .collect()
+}
+
+// Chooses uniformly at random from the valid discards and plays
+pub struct RandomStrategy {
+    rng: StdRng,
+}
+
+impl RandomStrategy {
+    pub fn new(seed: u64) -> RandomStrategy {
+        RandomStrategy {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn name(&self) -> String {
+        String::from("random")
+    }
+
+    fn choose_discard(
+        &mut self,
+        hand: &[deck::Card],
+        num_discards: usize,
+        _is_dealer: bool,
+    ) -> Vec<u8> {
+        let indices: Vec<u8> = (0..hand.len() as u8).collect();
+        indices
+            .choose_multiple(&mut self.rng, num_discards)
+            .cloned()
+            .collect()
+    }
+
+    fn choose_play(
+        &mut self,
+        hand: &[deck::Card],
+        is_played: &[bool],
+        play_group: &crate::PlayGroup,
+        settings: &settings::GameSettings,
+    ) -> crate::PlayTurn {
+        match legal_plays(hand, is_played, play_group.total, settings).choose(&mut self.rng) {
+            Some(index) => crate::PlayTurn::CardSelected(*index),
+            None => crate::PlayTurn::Go,
+        }
+    }
+}
+
+// Keeps the cards with the highest average show score over every possible starter, adjusted by the
+// points thrown into the crib, and pegs whatever scores the most right away while avoiding leaving
+// the count at 5 or 21
+#[derive(Default)]
+pub struct GreedyStrategy {
+    // When given, the expected crib of a two card discard is taken from the table rather than only
+    // the points the two cards make together
+    crib_table: Option<crate::analysis::crib_table::CribTable>,
+}
+
+impl GreedyStrategy {
+    pub fn new() -> GreedyStrategy {
+        GreedyStrategy { crib_table: None }
+    }
+
+    pub fn with_crib_table(crib_table: crate::analysis::crib_table::CribTable) -> GreedyStrategy {
+        GreedyStrategy {
+            crib_table: Some(crib_table),
+        }
+    }
+
+    // Returns the points the discards are expected to add to the crib, counted for the dealer and
+    // against the pone
+    fn crib_value(&self, thrown: &[deck::Card], is_dealer: bool) -> f64 {
+        let table_value = match (&self.crib_table, thrown) {
+            (Some(crib_table), [first, second]) => {
+                crib_table.discard_value(first.value, second.value, is_dealer)
+            }
+            _ => None,
+        };
+        table_value.unwrap_or_else(|| {
+            let points = f64::from(discard_points(thrown));
+            if is_dealer {
+                points
+            } else {
+                -points
+            }
+        })
+    }
+}
+
+impl Strategy for GreedyStrategy {
+    fn name(&self) -> String {
+        String::from("greedy")
+    }
+
+    fn choose_discard(
+        &mut self,
+        hand: &[deck::Card],
+        num_discards: usize,
+        is_dealer: bool,
+    ) -> Vec<u8> {
+        // Every card not in the hand could be the starter
+        let starters: Vec<deck::Card> = full_deck()
+            .into_iter()
+            .filter(|card| !hand.contains(card))
+            .collect();
+
+        let mut best_discards = Vec::new();
+        let mut best_value = f64::MIN;
+        for discards in combinations(hand.len(), num_discards) {
+            let kept: Vec<deck::Card> = (0..hand.len())
+                .filter(|index| !discards.contains(&(*index as u8)))
+                .map(|index| hand[index])
+                .collect();
+            let thrown: Vec<deck::Card> =
+                discards.iter().map(|index| hand[*index as usize]).collect();
+
+            let mut total = 0;
+            for starter in &starters {
+                total += score::show::score_hand(0, &kept, *starter, false)
+                    .iter()
+                    .map(|score_event| score_event.point_value as u32)
+                    .sum::<u32>();
+            }
+            let value =
+                f64::from(total) / starters.len() as f64 + self.crib_value(&thrown, is_dealer);
+
+            if value > best_value {
+                best_value = value;
+                best_discards = discards;
+            }
+        }
+
+        best_discards
+    }
+
+    fn choose_play(
+        &mut self,
+        hand: &[deck::Card],
+        is_played: &[bool],
+        play_group: &crate::PlayGroup,
+        settings: &settings::GameSettings,
+    ) -> crate::PlayTurn {
+        let mut best_play = None;
+        let mut best_value = i32::MIN;
+        for index in legal_plays(hand, is_played, play_group.total, settings) {
+            let card = hand[index as usize];
+            let mut next_group = play_group.clone();
+            next_group.cards.push(card);
+            next_group.total += settings.play_value(card);
+
+            let mut value: i32 = score::play::play_score(0, &next_group)
+                .iter()
+                .map(|score_event| i32::from(score_event.point_value))
+                .sum::<i32>()
+                * 10;
+            if next_group.total == 5 || next_group.total == 21 {
+                value -= 15;
+            }
+            // Prefer getting rid of high cards when nothing else separates the plays
+            value += i32::from(settings.play_value(card));
+
+            if value > best_value {
+                best_value = value;
+                best_play = Some(index);
+            }
+        }
+
+        match best_play {
+            Some(index) => crate::PlayTurn::CardSelected(index),
+            None => crate::PlayTurn::Go,
+        }
+    }
+}
+
+// Returns the points guaranteed to the crib by the discarded cards: a pair or a fifteen
+fn discard_points(thrown: &[deck::Card]) -> u8 {
+    let mut points = 0;
+    for (position, first) in thrown.iter().enumerate() {
+        for second in &thrown[position + 1..] {
+            if first.value == second.value {
+                points += 2;
+            }
+            if deck::return_play_value(*first) + deck::return_play_value(*second) == 15 {
+                points += 2;
+            }
+        }
+    }
+    points
+}
+
+// Returns every way of choosing k of the indices 0 to n - 1
+fn combinations(n: usize, k: usize) -> Vec<Vec<u8>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    if n < k {
+        return vec![];
+    }
+
+    // Every combination either includes the last index or doesn't
+    let mut output = combinations(n - 1, k);
+    for mut combination in combinations(n - 1, k - 1) {
+        combination.push((n - 1) as u8);
+        output.push(combination);
+    }
+    output
+}
+
+// Returns an unshuffled deck of all 52 cards
+fn full_deck() -> Vec<deck::Card> {
+    let mut deck = deck::Deck::new();
+    (0..52).map(|_| deck.deal()).collect()
+}
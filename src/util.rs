@@ -1,7 +1,6 @@
-use std::char;
-
 // Returns a card object based on a specified value and suit character for the purpose of
 // testing
+#[cfg(test)]
 pub(crate) fn return_card(set_value: char, set_suit: char) -> super::deck::Card {
     let set_value: super::deck::CardValue = match set_value {
         'A' => super::deck::CardValue::Ace,
@@ -18,6 +17,7 @@ pub(crate) fn return_card(set_value: char, set_suit: char) -> super::deck::Card
         'J' => super::deck::CardValue::Jack,
         'Q' => super::deck::CardValue::Queen,
         'K' => super::deck::CardValue::King,
+        'X' => super::deck::CardValue::Joker,
         _ => panic!("Unexpexted value in return_card()"),
     };
 
@@ -36,6 +36,7 @@ pub(crate) fn return_card(set_value: char, set_suit: char) -> super::deck::Card
 }
 
 // Returns a list of all possible rule variants for the purpose of testing
+#[cfg(test)]
 pub(crate) fn return_variants() -> Vec<super::settings::RuleVariant> {
     vec![
         super::settings::RuleVariant::TwoStandard,
@@ -50,21 +51,69 @@ pub(crate) fn return_variants() -> Vec<super::settings::RuleVariant> {
     ]
 }
 
+// Returns the next event of a simple game in which every player discards their first cards and
+// plays their first playable card, confirming everything else, for the purpose of testing
+#[cfg(test)]
+pub(crate) fn return_next_event(game: &crate::Game) -> crate::GameEvent {
+    match game.state() {
+        crate::GameState::Discard => {
+            let preset = game.game.settings.unwrap().variant.preset();
+            let index_dealer = game.index_dealer().unwrap();
+            crate::GameEvent::DiscardSelection(
+                (0..game.players().len() as u8)
+                    .map(|player_index| {
+                        (0..preset.discards_by(player_index, index_dealer)).collect()
+                    })
+                    .collect(),
+            )
+        }
+        crate::GameState::PlayWaitForCard => {
+            let seat = game.index_active().unwrap();
+            let total = game.play_groups().last().unwrap().total();
+            let hand = &game.players()[seat as usize].hand;
+            match (0..hand.len() as u8).find(|index| {
+                !game.has_card_been_played(seat, *index)
+                    && total + super::deck::return_play_value(hand[*index as usize]) <= 31
+            }) {
+                Some(index) => crate::GameEvent::Play(crate::PlayTurn::CardSelected(index)),
+                None => crate::GameEvent::Play(crate::PlayTurn::Go),
+            }
+        }
+        _ => crate::GameEvent::Confirmation,
+    }
+}
+
 // Returns a u8 with the number of players for a given rule variant
 pub(crate) fn return_num_players_for_variant(variant: super::settings::RuleVariant) -> u8 {
-    match variant {
-        crate::settings::RuleVariant::TwoStandard => 2,
-        crate::settings::RuleVariant::TwoFiveCard => 2,
-        crate::settings::RuleVariant::TwoSevenCard => 2,
-        crate::settings::RuleVariant::ThreeStandard => 3,
-        crate::settings::RuleVariant::ThreeCaptain => 3,
-        crate::settings::RuleVariant::FourIndividual => 4,
-        crate::settings::RuleVariant::FourPairs => 4,
-        crate::settings::RuleVariant::FiveStandard => 5,
-        crate::settings::RuleVariant::SixPairs => 6,
+    variant.preset().num_players
+}
+
+// Moves the game to a new state and tells the observers when it differs from the last
+pub(crate) fn set_state(game: &mut crate::GameImpl, state: crate::GameState) {
+    let old_state = game.state;
+    game.state = state;
+    if old_state != state {
+        crate::observer::notify(game, |observer| observer.on_state_change(old_state, state));
     }
 }
 
+// Processes the ScoreEvents of a player by telling the observers of each and then scoring their
+// total with process_score. Returns true if a player has reached the threshold
+pub(crate) fn process_score_events(
+    game: &mut crate::GameImpl,
+    player_index: usize,
+    score_events: &[crate::score::ScoreEvent],
+) -> bool {
+    for score_event in score_events {
+        crate::observer::notify(game, |observer| observer.on_score(score_event));
+    }
+    let score_change = score_events
+        .iter()
+        .map(|score_event| score_event.point_value)
+        .sum();
+    process_score(game, player_index, score_change)
+}
+
 // Processes a score change; ensures change also occurs with partner and check for victory (or loss
 // with lowball). Returns true if a player has reached the threshold, otherwise return false
 pub(crate) fn process_score(
@@ -72,52 +121,61 @@ pub(crate) fn process_score(
     player_index: usize,
     score_change: u8,
 ) -> bool {
-    game.players[player_index].change_score(score_change);
+    let target_score = target_score(game, player_index);
+    game.players[player_index].change_score(i16::from(score_change), target_score);
     if let Some(partner_index) = game.players[player_index].partner_index {
-        game.players[partner_index as usize].change_score(score_change);
+        game.players[partner_index as usize].change_score(i16::from(score_change), target_score);
+    }
+    if score_change > 0 {
+        notify_peg_move(game, player_index);
     }
 
-    match game.settings.unwrap().variant {
-        crate::settings::RuleVariant::TwoFiveCard => {
-            if game.players[player_index].front_peg_pos >= 61 {
-                game.state = crate::GameState::Win;
-                true
-            } else {
-                false
-            }
-        }
-        crate::settings::RuleVariant::TwoSevenCard => {
-            if game.players[player_index].front_peg_pos >= 151 {
-                game.state = crate::GameState::Win;
-                true
-            } else {
-                false
-            }
-        }
-        crate::settings::RuleVariant::ThreeCaptain => {
-            if game.players[player_index].partner_index.is_some() {
-                if game.players[player_index].front_peg_pos >= 121 {
-                    game.state = crate::GameState::Win;
-                    true
-                } else {
-                    false
-                }
-            } else {
-                if game.players[player_index].front_peg_pos >= 61 {
-                    game.state = crate::GameState::Win;
-                    true
-                } else {
-                    false
-                }
-            }
-        }
-        _ => {
-            if game.players[player_index].front_peg_pos >= 121 {
-                game.state = crate::GameState::Win;
-                true
-            } else {
-                false
-            }
-        }
+    if game.players[player_index].front_peg_pos >= target_score {
+        let result =
+            crate::standings::game_result(&game.settings.unwrap(), &game.players, player_index);
+        crate::observer::notify(game, |observer| observer.on_game_won(&result));
+        game.match_standings.record(result);
+        game.last_hand = crate::view::hand_reveal(game);
+        set_state(game, crate::GameState::Win);
+        true
+    } else {
+        false
+    }
+}
+
+// Moves a player and their partner back the given number of holes, or to the start when they are
+// closer to it than that. Returns the number of holes moved back
+pub(crate) fn process_move_back(game: &mut crate::GameImpl, player_index: usize, holes: u8) -> u8 {
+    let holes = holes.min(game.players[player_index].front_peg_pos);
+    let target_score = target_score(game, player_index);
+    game.players[player_index].change_score(-i16::from(holes), target_score);
+    if let Some(partner_index) = game.players[player_index].partner_index {
+        game.players[partner_index as usize].change_score(-i16::from(holes), target_score);
+    }
+    if holes > 0 {
+        notify_peg_move(game, player_index);
+    }
+    holes
+}
+
+// Returns the score the player, and their partner if they have one, must reach to win the game
+fn target_score(game: &crate::GameImpl, player_index: usize) -> u8 {
+    game.settings
+        .unwrap()
+        .variant
+        .target_score(game.players[player_index].partner_index.is_some())
+}
+
+// Tells the observers where the pegs of a player and their partner now are
+fn notify_peg_move(game: &mut crate::GameImpl, player_index: usize) {
+    let player_indices = std::iter::once(player_index as u8)
+        .chain(game.players[player_index].partner_index)
+        .collect::<Vec<u8>>();
+    for player_index in player_indices {
+        let player = &game.players[player_index as usize];
+        let (back_peg_pos, front_peg_pos) = (player.back_peg_pos, player.front_peg_pos);
+        crate::observer::notify(game, |observer| {
+            observer.on_peg_move(player_index, back_peg_pos, front_peg_pos)
+        });
     }
 }
@@ -0,0 +1,478 @@
+use crate::deck;
+use crate::settings;
+use crate::standings;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// The game as seen from one seat, holding only what the player in that seat may know such that it
+// can be sent to a client without giving away the other hands or the deck
+//
+// A player sees their own hand, the cards played, the starter once it is cut and the pegs of every
+// player. The other hands are revealed from the show on as each is counted, starting from the
+// dealer's left, and the crib is revealed once it is turned over after the dealer's hand
+//
+// Spectators see the same table with every hand, none until they are counted, or all of them once
+// the show begins, and every card of a hand may be reviewed once it has ended
+
+#[cfg(test)]
+mod test {
+    use super::{SpectatorFeed, SpectatorView, SpectatorVisibility};
+    use crate::settings::{GameSettings, RuleVariant, ScoringRules, VictorDealerOption};
+    use crate::util::return_next_event;
+    use crate::{Game, GameEvent, GameState, PlayTurn};
+
+    fn set_up_game() -> Game {
+        let mut game = Game::from_seed(4);
+        game.process_event(GameEvent::GameSetup(GameSettings {
+            variant: RuleVariant::ThreeStandard,
+            victor_dealer_option: VictorDealerOption::LosersDrawForDealer,
+            is_manual_scoring: false,
+            is_underpegging: false,
+            is_muggins: false,
+            is_lowball: false,
+            is_back_up_ten: false,
+            auction: None,
+            skunk_lines: None,
+            jokers: None,
+            scoring_rules: ScoringRules::default(),
+            time_limits: None,
+        }))
+        .unwrap();
+        while game.state() != GameState::Discard {
+            game.process_event(GameEvent::Confirmation).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn hands_are_hidden() {
+        let mut game = set_up_game();
+        let view = game.view(1);
+        assert_eq!(view.seat, 1);
+        assert_eq!(view.state, GameState::Discard);
+        assert_eq!(view.players.len(), 3);
+        assert_eq!(
+            view.players[1].hand.as_deref(),
+            Some(&game.players()[1].hand[..])
+        );
+        assert_eq!(view.players[0].hand, None);
+        assert_eq!(view.players[2].hand, None);
+        assert_eq!(view.players[0].num_cards, 5);
+        assert_eq!(view.starter_card, None);
+
+        game.process_event(GameEvent::DiscardSelection(vec![vec![0], vec![0], vec![0]]))
+            .unwrap();
+        let view = game.view(1);
+        assert_eq!(view.crib_size, 4);
+        assert_eq!(view.crib, None);
+        assert_eq!(view.players[0].num_cards, 4);
+
+        game.process_event(GameEvent::Confirmation).unwrap();
+        let index_active = game.index_active().unwrap();
+        game.process_event(GameEvent::Play(PlayTurn::CardSelected(0)))
+            .unwrap();
+        let view = game.view(1);
+        assert!(view.starter_card.is_some());
+        assert_eq!(
+            view.play_groups.last().unwrap().cards(),
+            &game.players()[index_active as usize].hand[..1]
+        );
+        if index_active != 1 {
+            assert_eq!(view.players[index_active as usize].hand, None);
+        }
+    }
+
+    // Sends events until the game reaches the given state, each player playing their first
+    // playable card
+    fn play_to(game: &mut Game, state: GameState) {
+        while game.state() != state {
+            let event = return_next_event(game);
+            game.process_event(event).unwrap();
+        }
+    }
+
+    // Each hand is revealed as it is counted and the crib once it is turned over
+    #[test]
+    fn show_reveals_hands() {
+        let mut game = set_up_game();
+        play_to(&mut game, GameState::ShowScore);
+
+        let dealer = game.index_dealer().unwrap();
+        let first = (dealer + 1) % 3;
+        let second = (dealer + 2) % 3;
+        let view = game.view(dealer);
+        assert!(view.players[first as usize].hand.is_some());
+        assert_eq!(view.players[second as usize].hand, None);
+
+        game.process_event(GameEvent::Confirmation).unwrap();
+        let view = game.view(dealer);
+        assert!(view.players[second as usize].hand.is_some());
+        assert_eq!(view.crib, None);
+
+        game.process_event(GameEvent::Confirmation).unwrap();
+        let view = game.view(first);
+        assert!(view.players[dealer as usize].hand.is_some());
+        assert_eq!(view.crib, None);
+
+        game.process_event(GameEvent::Confirmation).unwrap();
+        assert_eq!(game.state(), GameState::CribSort);
+        let view = game.view(first);
+        assert_eq!(view.crib.as_deref(), Some(game.crib()));
+        assert!(view.players.iter().all(|player| player.hand.is_some()));
+    }
+
+    #[test]
+    fn spectator_visibility() {
+        let mut game = set_up_game();
+        let hands = |view: &SpectatorView| {
+            view.players
+                .iter()
+                .filter(|player| player.hand.is_some())
+                .count()
+        };
+        assert_eq!(
+            hands(&game.spectator_view(SpectatorVisibility::AllHands)),
+            3
+        );
+        assert_eq!(hands(&game.spectator_view(SpectatorVisibility::NoHands)), 0);
+        assert_eq!(
+            hands(&game.spectator_view(SpectatorVisibility::DelayedReveal)),
+            0
+        );
+
+        play_to(&mut game, GameState::CutStarter);
+        let view = game.spectator_view(SpectatorVisibility::AllHands);
+        assert_eq!(view.crib.as_deref(), Some(game.crib()));
+        assert_eq!(
+            game.spectator_view(SpectatorVisibility::DelayedReveal).crib,
+            None
+        );
+
+        play_to(&mut game, GameState::ShowScore);
+        assert_eq!(hands(&game.spectator_view(SpectatorVisibility::NoHands)), 1);
+        let view = game.spectator_view(SpectatorVisibility::DelayedReveal);
+        assert_eq!(hands(&view), 3);
+        assert_eq!(view.crib.as_deref(), Some(game.crib()));
+    }
+
+    #[test]
+    fn spectator_feed_runs_behind() {
+        let mut game = set_up_game();
+        let mut feed = SpectatorFeed::new(SpectatorVisibility::AllHands, 2);
+        assert_eq!(feed.update(&game), None);
+        game.process_event(GameEvent::DiscardSelection(vec![vec![0], vec![0], vec![0]]))
+            .unwrap();
+        assert_eq!(feed.update(&game), None);
+        assert_eq!(feed.update(&game), None);
+
+        let sequence = game.sequence();
+        game.process_event(GameEvent::Confirmation).unwrap();
+        let view = feed.update(&game).unwrap();
+        assert_eq!(view.sequence, sequence - 1);
+        assert_eq!(view.state, GameState::Discard);
+        assert_eq!(view.starter_card, None);
+        assert_eq!(feed.update(&game).unwrap().sequence, sequence - 1);
+    }
+
+    #[test]
+    fn hand_is_revealed_after_the_crib() {
+        let mut game = set_up_game();
+        let hands: Vec<Vec<crate::deck::Card>> = game
+            .players()
+            .iter()
+            .map(|player| player.hand.clone())
+            .collect();
+        play_to(&mut game, GameState::CribScore);
+        assert_eq!(game.hand_reveal(), None);
+
+        let crib = game.crib().to_vec();
+        let starter_card = game.starter_card().unwrap();
+        let dealer = game.index_dealer().unwrap();
+        game.process_event(GameEvent::Confirmation).unwrap();
+        let reveal = game.hand_reveal().unwrap();
+        assert_eq!(reveal.index_dealer, dealer);
+        assert_eq!(reveal.starter_card, starter_card);
+        assert_eq!(reveal.crib, crib);
+        // With three players the crib is dealt a card from the deck besides the discards
+        for (index, hand) in hands.iter().enumerate() {
+            assert_eq!(reveal.discards[index], hand[..1]);
+            assert_eq!(reveal.hands[index], hand[1..]);
+            assert!(reveal.crib.contains(&hand[0]));
+        }
+    }
+
+    // A game won while counting the hands still reveals the hand it was won in rather than leaving
+    // the reveal of the hand before
+    #[test]
+    fn hand_is_revealed_when_won_in_the_show() {
+        let mut game = set_up_game();
+        play_to(&mut game, GameState::Deal);
+        assert!(game.hand_reveal().is_some());
+
+        play_to(&mut game, GameState::ShowScore);
+        for player in game.game.players.iter_mut() {
+            player.front_peg_pos = 120;
+        }
+        let hands: Vec<Vec<crate::deck::Card>> = game
+            .players()
+            .iter()
+            .map(|player| player.hand.clone())
+            .collect();
+        let starter_card = game.starter_card().unwrap();
+        while game.state() == GameState::ShowScore {
+            game.process_event(GameEvent::Confirmation).unwrap();
+        }
+        assert_eq!(game.state(), GameState::Win);
+
+        let reveal = game.hand_reveal().unwrap();
+        assert_eq!(reveal.starter_card, starter_card);
+        assert_eq!(reveal.hands, hands);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SeatView {
+    // The seat the view is for
+    pub seat: u8,
+    // The sequence number of the last event accepted before the view was taken
+    pub sequence: u64,
+    pub state: crate::GameState,
+    pub settings: Option<settings::GameSettings>,
+    pub index_dealer: Option<u8>,
+    pub index_active: Option<u8>,
+    pub starter_card: Option<deck::Card>,
+    // Every player at the table by player index
+    pub players: Vec<PlayerView>,
+    pub crib_size: u8,
+    // The crib once it has been turned over to be counted
+    pub crib: Option<Vec<deck::Card>>,
+    pub play_groups: Vec<crate::PlayGroup>,
+    pub auction: Option<crate::Auction>,
+    pub match_standings: standings::MatchStandings,
+    // The milliseconds left for the decision being waited on when it has a time limit
+    pub time_remaining: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlayerView {
+    pub back_peg_pos: u8,
+    pub front_peg_pos: u8,
+    pub partner_index: Option<u8>,
+    // The number of cards held including those already played
+    pub num_cards: u8,
+    // The player's hand when the seat may see it
+    pub hand: Option<Vec<deck::Card>>,
+}
+
+// How much of the hands a spectator sees
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SpectatorVisibility {
+    // Every hand and the crib as soon as they are dealt and laid away, as for commentary on a
+    // delayed stream
+    AllHands,
+    // Only what the players see of each other: each hand as it is counted in the show and the crib
+    // once it is turned over
+    NoHands,
+    // Nothing of the hands during the play, then every hand and the crib once the show begins
+    DelayedReveal,
+}
+
+// The game as seen from outside the table
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpectatorView {
+    pub visibility: SpectatorVisibility,
+    pub sequence: u64,
+    pub state: crate::GameState,
+    pub settings: Option<settings::GameSettings>,
+    pub index_dealer: Option<u8>,
+    pub index_active: Option<u8>,
+    pub starter_card: Option<deck::Card>,
+    pub players: Vec<PlayerView>,
+    pub crib_size: u8,
+    pub crib: Option<Vec<deck::Card>>,
+    pub play_groups: Vec<crate::PlayGroup>,
+    pub auction: Option<crate::Auction>,
+    pub match_standings: standings::MatchStandings,
+    pub time_remaining: Option<u64>,
+}
+
+// Holds spectator views back by a number of accepted events such that a stream of the game runs
+// behind the table
+#[derive(Debug, Clone)]
+pub struct SpectatorFeed {
+    visibility: SpectatorVisibility,
+    delay: usize,
+    views: VecDeque<SpectatorView>,
+}
+
+impl SpectatorFeed {
+    pub fn new(visibility: SpectatorVisibility, delay: usize) -> SpectatorFeed {
+        SpectatorFeed {
+            visibility,
+            delay,
+            views: VecDeque::with_capacity(delay + 1),
+        }
+    }
+
+    // Takes the game after an event and returns the view which is the delay's number of events
+    // behind it, or None until that many events have been accepted; the game may be given more
+    // than once per event
+    pub fn update(&mut self, game: &crate::Game) -> Option<&SpectatorView> {
+        let is_new = self
+            .views
+            .back()
+            .map_or(true, |view| view.sequence < game.sequence());
+        if is_new {
+            self.views.push_back(game.spectator_view(self.visibility));
+            if self.views.len() > self.delay + 1 {
+                self.views.pop_front();
+            }
+        }
+
+        if self.views.len() > self.delay {
+            self.views.front()
+        } else {
+            None
+        }
+    }
+}
+
+// Every card of a hand once it has ended with its crib scored or a player winning, for reviewing
+// the hand afterwards
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HandReveal {
+    // The player whose crib it was
+    pub index_dealer: u8,
+    pub starter_card: deck::Card,
+    // The cards each player kept by player index
+    pub hands: Vec<Vec<deck::Card>>,
+    // The cards each player laid away by player index
+    pub discards: Vec<Vec<deck::Card>>,
+    // The crib including any card dealt to it from the deck
+    pub crib: Vec<deck::Card>,
+}
+
+// Returns every card of the current hand, or None when the hand ended before the starter was cut
+pub(crate) fn hand_reveal(game: &crate::GameImpl) -> Option<HandReveal> {
+    Some(HandReveal {
+        index_dealer: game.index_dealer?,
+        starter_card: game.starter_card?,
+        hands: game
+            .players
+            .iter()
+            .map(|player| player.hand.clone())
+            .collect(),
+        discards: game.discards.clone(),
+        crib: game.crib.clone(),
+    })
+}
+
+pub(crate) fn seat_view(game: &crate::GameImpl, seat: u8) -> SeatView {
+    SeatView {
+        seat,
+        sequence: game.event_log.sequence,
+        state: game.state,
+        settings: game.settings,
+        index_dealer: game.index_dealer,
+        index_active: game.index_active,
+        starter_card: game.starter_card,
+        players: player_views(game, |index| index == seat || is_hand_revealed(game, index)),
+        crib_size: game.crib.len() as u8,
+        crib: if is_crib_revealed(game) {
+            Some(game.crib.clone())
+        } else {
+            None
+        },
+        play_groups: game.play_groups.clone(),
+        auction: game.auction.clone(),
+        match_standings: game.match_standings.clone(),
+        time_remaining: crate::state_logic::timeout::time_remaining(game),
+    }
+}
+
+pub(crate) fn spectator_view(
+    game: &crate::GameImpl,
+    visibility: SpectatorVisibility,
+) -> SpectatorView {
+    let is_show = is_crib_revealed(game)
+        || matches!(
+            game.state,
+            crate::GameState::ShowScore | crate::GameState::ShowMuggins
+        );
+    let (is_hand_visible, is_crib_visible): (Box<dyn Fn(u8) -> bool>, bool) = match visibility {
+        SpectatorVisibility::AllHands => (Box::new(|_| true), true),
+        SpectatorVisibility::NoHands => (
+            Box::new(|index| is_hand_revealed(game, index)),
+            is_crib_revealed(game),
+        ),
+        SpectatorVisibility::DelayedReveal => (Box::new(move |_| is_show), is_show),
+    };
+
+    SpectatorView {
+        visibility,
+        sequence: game.event_log.sequence,
+        state: game.state,
+        settings: game.settings,
+        index_dealer: game.index_dealer,
+        index_active: game.index_active,
+        starter_card: game.starter_card,
+        players: player_views(game, is_hand_visible),
+        crib_size: game.crib.len() as u8,
+        crib: if is_crib_visible {
+            Some(game.crib.clone())
+        } else {
+            None
+        },
+        play_groups: game.play_groups.clone(),
+        auction: game.auction.clone(),
+        match_standings: game.match_standings.clone(),
+        time_remaining: crate::state_logic::timeout::time_remaining(game),
+    }
+}
+
+fn player_views(game: &crate::GameImpl, is_hand_visible: impl Fn(u8) -> bool) -> Vec<PlayerView> {
+    game.players
+        .iter()
+        .enumerate()
+        .map(|(index, player)| PlayerView {
+            back_peg_pos: player.back_peg_pos,
+            front_peg_pos: player.front_peg_pos,
+            partner_index: player.partner_index,
+            num_cards: player.hand.len() as u8,
+            hand: if is_hand_visible(index as u8) {
+                Some(player.hand.clone())
+            } else {
+                None
+            },
+        })
+        .collect()
+}
+
+// Returns whether a player's hand has been laid down in the show; hands are counted in turn from
+// the dealer's left with the dealer's last
+pub(crate) fn is_hand_revealed(game: &crate::GameImpl, player_index: u8) -> bool {
+    let num_players = game.players.len() as u8;
+    let (index_dealer, index_active) = match (game.index_dealer, game.index_active) {
+        (Some(index_dealer), Some(index_active)) => (index_dealer, index_active),
+        _ => return false,
+    };
+    // The distance of a player to the left of the first player to be counted
+    let order = |index: u8| (index + num_players - index_dealer - 1) % num_players;
+
+    match game.state {
+        crate::GameState::ShowScore | crate::GameState::ShowMuggins => {
+            order(player_index) <= order(index_active)
+        }
+        crate::GameState::CribSort
+        | crate::GameState::CribScore
+        | crate::GameState::CribMuggins => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn is_crib_revealed(game: &crate::GameImpl) -> bool {
+    matches!(
+        game.state,
+        crate::GameState::CribSort | crate::GameState::CribScore | crate::GameState::CribMuggins
+    )
+}